@@ -1,5 +1,6 @@
 import { Component, Show, createSignal, onMount, onCleanup } from 'solid-js'
 import { invoke } from '@tauri-apps/api/core'
+import { listen, UnlistenFn } from '@tauri-apps/api/event'
 import toast from 'solid-toast'
 import TitleBar from './components/TitleBar'
 import Header from './components/Header'
@@ -13,6 +14,7 @@ import { ThemeProvider } from './providers/ThemeProvider'
 import { ServiceProvider, useServices } from './providers/ServiceProvider'
 import { NavigationProvider, useNavigation } from './providers/NavigationProvider'
 import { EventProvider } from './providers/EventProvider'
+import { errorMessage, PackageProgress, ServicePackage } from './types/service'
 
 const AppContent: Component = () => {
   const { 
@@ -137,17 +139,42 @@ const AppContent: Component = () => {
     setShowRemovalDialog(true)
   }
 
-  const handleRemovalConfirm = (serviceName: string) => {
+  const handleRemovalConfirm = async (serviceName: string) => {
+    // Show exactly which package gets purged before asking for the password
+    let resolved: ServicePackage
+    try {
+      resolved = await invoke<ServicePackage>('get_service_package', { serviceName })
+    } catch (error) {
+      toast.error(`Cannot remove "${serviceName}": ${errorMessage(error)}`, {
+        duration: 6000,
+        icon: '❌'
+      })
+      setShowRemovalDialog(false)
+      return
+    }
+    if (!window.confirm(`This will uninstall the package "${resolved.package}" (${resolved.unit_file}) with ${resolved.package_manager}. Continue?`)) {
+      setShowRemovalDialog(false)
+      return
+    }
+
     setCurrentAuthAction({
       action: 'remove',
       serviceName,
       callback: async (password: string) => {
+        // Show the package manager's latest output line while the removal runs
+        const progressToast = toast.loading(`Removing "${serviceName}"...`)
+        let unlisten: UnlistenFn | undefined
         try {
+          unlisten = await listen<PackageProgress>('remove-progress', (event) => {
+            toast.loading(event.payload.line, { id: progressToast })
+          })
           const result = await invoke<string>('remove_service', { 
             serviceName, 
+            package: resolved.package,
             password 
           })
           console.log('Removal result:', result)
+          toast.dismiss(progressToast)
           toast.success(`Service "${serviceName}" removed successfully`, {
             duration: 4000,
             icon: '🗑️'
@@ -156,10 +183,13 @@ const AppContent: Component = () => {
           setTimeout(() => refreshServices(), 2000)
         } catch (error) {
           console.error('Removal failed:', error)
-          toast.error(`Failed to remove service "${serviceName}": ${error}`, {
+          toast.dismiss(progressToast)
+          toast.error(`Failed to remove service "${serviceName}": ${errorMessage(error)}`, {
             duration: 6000,
             icon: '❌'
           })
+        } finally {
+          unlisten?.()
         }
       }
     })
@@ -0,0 +1,84 @@
+use chrono::{DateTime, Utc};
+use log::{Level, Log, Metadata, Record};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::{Mutex, OnceLock};
+
+// Oldest records are dropped once the buffer holds this many
+const APP_LOG_CAPACITY: usize = 2000;
+
+static APP_LOG_BUFFER: OnceLock<Mutex<VecDeque<AppLogEntry>>> = OnceLock::new();
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppLogEntry {
+    pub timestamp: DateTime<Utc>,
+    pub level: String,
+    pub module: Option<String>,
+    pub message: String,
+}
+
+// Writes to stderr through env_logger as before and keeps a copy of every record that
+// passes the filter, so a packaged build can still show what the manager did
+struct BufferedLogger {
+    inner: env_logger::Logger,
+}
+
+impl Log for BufferedLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.inner.enabled(metadata)
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.inner.matches(record) {
+            return;
+        }
+        self.inner.log(record);
+
+        let entry = AppLogEntry {
+            timestamp: Utc::now(),
+            level: record.level().to_string(),
+            module: record.module_path().map(|module| module.to_string()),
+            message: record.args().to_string(),
+        };
+        let mut buffer = buffer().lock().unwrap_or_else(|e| e.into_inner());
+        if buffer.len() == APP_LOG_CAPACITY {
+            buffer.pop_front();
+        }
+        buffer.push_back(entry);
+    }
+
+    fn flush(&self) {
+        self.inner.flush();
+    }
+}
+
+fn buffer() -> &'static Mutex<VecDeque<AppLogEntry>> {
+    APP_LOG_BUFFER.get_or_init(|| Mutex::new(VecDeque::with_capacity(APP_LOG_CAPACITY)))
+}
+
+// Installs the global logger; RUST_LOG still controls the filter, defaulting to info
+pub fn init() {
+    let inner = env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info"))
+        .format_timestamp_millis()
+        .format_module_path(false)
+        .build();
+    let max_level = inner.filter();
+
+    if log::set_boxed_logger(Box::new(BufferedLogger { inner })).is_ok() {
+        log::set_max_level(max_level);
+    }
+}
+
+// Returns up to `limit` of the newest records at `min_level` or more severe, oldest first
+pub fn recent(min_level: Level, limit: usize) -> Vec<AppLogEntry> {
+    let buffer = buffer().lock().unwrap_or_else(|e| e.into_inner());
+    let mut entries: Vec<AppLogEntry> = buffer
+        .iter()
+        .rev()
+        .filter(|entry| entry.level.parse::<Level>().map(|level| level <= min_level).unwrap_or(true))
+        .take(limit)
+        .cloned()
+        .collect();
+    entries.reverse();
+    entries
+}
@@ -69,6 +69,24 @@ const ServiceCard: Component<ServiceCardProps> = (props) => {
           health: 'text-muted-foreground',
           variant: 'secondary' as const
         }
+      case 'Activating':
+      case 'Deactivating':
+      case 'Reloading':
+        return {
+          color: 'status-indicator unknown',
+          icon: '◐',
+          text: props.service.status,
+          health: 'text-warning',
+          variant: 'warning' as const
+        }
+      case 'SocketActivated':
+        return {
+          color: 'status-indicator stopped',
+          icon: '◌',
+          text: 'Waiting for connection',
+          health: 'text-muted-foreground',
+          variant: 'secondary' as const
+        }
       case 'Failed':
         return {
           color: 'status-indicator failed',
@@ -114,7 +132,7 @@ const ServiceCard: Component<ServiceCardProps> = (props) => {
       <div class="flex items-start justify-between mb-4">
         <div class="flex-1 min-w-0">
           <div class="flex items-center space-x-3 mb-2">
-            <h3 class="font-semibold text-foreground text-lg truncate">{props.service.name}</h3>
+            <h3 class="font-semibold text-foreground text-lg truncate" title={props.service.name}>{props.service.display_name ?? props.service.name}</h3>
             <button
               onClick={() => toggleSelection(props.service.name)}
               onKeyDown={(e) => handleKeyDown(e, () => toggleSelection(props.service.name))}
@@ -1,6 +1,6 @@
-import { Component, createSignal, Show, onMount } from 'solid-js'
+import { Component, createSignal, Show, onMount, onCleanup } from 'solid-js'
 import { invoke } from '@tauri-apps/api/core'
-import { Service, SystemMetrics } from '../types/service'
+import { Service, SystemMetrics, errorMessage, isCommandError } from '../types/service'
 import { useServices } from '../providers/ServiceProvider'
 import { useNavigation } from '../providers/NavigationProvider'
 import toast from 'solid-toast'
@@ -40,22 +40,39 @@ const ServiceDetailPage: Component = () => {
   if (!service()) return null
 
 
+  // Id of the metrics request in flight, so leaving the page can cancel it
+  let metricsRequestId: string | null = null
+
   const loadMetrics = async () => {
     console.log('Loading metrics for service:', service().name)
     setMetricsLoading(true)
+    const requestId = crypto.randomUUID()
+    metricsRequestId = requestId
     try {
       const result = await invoke<SystemMetrics>('get_service_metrics', {
-        serviceName: service().name
+        serviceName: service().name,
+        requestId
       })
       console.log('Loaded metrics:', result)
       setMetrics(result)
     } catch (error) {
-      console.error('Failed to load metrics:', error)
+      if (!(isCommandError(error) && error.code === 'Cancelled')) {
+        console.error('Failed to load metrics:', error)
+      }
     } finally {
+      if (metricsRequestId === requestId) {
+        metricsRequestId = null
+      }
       setMetricsLoading(false)
     }
   }
 
+  onCleanup(() => {
+    if (metricsRequestId) {
+      invoke('cancel_request', { requestId: metricsRequestId }).catch(() => {})
+    }
+  })
+
   const refreshServiceStatus = async () => {
     if (!service()) return
     setStatusUpdating(true)
@@ -108,7 +125,7 @@ const ServiceDetailPage: Component = () => {
       
       // Log only the command and error to terminal (no custom wrapper messages)
       if (typeof window !== 'undefined' && window.logServiceAction) {
-        window.logServiceAction(action, command, String(error), 1, 0)
+        window.logServiceAction(action, command, errorMessage(error), 1, 0)
       }
       
       // Even if action failed, try to refresh to get current state
@@ -162,14 +179,14 @@ const ServiceDetailPage: Component = () => {
       console.error(`Failed to get service status:`, error)
       
       // Show error toast
-      toast.error(`Status command failed: ${error}`, {
+      toast.error(`Status command failed: ${errorMessage(error)}`, {
         duration: 4000,
         icon: '❌'
       })
       
       // Log the failed status command to terminal
       if (typeof window !== 'undefined' && window.logServiceAction) {
-        window.logServiceAction('status', command, String(error), 1, 0)
+        window.logServiceAction('status', command, errorMessage(error), 1, 0)
       }
     } finally {
       setActionLoading(null)
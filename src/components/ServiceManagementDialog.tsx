@@ -1,4 +1,4 @@
-import { Component, createSignal, Show, For, onMount } from 'solid-js'
+import { Component, createSignal, Show, For, onMount, onCleanup } from 'solid-js'
 import { invoke } from '@tauri-apps/api/core'
 import Button from './ui/Button'
 import {
@@ -27,6 +27,7 @@ import {
   Video,
   Wrench
 } from 'lucide-solid'
+import { isCommandError } from '../types/service'
 
 interface SystemService {
   name: string
@@ -35,6 +36,12 @@ interface SystemService {
   category: string
   enabled: boolean
   description: string
+  load_state: string
+  active_state: string
+  sub_state: string
+  last_started: string | null
+  uptime_secs: number | null
+  scope: 'System' | 'User'
 }
 
 interface TrackedService {
@@ -45,6 +52,13 @@ interface TrackedService {
   category: string
   enabled: boolean
   auto_start: boolean
+  sort_order: number
+  tags: string[]
+  notes?: string
+  last_status?: string
+  last_checked?: string
+  log_flood_threshold?: number
+  critical: boolean
   created_at: string
   updated_at: string
 }
@@ -115,18 +129,34 @@ const ServiceManagementDialog: Component<ServiceManagementDialogProps> = (props)
     }
   }
 
+  // Id of the service listing in flight, so closing the dialog can cancel it
+  let listRequestId: string | null = null
+
   const loadSystemServices = async () => {
     setLoading(true)
+    const requestId = crypto.randomUUID()
+    listRequestId = requestId
     try {
-      const services = await invoke<SystemService[]>('get_all_system_services')
+      const services = await invoke<SystemService[]>('get_all_system_services', { requestId })
       setSystemServices(services)
     } catch (error) {
-      console.error('Failed to load system services:', error)
+      if (!(isCommandError(error) && error.code === 'Cancelled')) {
+        console.error('Failed to load system services:', error)
+      }
     } finally {
+      if (listRequestId === requestId) {
+        listRequestId = null
+      }
       setLoading(false)
     }
   }
 
+  onCleanup(() => {
+    if (listRequestId) {
+      invoke('cancel_request', { requestId: listRequestId }).catch(() => {})
+    }
+  })
+
   const loadTrackedServices = async () => {
     try {
       const services = await invoke<TrackedService[]>('get_tracked_services')
@@ -1,5 +1,5 @@
 import { Component, Show, createSignal, onMount } from 'solid-js'
-import { SystemMetrics, Service } from '../types/service'
+import { SystemMetrics, Service, ServiceLimits } from '../types/service'
 import MetricCard from './MetricCard'
 import { invoke } from '@tauri-apps/api/core'
 import {
@@ -7,7 +7,9 @@ import {
   Server,
   Clock,
   Globe,
-  Hash
+  Hash,
+  Gauge,
+  FileText
 } from 'lucide-solid'
 
 interface ServiceMetricsProps {
@@ -19,6 +21,7 @@ interface ServiceMetricsProps {
 const ServiceMetrics: Component<ServiceMetricsProps> = (props) => {
   const [serviceInfo, setServiceInfo] = createSignal<any>(null)
   const [portInfo, setPortInfo] = createSignal<string[]>([])
+  const [limits, setLimits] = createSignal<ServiceLimits | null>(null)
 
 
 
@@ -59,9 +62,44 @@ const ServiceMetrics: Component<ServiceMetricsProps> = (props) => {
     }
   }
 
+  const loadLimits = async () => {
+    // Containers have no systemd limits to show
+    if (props.service.name.startsWith('docker:')) return
+    try {
+      const result = await invoke<ServiceLimits>('get_service_limits', { serviceName: props.service.name })
+      setLimits(result)
+    } catch (error) {
+      console.error('Failed to load resource limits:', error)
+      setLimits(null)
+    }
+  }
+
+  const formatBytes = (bytes: number) => {
+    const units = ['B', 'KiB', 'MiB', 'GiB', 'TiB']
+    let value = bytes
+    let unit = 0
+    while (value >= 1024 && unit < units.length - 1) {
+      value /= 1024
+      unit++
+    }
+    return unit === 0 ? `${value} B` : `${value.toFixed(1)} ${units[unit]}`
+  }
+
+  const percentOf = (used: number, limit: number) => (limit > 0 ? (used / limit) * 100 : 0)
+
+  // Bars turn amber and then red as usage closes in on the cap
+  const limitColor = (percent: number) => (percent >= 90 ? 'red' : percent >= 70 ? 'amber' : 'green')
+
+  const hasLimits = () => {
+    const current = limits()
+    return !!current && [current.memory_max, current.cpu_quota_percent, current.tasks_max, current.open_files_max]
+      .some(limit => limit !== null)
+  }
+
   onMount(() => {
     loadServiceInfo()
     loadPortInfo()
+    loadLimits()
   })
 
 
@@ -184,6 +222,78 @@ const ServiceMetrics: Component<ServiceMetricsProps> = (props) => {
               </Show>
             </div>
           </div>
+
+          {/* Usage against the unit's configured limits */}
+          <Show when={hasLimits()}>
+            <div>
+              <h3 class="text-lg font-semibold text-foreground mb-4 flex items-center">
+                <Gauge class="w-5 h-5 mr-2" />
+                Resource Limits
+              </h3>
+              <div class="grid grid-cols-1 md:grid-cols-2 lg:grid-cols-4 gap-6">
+                <Show when={limits()?.memory_max}>
+                  {(memoryMax) => {
+                    const percent = () => percentOf(props.metrics!.memory_usage, memoryMax())
+                    return (
+                      <MetricCard
+                        icon={Server}
+                        title="Memory"
+                        value={percent()}
+                        unit="%"
+                        color={limitColor(percent())}
+                        progress={percent()}
+                        subtitle={`${props.metrics!.display.memory_usage_human} of ${formatBytes(memoryMax())}`}
+                      />
+                    )
+                  }}
+                </Show>
+                <Show when={limits()?.cpu_quota_share}>
+                  {(share) => {
+                    // cpu_usage is a share of all cores, so compare it with the quota on that scale
+                    const percent = () => percentOf(props.metrics!.cpu_usage, share())
+                    return (
+                      <MetricCard
+                        icon={Activity}
+                        title="CPU Quota"
+                        value={percent()}
+                        unit="%"
+                        color={limitColor(percent())}
+                        progress={percent()}
+                        subtitle={`${props.metrics!.cpu_usage.toFixed(1)}% of ${share().toFixed(1)}% of total CPU (quota ${limits()!.cpu_quota_percent}%)`}
+                      />
+                    )
+                  }}
+                </Show>
+                <Show when={limits()?.tasks_max}>
+                  {(tasksMax) => {
+                    const percent = () => percentOf(props.metrics!.process_count, tasksMax())
+                    return (
+                      <MetricCard
+                        icon={Hash}
+                        title="Tasks"
+                        value={percent()}
+                        unit="%"
+                        color={limitColor(percent())}
+                        progress={percent()}
+                        subtitle={`${props.metrics!.process_count} of ${tasksMax()} tasks`}
+                      />
+                    )
+                  }}
+                </Show>
+                <Show when={limits()?.open_files_max}>
+                  {(openFilesMax) => (
+                    <MetricCard
+                      icon={FileText}
+                      title="Open Files"
+                      value={props.metrics!.open_files}
+                      color="blue"
+                      subtitle={`Limit of ${openFilesMax()} per process`}
+                    />
+                  )}
+                </Show>
+              </div>
+            </div>
+          </Show>
         </Show>
       </div>
     </Show>
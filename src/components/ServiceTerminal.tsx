@@ -1,6 +1,6 @@
 import { Component, createSignal, Show, onMount, For, createEffect, onCleanup } from 'solid-js'
 import { invoke } from '@tauri-apps/api/core'
-import { Service } from '../types/service'
+import { Service, errorMessage } from '../types/service'
 import HighlightedCommand from './HighlightedCommand'
 import {
   Terminal,
@@ -230,7 +230,7 @@ const ServiceTerminal: Component<ServiceTerminalProps> = (props) => {
         addEntry('error', `Command failed with exit code: ${result.exit_code}`)
       }
     } catch (error) {
-      addEntry('error', `Command execution failed: ${error}`)
+      addEntry('error', `Command execution failed: ${errorMessage(error)}`)
     }
   }
 
@@ -247,7 +247,7 @@ const ServiceTerminal: Component<ServiceTerminalProps> = (props) => {
         await executeCustomCommand(cmd)
       }
     } catch (error) {
-      addEntry('error', `Failed to get real-time service info: ${error}`)
+      addEntry('error', `Failed to get real-time service info: ${errorMessage(error)}`)
     }
   }
 
@@ -0,0 +1,94 @@
+use notify_debouncer_mini::notify::{RecommendedWatcher, RecursiveMode};
+use notify_debouncer_mini::{new_debouncer, DebounceEventResult, Debouncer};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::AppHandle;
+use tokio::sync::{mpsc, Mutex};
+use crate::events::{emit_service_event, ServiceEvent};
+use crate::services::restart_local_service;
+
+// Editors tend to write a file in several steps, so changes are batched for this long
+const DEBOUNCE_SECS: u64 = 1;
+
+pub struct ConfigWatch {
+    pub path: PathBuf,
+    // Dropping the debouncer stops its thread and ends the restart task
+    _debouncer: Debouncer<RecommendedWatcher>,
+}
+
+// One watch per service, keyed by service name
+pub type ConfigWatches = Arc<Mutex<HashMap<String, ConfigWatch>>>;
+
+impl ConfigWatch {
+    // Restarts the service whenever the file, or anything under the directory, changes
+    pub fn start(app_handle: AppHandle, service_name: String, path: &Path) -> Result<Self, String> {
+        let path = path
+            .canonicalize()
+            .map_err(|e| format!("Cannot watch {}: {}", path.display(), e))?;
+
+        // Editors often save by replacing the file, which drops an inotify watch on the
+        // file itself, so files are watched through their parent directory
+        let (watch_path, file_filter) = if path.is_dir() {
+            (path.clone(), None)
+        } else {
+            let parent = path
+                .parent()
+                .ok_or_else(|| format!("Cannot watch {}: no parent directory", path.display()))?;
+            (parent.to_path_buf(), Some(path.clone()))
+        };
+
+        let (tx, mut rx) = mpsc::unbounded_channel::<()>();
+        let mut debouncer = new_debouncer(Duration::from_secs(DEBOUNCE_SECS), move |result: DebounceEventResult| {
+            match result {
+                Ok(events) => {
+                    let relevant = events.iter().any(|event| match &file_filter {
+                        Some(file) => &event.path == file,
+                        None => true,
+                    });
+                    if relevant {
+                        let _ = tx.send(());
+                    }
+                }
+                Err(e) => log::warn!("⚠️ Config watcher error: {}", e),
+            }
+        })
+        .map_err(|e| format!("Failed to create config watcher: {}", e))?;
+
+        let recursive_mode = if path.is_dir() { RecursiveMode::Recursive } else { RecursiveMode::NonRecursive };
+        debouncer
+            .watcher()
+            .watch(&watch_path, recursive_mode)
+            .map_err(|e| format!("Failed to watch {}: {}", path.display(), e))?;
+
+        let reported_path = path.display().to_string();
+        tokio::spawn(async move {
+            while rx.recv().await.is_some() {
+                log::info!("📝 Config for {} changed, restarting", service_name);
+                let (success, message) = match restart_local_service(&service_name).await {
+                    Ok(operation) => (operation.success, operation.message),
+                    Err(e) => (false, e.to_string()),
+                };
+                if !success {
+                    log::warn!("⚠️ Restart after config change failed: {}", message);
+                }
+
+                let event = ServiceEvent::ConfigReloaded {
+                    service_name: service_name.clone(),
+                    path: reported_path.clone(),
+                    success,
+                    message,
+                    timestamp: chrono::Utc::now().to_rfc3339(),
+                };
+                if let Err(e) = emit_service_event(&app_handle, &event) {
+                    log::error!("❌ Failed to emit config reloaded event: {}", e);
+                }
+            }
+            log::debug!("🛑 Config watch for {} stopped", service_name);
+        });
+
+        log::info!("👀 Watching {} for changes", path.display());
+        Ok(Self { path, _debouncer: debouncer })
+    }
+}
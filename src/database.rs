@@ -1,7 +1,9 @@
-use sqlx::{Pool, Sqlite, SqlitePool, Row};
+use sqlx::{Connection, Pool, Sqlite, SqliteConnection, SqlitePool, Row};
+use sqlx::sqlite::SqliteConnectOptions;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
-use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use chrono::{DateTime, SecondsFormat, Utc};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TrackedService {
@@ -12,6 +14,18 @@ pub struct TrackedService {
     pub category: String,
     pub enabled: bool,
     pub auto_start: bool,
+    pub sort_order: i64,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub notes: Option<String>,
+    // Last status the monitor saw, so the UI has something to show before the first poll
+    pub last_status: Option<String>,
+    pub last_checked: Option<DateTime<Utc>>,
+    // Journal entries per second above which the monitor reports a log flood
+    pub log_flood_threshold: Option<f64>,
+    // Stopping or restarting needs an explicit confirmation
+    #[serde(default)]
+    pub critical: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -24,17 +38,88 @@ pub struct ServiceConfig {
     pub config_type: String, // string, number, boolean, json
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Host {
+    pub id: i64,
+    pub name: String,
+    pub address: String,
+    pub user: String,
+    pub port: u16,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnoozedAlert {
+    pub service_name: String,
+    pub until: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+// One metrics sample the monitor recorded for a service
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricsSnapshot {
+    pub service_name: String,
+    pub cpu_usage: f32,
+    pub memory_usage: u64,
+    pub network_in: u64,
+    pub network_out: u64,
+    pub disk_read: u64,
+    pub disk_write: u64,
+    pub recorded_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DatabaseBackup {
+    pub path: String,
+    pub size_bytes: u64,
+    pub schema_version: i64,
+    pub timestamp: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DatabaseIntegrity {
+    pub ok: bool,
+    pub problems: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DatabaseVacuum {
+    pub size_before: u64,
+    pub size_after: u64,
+}
+
+// Bumped whenever run_migrations changes the schema; stored in PRAGMA user_version
+pub const SCHEMA_VERSION: i64 = 11;
+
+// Environment variable and command line flag that relocate the database
+pub const DATA_DIR_ENV: &str = "DSM_DATA_DIR";
+const DATA_DIR_FLAG: &str = "--data-dir";
+
+// Columns whose defaults used to be written in SQLite's datetime('now') format
+const TIMESTAMP_COLUMNS: [(&str, &str); 8] = [
+    ("tracked_services", "created_at"),
+    ("tracked_services", "updated_at"),
+    ("service_configs", "created_at"),
+    ("service_configs", "updated_at"),
+    ("service_tags", "created_at"),
+    ("hosts", "created_at"),
+    ("snoozed_alerts", "created_at"),
+    ("app_settings", "updated_at"),
+];
+
+// Tables a backup must contain before it can be restored
+const REQUIRED_TABLES: [&str; 2] = ["tracked_services", "service_configs"];
+
 pub struct Database {
     pool: Pool<Sqlite>,
+    path: PathBuf,
 }
 
 impl Database {
     pub async fn new() -> Result<Self, sqlx::Error> {
         log::info!("🗄️ Initializing database connection");
         
-        let data_dir = dirs::data_dir()
-            .unwrap_or_else(|| PathBuf::from("."))
-            .join("dev-services-manager");
+        let data_dir = Self::data_dir();
         
         log::debug!("📁 Data directory: {:?}", data_dir);
         
@@ -48,22 +133,9 @@ impl Database {
         }
         
         let db_path = data_dir.join("services.db");
-        let database_url = format!("sqlite://{}?mode=rwc", db_path.display());
+        let pool = Self::connect(&db_path).await?;
         
-        log::debug!("🔗 Database URL: {}", database_url);
-        
-        let pool = match SqlitePool::connect(&database_url).await {
-            Ok(pool) => {
-                log::info!("✅ Database connection pool created successfully");
-                pool
-            }
-            Err(e) => {
-                log::error!("❌ Failed to create database connection pool: {}", e);
-                return Err(e);
-            }
-        };
-        
-        let db = Database { pool };
+        let db = Database { pool, path: db_path };
         
         log::info!("🔄 Running database migrations");
         match db.run_migrations().await {
@@ -78,6 +150,47 @@ impl Database {
         Ok(db)
     }
     
+    // --data-dir or DSM_DATA_DIR override the platform default; an override that can't be
+    // written to is ignored so a bad setting doesn't stop the app from starting
+    fn data_dir() -> PathBuf {
+        let default_dir = dirs::data_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("dev-services-manager");
+        
+        let Some(override_dir) = data_dir_override() else {
+            return default_dir;
+        };
+        
+        match ensure_writable_dir(&override_dir) {
+            Ok(()) => {
+                log::info!("📁 Using data directory override: {}", override_dir.display());
+                override_dir
+            }
+            Err(e) => {
+                log::warn!("⚠️ Data directory {} is not writable ({}), using {}",
+                    override_dir.display(), e, default_dir.display());
+                default_dir
+            }
+        }
+    }
+    
+    async fn connect(db_path: &Path) -> Result<Pool<Sqlite>, sqlx::Error> {
+        let database_url = format!("sqlite://{}?mode=rwc", db_path.display());
+        
+        log::debug!("🔗 Database URL: {}", database_url);
+        
+        match SqlitePool::connect(&database_url).await {
+            Ok(pool) => {
+                log::info!("✅ Database connection pool created successfully");
+                Ok(pool)
+            }
+            Err(e) => {
+                log::error!("❌ Failed to create database connection pool: {}", e);
+                Err(e)
+            }
+        }
+    }
+    
     async fn run_migrations(&self) -> Result<(), sqlx::Error> {
         log::debug!("🔄 Creating tracked_services table");
         
@@ -92,14 +205,38 @@ impl Database {
                 category TEXT NOT NULL DEFAULT 'Other',
                 enabled BOOLEAN NOT NULL DEFAULT 1,
                 auto_start BOOLEAN NOT NULL DEFAULT 0,
-                created_at TEXT NOT NULL DEFAULT (datetime('now')),
-                updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+                sort_order INTEGER NOT NULL DEFAULT 0,
+                notes TEXT,
+                created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+                updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
             )
             "#,
         )
         .execute(&self.pool)
         .await?;
         
+        // Databases created by older versions need later columns added
+        if self.add_column_if_missing("tracked_services", "sort_order", "INTEGER NOT NULL DEFAULT 0").await? {
+            // Backfill positions from the previous alphabetical ordering
+            sqlx::query(
+                r#"
+                UPDATE tracked_services SET sort_order = (
+                    SELECT COUNT(*) FROM tracked_services AS other
+                    WHERE other.display_name < tracked_services.display_name
+                       OR (other.display_name = tracked_services.display_name AND other.id < tracked_services.id)
+                )
+                "#,
+            )
+            .execute(&self.pool)
+            .await?;
+        }
+        
+        self.add_column_if_missing("tracked_services", "notes", "TEXT").await?;
+        self.add_column_if_missing("tracked_services", "last_status", "TEXT").await?;
+        self.add_column_if_missing("tracked_services", "last_checked", "TEXT").await?;
+        self.add_column_if_missing("tracked_services", "log_flood_threshold", "REAL").await?;
+        self.add_column_if_missing("tracked_services", "critical", "BOOLEAN NOT NULL DEFAULT 0").await?;
+        
         log::debug!("🔄 Creating service_configs table");
         
         // Create service_configs table
@@ -111,8 +248,8 @@ impl Database {
                 config_key TEXT NOT NULL,
                 config_value TEXT NOT NULL,
                 config_type TEXT NOT NULL DEFAULT 'string',
-                created_at TEXT NOT NULL DEFAULT (datetime('now')),
-                updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+                created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+                updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
                 UNIQUE(service_name, config_key),
                 FOREIGN KEY(service_name) REFERENCES tracked_services(name)
             )
@@ -121,6 +258,93 @@ impl Database {
         .execute(&self.pool)
         .await?;
         
+        log::debug!("🔄 Creating service_tags table");
+        
+        // Create service_tags table for free-form labels on tracked services
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS service_tags (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                service_name TEXT NOT NULL,
+                tag TEXT NOT NULL,
+                created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+                UNIQUE(service_name, tag),
+                FOREIGN KEY(service_name) REFERENCES tracked_services(name)
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+        
+        log::debug!("🔄 Creating hosts table");
+        
+        // Create hosts table for machines managed over SSH
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS hosts (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT NOT NULL UNIQUE,
+                address TEXT NOT NULL,
+                user TEXT NOT NULL,
+                port INTEGER NOT NULL DEFAULT 22,
+                created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+        
+        log::debug!("🔄 Creating snoozed_alerts table");
+        
+        // Services whose alerts are muted for maintenance until the given time
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS snoozed_alerts (
+                service_name TEXT PRIMARY KEY,
+                until TEXT NOT NULL,
+                created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+        
+        log::debug!("🔄 Creating metrics_history table");
+        
+        // Metrics the monitor collected, kept for before/after comparisons
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS metrics_history (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                service_name TEXT NOT NULL,
+                cpu_usage REAL NOT NULL,
+                memory_usage INTEGER NOT NULL,
+                network_in INTEGER NOT NULL,
+                network_out INTEGER NOT NULL,
+                disk_read INTEGER NOT NULL,
+                disk_write INTEGER NOT NULL,
+                recorded_at TEXT NOT NULL
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+        
+        log::debug!("🔄 Creating app_settings table");
+        
+        // Create app_settings table for application-wide preferences
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS app_settings (
+                key TEXT PRIMARY KEY,
+                value TEXT NOT NULL,
+                updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+        
         log::debug!("🔄 Creating database indexes");
         
         // Create indexes
@@ -132,10 +356,214 @@ impl Database {
             .execute(&self.pool)
             .await?;
         
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_service_tags_tag ON service_tags(tag)")
+            .execute(&self.pool)
+            .await?;
+        
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_metrics_history_service ON metrics_history(service_name, recorded_at)")
+            .execute(&self.pool)
+            .await?;
+        
+        // Older versions defaulted to datetime('now'), which isn't RFC3339
+        for (table, column) in TIMESTAMP_COLUMNS {
+            let updated = sqlx::query(&format!(
+                "UPDATE {table} SET {column} = replace({column}, ' ', 'T') || '.000Z' \
+                 WHERE {column} GLOB '[0-9][0-9][0-9][0-9]-[0-9][0-9]-[0-9][0-9] [0-9][0-9]:[0-9][0-9]:[0-9][0-9]'"
+            ))
+            .execute(&self.pool)
+            .await?
+            .rows_affected();
+            if updated > 0 {
+                log::info!("🔄 Converted {} {}.{} values to RFC3339", updated, table, column);
+            }
+        }
+        
+        // user_version lets restore_database reject backups from a newer app
+        sqlx::query(&format!("PRAGMA user_version = {}", SCHEMA_VERSION))
+            .execute(&self.pool)
+            .await?;
+        
         log::debug!("✅ Database migrations completed");
         Ok(())
     }
     
+    // Round trip to SQLite, for health checks
+    pub async fn ping(&self) -> Result<(), sqlx::Error> {
+        sqlx::query("SELECT 1").execute(&self.pool).await?;
+        Ok(())
+    }
+    
+    // The directory services.db lives in, after any --data-dir override
+    pub fn directory(&self) -> &Path {
+        self.path.parent().unwrap_or(Path::new("."))
+    }
+    
+    // Writes a consistent copy of the live database; VACUUM INTO is safe while connections are open
+    pub async fn backup(&self, target: &Path) -> Result<DatabaseBackup, sqlx::Error> {
+        log::info!("💾 Backing up database to {}", target.display());
+        
+        sqlx::query("VACUUM INTO ?")
+            .bind(target.to_string_lossy().to_string())
+            .execute(&self.pool)
+            .await?;
+        
+        let size_bytes = std::fs::metadata(target).map(|m| m.len()).unwrap_or(0);
+        log::info!("✅ Database backup written ({} bytes)", size_bytes);
+        
+        Ok(DatabaseBackup {
+            path: target.to_string_lossy().to_string(),
+            size_bytes,
+            schema_version: SCHEMA_VERSION,
+            timestamp: Utc::now(),
+        })
+    }
+    
+    pub async fn check_integrity(&self) -> Result<DatabaseIntegrity, sqlx::Error> {
+        log::info!("🩺 Checking database integrity");
+        
+        // integrity_check returns a single "ok" row, or one row per problem found
+        let rows: Vec<String> = sqlx::query_scalar("PRAGMA integrity_check")
+            .fetch_all(&self.pool)
+            .await?;
+        
+        let ok = rows.len() == 1 && rows[0] == "ok";
+        if ok {
+            log::info!("✅ Database integrity check passed");
+        } else {
+            log::warn!("⚠️ Database integrity check found {} problems", rows.len());
+        }
+        
+        Ok(DatabaseIntegrity {
+            ok,
+            problems: if ok { Vec::new() } else { rows },
+        })
+    }
+    
+    // Callers hold the Database mutex, so the monitor can't write while this runs
+    pub async fn vacuum(&self) -> Result<DatabaseVacuum, sqlx::Error> {
+        log::info!("🧹 Vacuuming database");
+        
+        let size_before = std::fs::metadata(&self.path).map(|m| m.len()).unwrap_or(0);
+        sqlx::query("VACUUM")
+            .execute(&self.pool)
+            .await?;
+        let size_after = std::fs::metadata(&self.path).map(|m| m.len()).unwrap_or(0);
+        
+        log::info!("✅ Database vacuumed: {} -> {} bytes", size_before, size_after);
+        Ok(DatabaseVacuum { size_before, size_after })
+    }
+    
+    // Opens a backup read-only and checks it is intact and compatible with this version
+    pub async fn validate_backup(source: &Path) -> Result<i64, String> {
+        let options = SqliteConnectOptions::new()
+            .filename(source)
+            .read_only(true);
+        let mut conn = SqliteConnection::connect_with(&options)
+            .await
+            .map_err(|e| format!("{} is not a readable SQLite database: {}", source.display(), e))?;
+        
+        let result = async {
+            let integrity: String = sqlx::query_scalar("PRAGMA integrity_check")
+                .fetch_one(&mut conn)
+                .await
+                .map_err(|e| format!("{} is not a valid SQLite database: {}", source.display(), e))?;
+            if integrity != "ok" {
+                return Err(format!("{} failed the integrity check: {}", source.display(), integrity));
+            }
+            
+            let version: i64 = sqlx::query_scalar("PRAGMA user_version")
+                .fetch_one(&mut conn)
+                .await
+                .map_err(|e| format!("Failed to read schema version: {}", e))?;
+            if version > SCHEMA_VERSION {
+                return Err(format!(
+                    "Backup schema version {} is newer than this app supports ({})",
+                    version, SCHEMA_VERSION
+                ));
+            }
+            
+            for table in REQUIRED_TABLES {
+                let exists: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = ?")
+                    .bind(table)
+                    .fetch_one(&mut conn)
+                    .await
+                    .map_err(|e| format!("Failed to inspect backup schema: {}", e))?;
+                if exists == 0 {
+                    return Err(format!("Backup is missing the {} table", table));
+                }
+            }
+            
+            Ok(version)
+        }
+        .await;
+        
+        let _ = conn.close().await;
+        result
+    }
+    
+    // Swaps the live database for a validated backup and reconnects the pool
+    pub async fn restore(&mut self, source: &Path) -> Result<DatabaseBackup, String> {
+        log::info!("♻️ Restoring database from {}", source.display());
+        
+        let schema_version = Self::validate_backup(source).await?;
+        let size_bytes = std::fs::metadata(source).map(|m| m.len()).unwrap_or(0);
+        
+        // Keep the current data around in case the restored file turns out to be the wrong one
+        let previous = self.path.with_extension("db.pre-restore");
+        let _ = std::fs::remove_file(&previous);
+        self.backup(&previous)
+            .await
+            .map_err(|e| format!("Failed to save current database before restore: {}", e))?;
+        
+        self.pool.close().await;
+        
+        let swap = std::fs::copy(source, &self.path)
+            .map_err(|e| format!("Failed to copy {} into place: {}", source.display(), e));
+        if swap.is_err() {
+            log::error!("❌ Restore failed, putting the previous database back");
+            let _ = std::fs::copy(&previous, &self.path);
+        }
+        
+        // Reconnect even on failure so the app keeps working with whichever file is in place
+        self.pool = Self::connect(&self.path)
+            .await
+            .map_err(|e| format!("Failed to reopen database: {}", e))?;
+        swap?;
+        
+        // Older backups are brought up to the current schema
+        self.run_migrations()
+            .await
+            .map_err(|e| format!("Failed to migrate restored database: {}", e))?;
+        
+        log::info!("✅ Database restored from {} (schema version {})", source.display(), schema_version);
+        Ok(DatabaseBackup {
+            path: source.to_string_lossy().to_string(),
+            size_bytes,
+            schema_version,
+            timestamp: Utc::now(),
+        })
+    }
+    
+    // Returns true when the column had to be added
+    async fn add_column_if_missing(&self, table: &str, column: &str, definition: &str) -> Result<bool, sqlx::Error> {
+        let exists: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM pragma_table_info(?) WHERE name = ?")
+            .bind(table)
+            .bind(column)
+            .fetch_one(&self.pool)
+            .await?;
+        
+        if exists > 0 {
+            return Ok(false);
+        }
+        
+        log::info!("🔄 Adding {} column to {}", column, table);
+        sqlx::query(&format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition))
+            .execute(&self.pool)
+            .await?;
+        
+        Ok(true)
+    }
+    
     pub async fn add_tracked_service(
         &self,
         name: &str,
@@ -150,8 +578,8 @@ impl Database {
         
         let row = sqlx::query(
             r#"
-            INSERT INTO tracked_services (name, display_name, description, category, created_at, updated_at)
-            VALUES (?, ?, ?, ?, ?, ?)
+            INSERT INTO tracked_services (name, display_name, description, category, sort_order, created_at, updated_at)
+            VALUES (?, ?, ?, ?, (SELECT COALESCE(MAX(sort_order), -1) + 1 FROM tracked_services), ?, ?)
             RETURNING *
             "#,
         )
@@ -159,8 +587,8 @@ impl Database {
         .bind(display_name)
         .bind(description)
         .bind(category)
-        .bind(now.to_rfc3339())
-        .bind(now.to_rfc3339())
+        .bind(format_timestamp(now))
+        .bind(format_timestamp(now))
         .fetch_one(&self.pool)
         .await?;
         
@@ -172,12 +600,15 @@ impl Database {
             category: row.get("category"),
             enabled: row.get("enabled"),
             auto_start: row.get("auto_start"),
-            created_at: DateTime::parse_from_rfc3339(&row.get::<String, _>("created_at"))
-                .unwrap()
-                .with_timezone(&Utc),
-            updated_at: DateTime::parse_from_rfc3339(&row.get::<String, _>("updated_at"))
-                .unwrap()
-                .with_timezone(&Utc),
+            sort_order: row.get("sort_order"),
+            tags: Vec::new(),
+            notes: row.get("notes"),
+            last_status: row.get("last_status"),
+            last_checked: parse_optional_timestamp(&row, "last_checked"),
+            log_flood_threshold: row.get("log_flood_threshold"),
+            critical: row.get("critical"),
+            created_at: parse_timestamp(&row, "created_at"),
+            updated_at: parse_timestamp(&row, "updated_at"),
         };
         
         log::info!("✅ Service added to tracking with ID: {}", service.id);
@@ -194,6 +625,12 @@ impl Database {
             .execute(&self.pool)
             .await?;
         
+        log::debug!("🗑️ Removing associated tags for service: {}", name);
+        sqlx::query("DELETE FROM service_tags WHERE service_name = ?")
+            .bind(name)
+            .execute(&self.pool)
+            .await?;
+        
         // Then remove the service
         log::debug!("🗑️ Removing service from tracked_services: {}", name);
         sqlx::query("DELETE FROM tracked_services WHERE name = ?")
@@ -208,10 +645,64 @@ impl Database {
     pub async fn get_tracked_services(&self) -> Result<Vec<TrackedService>, sqlx::Error> {
         log::debug!("📋 Getting all tracked services");
         
-        let rows = sqlx::query("SELECT * FROM tracked_services ORDER BY display_name")
+        let rows = sqlx::query("SELECT * FROM tracked_services ORDER BY sort_order, display_name")
             .fetch_all(&self.pool)
             .await?;
         
+        self.tracked_services_from_rows(rows).await
+    }
+    
+    // None means the service isn't tracked; errors are reserved for database failures
+    pub async fn get_tracked_service(&self, name: &str) -> Result<Option<TrackedService>, sqlx::Error> {
+        log::debug!("📋 Getting tracked service: {}", name);
+        
+        let row = sqlx::query("SELECT * FROM tracked_services WHERE name = ?")
+            .bind(name)
+            .fetch_optional(&self.pool)
+            .await?;
+        
+        let Some(row) = row else {
+            return Ok(None);
+        };
+        Ok(self.tracked_services_from_rows(vec![row]).await?.pop())
+    }
+    
+    pub async fn get_tracked_services_by_tag(&self, tag: &str) -> Result<Vec<TrackedService>, sqlx::Error> {
+        let tag = normalize_tag(tag);
+        log::debug!("📋 Getting tracked services tagged: {}", tag);
+        
+        let rows = sqlx::query(
+            r#"
+            SELECT tracked_services.* FROM tracked_services
+            JOIN service_tags ON service_tags.service_name = tracked_services.name
+            WHERE service_tags.tag = ?
+            ORDER BY tracked_services.sort_order, tracked_services.display_name
+            "#,
+        )
+        .bind(&tag)
+        .fetch_all(&self.pool)
+        .await?;
+        
+        self.tracked_services_from_rows(rows).await
+    }
+    
+    async fn tracked_services_from_rows(
+        &self,
+        rows: Vec<sqlx::sqlite::SqliteRow>,
+    ) -> Result<Vec<TrackedService>, sqlx::Error> {
+        // Load every tag in one query instead of one per service
+        let tag_rows = sqlx::query("SELECT service_name, tag FROM service_tags ORDER BY tag")
+            .fetch_all(&self.pool)
+            .await?;
+        
+        let mut tags_by_service: HashMap<String, Vec<String>> = HashMap::new();
+        for row in tag_rows {
+            tags_by_service
+                .entry(row.get("service_name"))
+                .or_default()
+                .push(row.get("tag"));
+        }
+        
         let services: Vec<TrackedService> = rows
             .into_iter()
             .map(|row| TrackedService {
@@ -222,12 +713,17 @@ impl Database {
                 category: row.get("category"),
                 enabled: row.get("enabled"),
                 auto_start: row.get("auto_start"),
-                created_at: DateTime::parse_from_rfc3339(&row.get::<String, _>("created_at"))
-                    .unwrap()
-                    .with_timezone(&Utc),
-                updated_at: DateTime::parse_from_rfc3339(&row.get::<String, _>("updated_at"))
-                    .unwrap()
-                    .with_timezone(&Utc),
+                sort_order: row.get("sort_order"),
+                tags: tags_by_service
+                    .remove(&row.get::<String, _>("name"))
+                    .unwrap_or_default(),
+                notes: row.get("notes"),
+                last_status: row.get("last_status"),
+                last_checked: parse_optional_timestamp(&row, "last_checked"),
+                log_flood_threshold: row.get("log_flood_threshold"),
+                critical: row.get("critical"),
+                created_at: parse_timestamp(&row, "created_at"),
+                updated_at: parse_timestamp(&row, "updated_at"),
             })
             .collect();
         
@@ -260,7 +756,7 @@ impl Database {
         
         sqlx::query("UPDATE tracked_services SET enabled = ?, updated_at = ? WHERE name = ?")
             .bind(enabled)
-            .bind(now.to_rfc3339())
+            .bind(format_timestamp(now))
             .bind(name)
             .execute(&self.pool)
             .await?;
@@ -269,6 +765,185 @@ impl Database {
         Ok(())
     }
     
+    pub async fn reorder_tracked_services(&self, ordered_names: &[String]) -> Result<(), sqlx::Error> {
+        log::info!("🔀 Reordering {} tracked services", ordered_names.len());
+        
+        let now = Utc::now();
+        let mut tx = self.pool.begin().await?;
+        
+        for (position, name) in ordered_names.iter().enumerate() {
+            sqlx::query("UPDATE tracked_services SET sort_order = ?, updated_at = ? WHERE name = ?")
+                .bind(position as i64)
+                .bind(format_timestamp(now))
+                .bind(name)
+                .execute(&mut *tx)
+                .await?;
+        }
+        
+        tx.commit().await?;
+        
+        log::info!("✅ Tracked services reordered");
+        Ok(())
+    }
+    
+    // Persists one monitor cycle in a single transaction; updated_at is left alone
+    // because a status reading isn't an edit
+    pub async fn record_service_statuses(&self, statuses: &[(String, String)]) -> Result<(), sqlx::Error> {
+        if statuses.is_empty() {
+            return Ok(());
+        }
+        
+        let checked_at = format_timestamp(Utc::now());
+        let mut tx = self.pool.begin().await?;
+        
+        for (name, status) in statuses {
+            sqlx::query("UPDATE tracked_services SET last_status = ?, last_checked = ? WHERE name = ?")
+                .bind(status)
+                .bind(&checked_at)
+                .bind(name)
+                .execute(&mut *tx)
+                .await?;
+        }
+        
+        tx.commit().await?;
+        Ok(())
+    }
+    
+    // Fields passed as None keep their current value
+    pub async fn update_tracked_service(
+        &self,
+        name: &str,
+        display_name: Option<&str>,
+        description: Option<&str>,
+        category: Option<&str>,
+    ) -> Result<TrackedService, sqlx::Error> {
+        log::info!("✏️ Updating tracked service: {}", name);
+        
+        let row = sqlx::query(
+            r#"
+            UPDATE tracked_services SET
+                display_name = COALESCE(?, display_name),
+                description = COALESCE(?, description),
+                category = COALESCE(?, category),
+                updated_at = ?
+            WHERE name = ?
+            RETURNING *
+            "#,
+        )
+        .bind(display_name)
+        .bind(description)
+        .bind(category)
+        .bind(format_timestamp(Utc::now()))
+        .bind(name)
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or(sqlx::Error::RowNotFound)?;
+        
+        self.tracked_services_from_rows(vec![row])
+            .await?
+            .pop()
+            .ok_or(sqlx::Error::RowNotFound)
+    }
+    
+    pub async fn set_service_note(&self, name: &str, note: Option<&str>) -> Result<(), sqlx::Error> {
+        log::info!("📝 Updating note for service: {}", name);
+        
+        let now = Utc::now();
+        
+        let result = sqlx::query("UPDATE tracked_services SET notes = ?, updated_at = ? WHERE name = ?")
+            .bind(note)
+            .bind(format_timestamp(now))
+            .bind(name)
+            .execute(&self.pool)
+            .await?;
+        
+        if result.rows_affected() == 0 {
+            return Err(sqlx::Error::RowNotFound);
+        }
+        
+        Ok(())
+    }
+    
+    pub async fn set_log_flood_threshold(&self, name: &str, threshold: Option<f64>) -> Result<(), sqlx::Error> {
+        log::info!("🌊 Updating log flood threshold for service: {} -> {:?}", name, threshold);
+        
+        let result = sqlx::query("UPDATE tracked_services SET log_flood_threshold = ?, updated_at = ? WHERE name = ?")
+            .bind(threshold)
+            .bind(format_timestamp(Utc::now()))
+            .bind(name)
+            .execute(&self.pool)
+            .await?;
+        
+        if result.rows_affected() == 0 {
+            return Err(sqlx::Error::RowNotFound);
+        }
+        
+        Ok(())
+    }
+    
+    pub async fn set_service_critical(&self, name: &str, critical: bool) -> Result<(), sqlx::Error> {
+        log::info!("🛡️ Marking service {} as {}", name, if critical { "critical" } else { "not critical" });
+        
+        let result = sqlx::query("UPDATE tracked_services SET critical = ?, updated_at = ? WHERE name = ?")
+            .bind(critical)
+            .bind(format_timestamp(Utc::now()))
+            .bind(name)
+            .execute(&self.pool)
+            .await?;
+        
+        if result.rows_affected() == 0 {
+            return Err(sqlx::Error::RowNotFound);
+        }
+        
+        Ok(())
+    }
+    
+    pub async fn is_service_critical(&self, name: &str) -> Result<bool, sqlx::Error> {
+        let critical: Option<bool> = sqlx::query_scalar("SELECT critical FROM tracked_services WHERE name = ?")
+            .bind(name)
+            .fetch_optional(&self.pool)
+            .await?;
+        
+        Ok(critical.unwrap_or(false))
+    }
+    
+    pub async fn add_tag(&self, service_name: &str, tag: &str) -> Result<(), sqlx::Error> {
+        let tag = normalize_tag(tag);
+        log::info!("🏷️ Tagging service {} with: {}", service_name, tag);
+        
+        // The unique constraint makes re-adding an existing tag a no-op
+        sqlx::query("INSERT OR IGNORE INTO service_tags (service_name, tag, created_at) VALUES (?, ?, ?)")
+            .bind(service_name)
+            .bind(&tag)
+            .bind(format_timestamp(Utc::now()))
+            .execute(&self.pool)
+            .await?;
+        
+        Ok(())
+    }
+    
+    pub async fn remove_tag(&self, service_name: &str, tag: &str) -> Result<(), sqlx::Error> {
+        let tag = normalize_tag(tag);
+        log::info!("🏷️ Removing tag {} from service: {}", tag, service_name);
+        
+        sqlx::query("DELETE FROM service_tags WHERE service_name = ? AND tag = ?")
+            .bind(service_name)
+            .bind(&tag)
+            .execute(&self.pool)
+            .await?;
+        
+        Ok(())
+    }
+    
+    pub async fn get_tags(&self, service_name: &str) -> Result<Vec<String>, sqlx::Error> {
+        let tags: Vec<String> = sqlx::query_scalar("SELECT tag FROM service_tags WHERE service_name = ? ORDER BY tag")
+            .bind(service_name)
+            .fetch_all(&self.pool)
+            .await?;
+        
+        Ok(tags)
+    }
+    
     pub async fn set_service_config(
         &self,
         service_name: &str,
@@ -292,14 +967,72 @@ impl Database {
         .bind(config_key)
         .bind(config_value)
         .bind(config_type)
-        .bind(now.to_rfc3339())
-        .bind(now.to_rfc3339())
+        .bind(format_timestamp(now))
+        .bind(format_timestamp(now))
         .execute(&self.pool)
         .await?;
         
         Ok(())
     }
     
+    // All or nothing: any failed upsert rolls back the ones before it
+    pub async fn set_service_configs(&self, service_name: &str, configs: &[ServiceConfig]) -> Result<(), sqlx::Error> {
+        let now = format_timestamp(Utc::now());
+        let mut tx = self.pool.begin().await?;
+        
+        for config in configs {
+            sqlx::query(
+                r#"
+                INSERT INTO service_configs (service_name, config_key, config_value, config_type, created_at, updated_at)
+                VALUES (?, ?, ?, ?, ?, ?)
+                ON CONFLICT(service_name, config_key) DO UPDATE SET
+                    config_value = excluded.config_value,
+                    config_type = excluded.config_type,
+                    updated_at = excluded.updated_at
+                "#,
+            )
+            .bind(service_name)
+            .bind(&config.config_key)
+            .bind(&config.config_value)
+            .bind(&config.config_type)
+            .bind(&now)
+            .bind(&now)
+            .execute(&mut *tx)
+            .await?;
+        }
+        
+        tx.commit().await?;
+        Ok(())
+    }
+    
+    pub async fn get_service_config(&self, service_name: &str, config_key: &str) -> Result<Option<ServiceConfig>, sqlx::Error> {
+        let row = sqlx::query(
+            "SELECT service_name, config_key, config_value, config_type FROM service_configs WHERE service_name = ? AND config_key = ?"
+        )
+        .bind(service_name)
+        .bind(config_key)
+        .fetch_optional(&self.pool)
+        .await?;
+        
+        Ok(row.map(|row| ServiceConfig {
+            service_name: row.get("service_name"),
+            config_key: row.get("config_key"),
+            config_value: row.get("config_value"),
+            config_type: row.get("config_type"),
+        }))
+    }
+    
+    // Returns false when there was no such key
+    pub async fn delete_service_config(&self, service_name: &str, config_key: &str) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM service_configs WHERE service_name = ? AND config_key = ?")
+            .bind(service_name)
+            .bind(config_key)
+            .execute(&self.pool)
+            .await?;
+        
+        Ok(result.rows_affected() > 0)
+    }
+    
     pub async fn get_service_configs(&self, service_name: &str) -> Result<Vec<ServiceConfig>, sqlx::Error> {
         let rows = sqlx::query(
             "SELECT service_name, config_key, config_value, config_type FROM service_configs WHERE service_name = ?"
@@ -320,4 +1053,356 @@ impl Database {
         
         Ok(configs)
     }
-}
\ No newline at end of file
+    
+    pub async fn add_host(&self, name: &str, address: &str, user: &str, port: u16) -> Result<Host, sqlx::Error> {
+        log::info!("➕ Adding host: {} ({}@{}:{})", name, user, address, port);
+        
+        let row = sqlx::query(
+            r#"
+            INSERT INTO hosts (name, address, user, port, created_at)
+            VALUES (?, ?, ?, ?, ?)
+            RETURNING *
+            "#,
+        )
+        .bind(name)
+        .bind(address)
+        .bind(user)
+        .bind(port as i64)
+        .bind(format_timestamp(Utc::now()))
+        .fetch_one(&self.pool)
+        .await?;
+        
+        Ok(host_from_row(&row))
+    }
+    
+    pub async fn remove_host(&self, id: i64) -> Result<(), sqlx::Error> {
+        log::info!("🗑️ Removing host: {}", id);
+        
+        sqlx::query("DELETE FROM hosts WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        
+        Ok(())
+    }
+    
+    pub async fn get_hosts(&self) -> Result<Vec<Host>, sqlx::Error> {
+        let rows = sqlx::query("SELECT * FROM hosts ORDER BY name")
+            .fetch_all(&self.pool)
+            .await?;
+        
+        Ok(rows.iter().map(host_from_row).collect())
+    }
+    
+    pub async fn get_host(&self, id: i64) -> Result<Option<Host>, sqlx::Error> {
+        let row = sqlx::query("SELECT * FROM hosts WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+        
+        Ok(row.as_ref().map(host_from_row))
+    }
+    
+    pub async fn snooze_alerts(&self, service_name: &str, until: DateTime<Utc>) -> Result<SnoozedAlert, sqlx::Error> {
+        log::info!("😴 Snoozing alerts for {} until {}", service_name, until.to_rfc3339());
+        
+        let row = sqlx::query(
+            r#"
+            INSERT INTO snoozed_alerts (service_name, until, created_at)
+            VALUES (?, ?, ?)
+            ON CONFLICT(service_name) DO UPDATE SET
+                until = excluded.until,
+                created_at = excluded.created_at
+            RETURNING *
+            "#,
+        )
+        .bind(service_name)
+        .bind(format_timestamp(until))
+        .bind(format_timestamp(Utc::now()))
+        .fetch_one(&self.pool)
+        .await?;
+        
+        Ok(snoozed_alert_from_row(&row))
+    }
+    
+    // Returns false when the service wasn't snoozed
+    pub async fn unsnooze_alerts(&self, service_name: &str) -> Result<bool, sqlx::Error> {
+        log::info!("🔔 Unsnoozing alerts for {}", service_name);
+        
+        let result = sqlx::query("DELETE FROM snoozed_alerts WHERE service_name = ?")
+            .bind(service_name)
+            .execute(&self.pool)
+            .await?;
+        
+        Ok(result.rows_affected() > 0)
+    }
+    
+    pub async fn record_metrics_snapshots(&self, snapshots: &[MetricsSnapshot]) -> Result<(), sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        for snapshot in snapshots {
+            sqlx::query(
+                r#"
+                INSERT INTO metrics_history
+                    (service_name, cpu_usage, memory_usage, network_in, network_out, disk_read, disk_write, recorded_at)
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+                "#,
+            )
+            .bind(&snapshot.service_name)
+            .bind(snapshot.cpu_usage)
+            .bind(snapshot.memory_usage as i64)
+            .bind(snapshot.network_in as i64)
+            .bind(snapshot.network_out as i64)
+            .bind(snapshot.disk_read as i64)
+            .bind(snapshot.disk_write as i64)
+            .bind(format_timestamp(snapshot.recorded_at))
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await
+    }
+    
+    // The snapshot closest to `at` that is no more than `tolerance` away from it
+    pub async fn nearest_metrics_snapshot(
+        &self,
+        service_name: &str,
+        at: DateTime<Utc>,
+        tolerance: chrono::Duration,
+    ) -> Result<Option<MetricsSnapshot>, sqlx::Error> {
+        let row = sqlx::query(
+            r#"
+            SELECT * FROM metrics_history
+            WHERE service_name = ? AND recorded_at BETWEEN ? AND ?
+            ORDER BY abs(julianday(recorded_at) - julianday(?))
+            LIMIT 1
+            "#,
+        )
+        .bind(service_name)
+        .bind(format_timestamp(at.checked_sub_signed(tolerance).unwrap_or(DateTime::<Utc>::MIN_UTC)))
+        .bind(format_timestamp(at.checked_add_signed(tolerance).unwrap_or(DateTime::<Utc>::MAX_UTC)))
+        .bind(format_timestamp(at))
+        .fetch_optional(&self.pool)
+        .await?;
+        
+        Ok(row.as_ref().map(metrics_snapshot_from_row))
+    }
+    
+    // Drops samples older than `before`; returns how many were removed
+    pub async fn prune_metrics_history(&self, before: DateTime<Utc>) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM metrics_history WHERE recorded_at < ?")
+            .bind(format_timestamp(before))
+            .execute(&self.pool)
+            .await?;
+        
+        Ok(result.rows_affected())
+    }
+    
+    // Only snoozes that haven't expired; expired rows are cleared on the way
+    pub async fn get_snoozed_alerts(&self) -> Result<Vec<SnoozedAlert>, sqlx::Error> {
+        let rows = sqlx::query("SELECT * FROM snoozed_alerts ORDER BY service_name")
+            .fetch_all(&self.pool)
+            .await?;
+        
+        let now = Utc::now();
+        let (active, expired): (Vec<SnoozedAlert>, Vec<SnoozedAlert>) = rows
+            .iter()
+            .map(snoozed_alert_from_row)
+            .partition(|snooze| snooze.until > now);
+        
+        for snooze in &expired {
+            sqlx::query("DELETE FROM snoozed_alerts WHERE service_name = ?")
+                .bind(&snooze.service_name)
+                .execute(&self.pool)
+                .await?;
+        }
+        
+        Ok(active)
+    }
+    
+    pub async fn get_app_setting(&self, key: &str) -> Result<Option<String>, sqlx::Error> {
+        let value: Option<String> = sqlx::query_scalar("SELECT value FROM app_settings WHERE key = ?")
+            .bind(key)
+            .fetch_optional(&self.pool)
+            .await?;
+        
+        Ok(value)
+    }
+    
+    pub async fn set_app_setting(&self, key: &str, value: &str) -> Result<(), sqlx::Error> {
+        log::info!("⚙️ Updating app setting: {} = {}", key, value);
+        
+        let now = Utc::now();
+        
+        sqlx::query(
+            r#"
+            INSERT INTO app_settings (key, value, updated_at)
+            VALUES (?, ?, ?)
+            ON CONFLICT(key) DO UPDATE SET
+                value = excluded.value,
+                updated_at = excluded.updated_at
+            "#,
+        )
+        .bind(key)
+        .bind(value)
+        .bind(format_timestamp(now))
+        .execute(&self.pool)
+        .await?;
+        
+        Ok(())
+    }
+}
+
+// Tags are stored trimmed and lowercased so "Backend " and "backend" are the same tag
+pub fn normalize_tag(tag: &str) -> String {
+    tag.trim().to_lowercase()
+}
+
+fn host_from_row(row: &sqlx::sqlite::SqliteRow) -> Host {
+    Host {
+        id: row.get("id"),
+        name: row.get("name"),
+        address: row.get("address"),
+        user: row.get("user"),
+        port: row.get::<i64, _>("port") as u16,
+        created_at: parse_timestamp(row, "created_at"),
+    }
+}
+
+fn snoozed_alert_from_row(row: &sqlx::sqlite::SqliteRow) -> SnoozedAlert {
+    SnoozedAlert {
+        service_name: row.get("service_name"),
+        until: parse_timestamp(row, "until"),
+        created_at: parse_timestamp(row, "created_at"),
+    }
+}
+
+fn metrics_snapshot_from_row(row: &sqlx::sqlite::SqliteRow) -> MetricsSnapshot {
+    MetricsSnapshot {
+        service_name: row.get("service_name"),
+        cpu_usage: row.get::<f64, _>("cpu_usage") as f32,
+        memory_usage: row.get::<i64, _>("memory_usage") as u64,
+        network_in: row.get::<i64, _>("network_in") as u64,
+        network_out: row.get::<i64, _>("network_out") as u64,
+        disk_read: row.get::<i64, _>("disk_read") as u64,
+        disk_write: row.get::<i64, _>("disk_write") as u64,
+        recorded_at: parse_timestamp(row, "recorded_at"),
+    }
+}
+
+// The command line flag takes precedence over the environment variable
+fn data_dir_override() -> Option<PathBuf> {
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == DATA_DIR_FLAG {
+            return args.next().map(PathBuf::from);
+        }
+        if let Some(value) = arg.strip_prefix(DATA_DIR_FLAG).and_then(|rest| rest.strip_prefix('=')) {
+            return Some(PathBuf::from(value));
+        }
+    }
+    
+    std::env::var_os(DATA_DIR_ENV)
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+}
+
+// Creating the directory isn't enough on read-only mounts, so probe with a real write
+fn ensure_writable_dir(dir: &Path) -> std::io::Result<()> {
+    std::fs::create_dir_all(dir)?;
+    let probe = dir.join(".write-test");
+    std::fs::write(&probe, b"")?;
+    std::fs::remove_file(&probe)
+}
+
+// Every stored timestamp uses this format, which is also what the column defaults
+// produce, so values written by Rust and by SQLite compare and sort the same way
+fn format_timestamp(timestamp: DateTime<Utc>) -> String {
+    timestamp.to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+// Accepts RFC3339 and SQLite's "2024-01-01 10:00:00" (taken as UTC); anything else is
+// logged and replaced with the current time rather than failing the whole query
+fn parse_timestamp(row: &sqlx::sqlite::SqliteRow, column: &str) -> DateTime<Utc> {
+    let value: String = row.get(column);
+    
+    if let Ok(timestamp) = DateTime::parse_from_rfc3339(&value) {
+        return timestamp.with_timezone(&Utc);
+    }
+    if let Ok(timestamp) = chrono::NaiveDateTime::parse_from_str(&value, "%Y-%m-%d %H:%M:%S%.f") {
+        return timestamp.and_utc();
+    }
+    
+    log::warn!("⚠️ Unparseable {} timestamp '{}', using the current time", column, value);
+    Utc::now()
+}
+
+fn parse_optional_timestamp(row: &sqlx::sqlite::SqliteRow, column: &str) -> Option<DateTime<Utc>> {
+    row.get::<Option<String>, _>(column)?;
+    Some(parse_timestamp(row, column))
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    // One connection, because every connection to :memory: opens its own empty database
+    async fn memory_database() -> Database {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("in-memory pool");
+        let db = Database { pool, path: PathBuf::new() };
+        db.run_migrations().await.expect("migrations");
+        db
+    }
+
+    async fn raw_created_at(db: &Database, name: &str) -> String {
+        sqlx::query("SELECT created_at FROM tracked_services WHERE name = ?")
+            .bind(name)
+            .fetch_one(&db.pool)
+            .await
+            .expect("row")
+            .get("created_at")
+    }
+
+    #[tokio::test]
+    async fn column_defaults_and_format_timestamp_store_the_same_format() {
+        let db = memory_database().await;
+
+        let before = Utc::now() - chrono::Duration::seconds(1);
+        sqlx::query("INSERT INTO tracked_services (name, display_name) VALUES ('by-default', 'By default')")
+            .execute(&db.pool)
+            .await
+            .expect("insert with defaults");
+        db.add_tracked_service("by-rust", "By Rust", None, "Other")
+            .await
+            .expect("insert with format_timestamp");
+        let after = Utc::now() + chrono::Duration::seconds(1);
+
+        let by_default = raw_created_at(&db, "by-default").await;
+        let by_rust = raw_created_at(&db, "by-rust").await;
+        for raw in [&by_default, &by_rust] {
+            let parsed = DateTime::parse_from_rfc3339(raw).expect("RFC3339").with_timezone(&Utc);
+            assert_eq!(raw.as_str(), format_timestamp(parsed), "{} is not in the stored format", raw);
+        }
+        assert!(by_default <= by_rust, "{} should sort before {}", by_default, by_rust);
+
+        for service in db.get_tracked_services().await.expect("read back") {
+            assert!(service.created_at >= before && service.created_at <= after, "{} out of range", service.created_at);
+            assert!(service.updated_at >= before && service.updated_at <= after, "{} out of range", service.updated_at);
+        }
+    }
+
+    #[tokio::test]
+    async fn migrations_convert_legacy_timestamps() {
+        let db = memory_database().await;
+
+        sqlx::query("INSERT INTO tracked_services (name, display_name, created_at) VALUES ('legacy', 'Legacy', '2024-01-01 10:00:00')")
+            .execute(&db.pool)
+            .await
+            .expect("insert legacy row");
+        db.run_migrations().await.expect("migrations");
+
+        assert_eq!(raw_created_at(&db, "legacy").await, "2024-01-01T10:00:00.000Z");
+    }
+}
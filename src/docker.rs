@@ -0,0 +1,296 @@
+use serde::Deserialize;
+use std::process::Command;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use chrono::Utc;
+use crate::error::CommandError;
+use crate::services::{Service, ServiceStatus, SystemMetrics, SystemMetricsDisplay};
+
+// Containers are addressed as "docker:<container>" so they can share the service commands
+pub const DOCKER_PREFIX: &str = "docker:";
+
+// The last probe's result and when it ran; re-probed after DOCKER_AVAILABILITY_TTL so a daemon
+// started or stopped after launch is noticed
+static DOCKER_AVAILABLE: Mutex<Option<(bool, Instant)>> = Mutex::new(None);
+const DOCKER_AVAILABILITY_TTL: Duration = Duration::from_secs(30);
+
+// One line of `docker ps --format '{{json .}}'`
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ContainerSummary {
+    #[serde(rename = "ID")]
+    id: String,
+    names: String,
+    image: String,
+    state: String,
+    status: String,
+}
+
+// Output of `docker stats --no-stream --format '{{json .}}'`
+#[derive(Debug, Deserialize)]
+struct ContainerStats {
+    #[serde(rename = "CPUPerc")]
+    cpu_perc: String,
+    #[serde(rename = "MemUsage")]
+    mem_usage: String,
+    #[serde(rename = "NetIO")]
+    net_io: String,
+    #[serde(rename = "BlockIO")]
+    block_io: String,
+    #[serde(rename = "PIDs")]
+    pids: String,
+}
+
+pub struct DockerBackend;
+
+impl DockerBackend {
+    // Returns the container name when the service name refers to a docker container
+    pub fn container_name(service_name: &str) -> Option<&str> {
+        service_name
+            .strip_prefix(DOCKER_PREFIX)
+            .filter(|name| Self::is_valid_container(name))
+    }
+
+    // A name starting with '-' would be read by docker as an option, so it isn't a container
+    fn is_valid_container(container: &str) -> bool {
+        !container.is_empty() && !container.starts_with('-')
+    }
+
+    fn require_valid_container(container: &str) -> Result<(), CommandError> {
+        if Self::is_valid_container(container) {
+            Ok(())
+        } else {
+            Err(CommandError::InvalidInput(format!("Invalid container name: {:?}", container)))
+        }
+    }
+
+    // Checks whether the docker CLI is installed and its daemon is reachable, reusing a recent answer
+    pub fn is_available() -> bool {
+        let mut cached = DOCKER_AVAILABLE.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some((available, checked_at)) = *cached {
+            if checked_at.elapsed() < DOCKER_AVAILABILITY_TTL {
+                return available;
+            }
+        }
+
+        let available = Command::new("docker")
+            .args(&["info", "--format", "{{.ServerVersion}}"])
+            .output()
+            .map(|output| output.status.success())
+            .unwrap_or(false);
+
+        let previous = cached.map(|(available, _)| available);
+        if previous != Some(available) {
+            if available {
+                log::info!("🐳 Docker daemon detected");
+            } else {
+                log::info!("ℹ️ Docker not available, container support disabled");
+            }
+        }
+        *cached = Some((available, Instant::now()));
+        available
+    }
+
+    // Makes the next is_available call probe again, e.g. after the daemon stopped answering
+    fn forget_availability() {
+        *DOCKER_AVAILABLE.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    }
+
+    pub fn list_containers() -> Result<Vec<Service>, CommandError> {
+        if !Self::is_available() {
+            return Ok(Vec::new());
+        }
+
+        // The json template works on older docker releases that lack `--format json`
+        let output = Command::new("docker")
+            .args(&["ps", "-a", "--no-trunc", "--format", "{{json .}}"])
+            .output()
+            .map_err(|e| CommandError::Internal(format!("Failed to list docker containers: {}", e)))?;
+
+        if !output.status.success() {
+            Self::forget_availability();
+            return Err(CommandError::Internal(format!("Failed to list docker containers: {}",
+                String::from_utf8_lossy(&output.stderr))));
+        }
+
+        let containers: Vec<Service> = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| match serde_json::from_str::<ContainerSummary>(line) {
+                Ok(summary) => Some(Self::to_service(summary)),
+                Err(e) => {
+                    log::warn!("⚠️ Failed to parse docker container entry: {}", e);
+                    None
+                }
+            })
+            .collect();
+
+        log::debug!("🐳 Found {} docker containers", containers.len());
+        Ok(containers)
+    }
+
+    pub fn get_container(container: &str) -> Result<Service, CommandError> {
+        Self::require_available()?;
+
+        Self::list_containers()?
+            .into_iter()
+            .find(|service| service.name == format!("{}{}", DOCKER_PREFIX, container))
+            .ok_or_else(|| CommandError::NotFound(format!("Container '{}' not found", container)))
+    }
+
+    // Runs `docker start|stop|restart -- <container>`
+    pub fn container_action(container: &str, action: &str) -> Result<std::process::Output, CommandError> {
+        Self::require_valid_container(container)?;
+        Self::require_available()?;
+
+        log::info!("🐳 Running docker {} {}", action, container);
+        Command::new("docker")
+            .args(&[action, "--", container])
+            .output()
+            .map_err(|e| CommandError::Internal(format!("Failed to execute docker {}: {}", action, e)))
+    }
+
+    pub fn require_available() -> Result<(), CommandError> {
+        if Self::is_available() {
+            Ok(())
+        } else {
+            Err(CommandError::BackendUnavailable("Docker is not installed or its daemon is not running".to_string()))
+        }
+    }
+
+    // Returns the last `lines` lines the container wrote to stdout and stderr
+    pub fn container_logs(container: &str, lines: u32) -> Result<Vec<String>, CommandError> {
+        Self::require_valid_container(container)?;
+        Self::require_available()?;
+
+        let output = Command::new("docker")
+            .args(&["logs", "--tail", &lines.to_string(), "--timestamps", "--", container])
+            .output()
+            .map_err(|e| CommandError::Internal(format!("Failed to get container logs: {}", e)))?;
+
+        if !output.status.success() {
+            return Err(CommandError::Internal(format!("Failed to get logs for {}: {}", container,
+                String::from_utf8_lossy(&output.stderr))));
+        }
+
+        // docker replays the container's stdout and stderr on its own streams
+        let mut logs: Vec<String> = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .chain(String::from_utf8_lossy(&output.stderr).lines())
+            .map(|line| line.to_string())
+            .collect();
+        // Lines start with an RFC3339 timestamp, so sorting restores the interleaving
+        logs.sort();
+        Ok(logs)
+    }
+
+    pub fn container_metrics(service_name: &str, container: &str) -> Result<SystemMetrics, CommandError> {
+        Self::require_valid_container(container)?;
+        Self::require_available()?;
+
+        let output = Command::new("docker")
+            .args(&["stats", "--no-stream", "--format", "{{json .}}", "--", container])
+            .output()
+            .map_err(|e| CommandError::Internal(format!("Failed to get container stats: {}", e)))?;
+
+        if !output.status.success() {
+            return Err(CommandError::Internal(format!("Failed to get stats for {}: {}", container,
+                String::from_utf8_lossy(&output.stderr))));
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stats: ContainerStats = serde_json::from_str(stdout.trim())
+            .map_err(|e| CommandError::Internal(format!("Failed to parse container stats: {}", e)))?;
+
+        let (memory_usage, memory_total) = Self::parse_size_pair(&stats.mem_usage);
+        let (network_in, network_out) = Self::parse_size_pair(&stats.net_io);
+        let (disk_read, disk_write) = Self::parse_size_pair(&stats.block_io);
+
+        Ok(SystemMetrics {
+            service_name: service_name.to_string(),
+            cpu_usage: stats.cpu_perc.trim_end_matches('%').parse().unwrap_or(0.0),
+            memory_usage,
+            memory_total,
+            network_in,
+            network_out,
+            disk_read,
+            disk_write,
+            process_count: stats.pids.trim().parse().unwrap_or(0),
+            // docker stats doesn't report file descriptors
+            open_files: 0,
+            open_files_sampled: false,
+            timestamp: Utc::now(),
+            display: SystemMetricsDisplay::default(),
+        }
+        .with_display())
+    }
+
+    // Parses "10.5MiB / 7.6GiB" style pairs into bytes
+    fn parse_size_pair(pair: &str) -> (u64, u64) {
+        let mut parts = pair.split('/').map(|part| Self::parse_size(part.trim()));
+        (parts.next().unwrap_or(0), parts.next().unwrap_or(0))
+    }
+
+    // Docker mixes binary (KiB, MiB) and decimal (kB, MB) units depending on the column
+    fn parse_size(size: &str) -> u64 {
+        let split = size
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(size.len());
+        let (number, unit) = size.split_at(split);
+        let Ok(value) = number.parse::<f64>() else {
+            return 0;
+        };
+
+        let multiplier: f64 = match unit.trim() {
+            "B" | "" => 1.0,
+            "kB" | "KB" => 1e3,
+            "MB" => 1e6,
+            "GB" => 1e9,
+            "TB" => 1e12,
+            "KiB" => 1024.0,
+            "MiB" => 1024.0 * 1024.0,
+            "GiB" => 1024.0 * 1024.0 * 1024.0,
+            "TiB" => 1024.0 * 1024.0 * 1024.0 * 1024.0,
+            _ => 1.0,
+        };
+        (value * multiplier) as u64
+    }
+
+    fn to_service(summary: ContainerSummary) -> Service {
+        // Names is a comma separated list; the first entry is the primary name
+        let name = summary.names.split(',').next().unwrap_or(&summary.names).to_string();
+        let status = Self::status_from_state(&summary.state, &summary.status);
+        let uptime = if status == ServiceStatus::Running {
+            Some(summary.status.clone())
+        } else {
+            None
+        };
+
+        Service {
+            name: format!("{}{}", DOCKER_PREFIX, name),
+            service_name: summary.id,
+            status,
+            enabled: false,
+            uptime,
+            last_started: None,
+            description: format!("Docker container ({}) - {}", summary.image, summary.status),
+            restart_count: 0,
+            last_result: None,
+            tracked: false,
+            display_name: None,
+        }
+    }
+
+    fn status_from_state(state: &str, status: &str) -> ServiceStatus {
+        match state {
+            "running" => ServiceStatus::Running,
+            "restarting" => ServiceStatus::Activating,
+            "removing" => ServiceStatus::Deactivating,
+            "paused" | "created" => ServiceStatus::Stopped,
+            "dead" => ServiceStatus::Failed,
+            // "Exited (0) 2 hours ago" is a clean stop, any other code is a failure
+            "exited" if status.starts_with("Exited (0)") => ServiceStatus::Stopped,
+            "exited" => ServiceStatus::Failed,
+            _ => ServiceStatus::Unknown,
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use serde::Serialize;
+use std::fmt;
+
+// Error returned by every command. It serializes as { "code": "NotFound", "message": "..." }
+// so the UI can branch on a stable code and still show the message as is.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "code", content = "message")]
+pub enum CommandError {
+    NotFound(String),
+    PermissionDenied(String),
+    BackendUnavailable(String),
+    InvalidInput(String),
+    // The caller cancelled the request; there's nothing to show
+    Cancelled(String),
+    Internal(String),
+}
+
+impl CommandError {
+    pub fn message(&self) -> &str {
+        match self {
+            CommandError::NotFound(message)
+            | CommandError::PermissionDenied(message)
+            | CommandError::BackendUnavailable(message)
+            | CommandError::InvalidInput(message)
+            | CommandError::Cancelled(message)
+            | CommandError::Internal(message) => message,
+        }
+    }
+}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+impl std::error::Error for CommandError {}
+
+// Helpers that still build their errors as strings have nothing more specific to say; errors
+// with a stable code are built as that variant where they are raised
+impl From<String> for CommandError {
+    fn from(message: String) -> Self {
+        CommandError::Internal(message)
+    }
+}
+
+impl From<&str> for CommandError {
+    fn from(message: &str) -> Self {
+        CommandError::from(message.to_string())
+    }
+}
+
+impl From<CommandError> for String {
+    fn from(error: CommandError) -> Self {
+        match error {
+            CommandError::NotFound(message)
+            | CommandError::PermissionDenied(message)
+            | CommandError::BackendUnavailable(message)
+            | CommandError::InvalidInput(message)
+            | CommandError::Cancelled(message)
+            | CommandError::Internal(message) => message,
+        }
+    }
+}
@@ -0,0 +1,155 @@
+use axum::extract::State;
+use axum::http::{header, HeaderValue, StatusCode};
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::{Json, Router};
+use serde::Serialize;
+use std::convert::Infallible;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+use tokio::sync::{broadcast, Mutex};
+use tokio_stream::wrappers::BroadcastStream;
+use tokio_stream::StreamExt;
+use crate::database::Database;
+use crate::events::{monitor_running, EventPayload};
+
+// Set once the SSE server is running; events are published as the same JSON tauri emits
+static EVENT_SENDER: OnceLock<broadcast::Sender<String>> = OnceLock::new();
+
+// The one web origin allowed to read the stream cross-origin; unset, browsers keep other
+// pages from reading service events off the loopback port
+static ALLOWED_ORIGIN: OnceLock<HeaderValue> = OnceLock::new();
+
+const EVENT_BUFFER: usize = 256;
+
+// A health check that waits on a busy database longer than this reports it as down
+const HEALTH_DB_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Serialize)]
+struct Health {
+    status: &'static str,
+    db_ok: bool,
+    monitor_running: bool,
+    // Tracked services, None when the database couldn't be read
+    service_count: Option<usize>,
+}
+
+pub fn publish(event: &EventPayload) {
+    let Some(sender) = EVENT_SENDER.get() else {
+        return;
+    };
+    if sender.receiver_count() == 0 {
+        return;
+    }
+
+    match serde_json::to_string(event) {
+        Ok(payload) => {
+            let _ = sender.send(payload);
+        }
+        Err(e) => log::warn!("⚠️ Failed to serialize event for stream: {}", e),
+    }
+}
+
+// Starts the SSE server and returns the events URL; the same server answers /healthz
+pub async fn start(
+    host: &str,
+    port: u16,
+    allowed_origin: Option<&str>,
+    database: Arc<Mutex<Database>>,
+) -> Result<String, String> {
+    if EVENT_SENDER.get().is_some() {
+        return Err("Event stream is already running".to_string());
+    }
+    let allowed_origin = allowed_origin
+        .map(|origin| {
+            HeaderValue::from_str(origin).map_err(|e| format!("Invalid allowed origin '{}': {}", origin, e))
+        })
+        .transpose()?;
+
+    let listener = tokio::net::TcpListener::bind((host, port))
+        .await
+        .map_err(|e| format!("Failed to bind event stream to {}:{}: {}", host, port, e))?;
+    let address = listener
+        .local_addr()
+        .map_err(|e| format!("Failed to read event stream address: {}", e))?;
+
+    let (sender, _) = broadcast::channel(EVENT_BUFFER);
+    if EVENT_SENDER.set(sender).is_err() {
+        return Err("Event stream is already running".to_string());
+    }
+    if let Some(origin) = allowed_origin {
+        let _ = ALLOWED_ORIGIN.set(origin);
+    }
+
+    let app = Router::new()
+        .route("/events", get(events_handler))
+        .route("/healthz", get(health_handler))
+        .with_state(database);
+    tokio::spawn(async move {
+        if let Err(e) = axum::serve(listener, app).await {
+            log::error!("❌ Event stream server stopped: {}", e);
+        }
+    });
+
+    let url = format!("http://{}/events", address);
+    log::info!("📡 Event stream listening on {}", url);
+    Ok(url)
+}
+
+async fn events_handler() -> impl IntoResponse {
+    let receiver = EVENT_SENDER
+        .get()
+        .expect("event stream handler runs only after start")
+        .subscribe();
+
+    let stream = BroadcastStream::new(receiver).filter_map(|message| match message {
+        Ok(payload) => Some(Ok::<_, Infallible>(Event::default().event("service-event").data(payload))),
+        // A client that falls behind the buffer just misses the dropped events
+        Err(_) => None,
+    });
+
+    // A web client served from another origin can only read the stream when that origin was
+    // configured; any other page is refused by the browser
+    let mut response = Sse::new(stream).keep_alive(KeepAlive::default()).into_response();
+    if let Some(origin) = ALLOWED_ORIGIN.get() {
+        response.headers_mut().insert(header::ACCESS_CONTROL_ALLOW_ORIGIN, origin.clone());
+    }
+    response
+}
+
+// 200 while the database answers and the monitor loop is alive, 503 otherwise, so an
+// orchestrator can restart the manager
+async fn health_handler(State(database): State<Arc<Mutex<Database>>>) -> impl IntoResponse {
+    let service_count = tokio::time::timeout(HEALTH_DB_TIMEOUT, async {
+        let db = database.lock().await;
+        db.ping().await?;
+        db.get_tracked_services().await.map(|services| services.len())
+    })
+    .await;
+
+    let service_count = match service_count {
+        Ok(Ok(count)) => Some(count),
+        Ok(Err(e)) => {
+            log::warn!("⚠️ Health check could not reach the database: {}", e);
+            None
+        }
+        Err(_) => {
+            log::warn!("⚠️ Health check timed out waiting for the database");
+            None
+        }
+    };
+
+    let db_ok = service_count.is_some();
+    let monitor_running = monitor_running();
+    let healthy = db_ok && monitor_running;
+    let health = Health {
+        status: if healthy { "ok" } else { "degraded" },
+        db_ok,
+        monitor_running,
+        service_count,
+    };
+
+    let status = if healthy { StatusCode::OK } else { StatusCode::SERVICE_UNAVAILABLE };
+    (status, Json(health))
+}
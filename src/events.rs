@@ -1,10 +1,13 @@
 use serde::{Deserialize, Serialize};
 use tauri::{AppHandle, Emitter};
+use tokio::task::JoinHandle;
 use tokio::time::{interval, Duration};
-use std::sync::Arc;
-use tokio::sync::Mutex;
-use crate::database::Database;
-use crate::services::{get_service_status_internal, ServiceStatus as ServiceStatusEnum};
+use std::collections::HashSet;
+use std::sync::{Arc, OnceLock};
+use tokio::sync::{Mutex, Notify};
+use crate::database::{Database, MetricsSnapshot};
+use crate::docker::DOCKER_PREFIX;
+use crate::services::{service_metrics, local_service_statuses, count_visible_services, log_entry_rate, CancelToken, CpuSamples, list_service_unit_files, list_service_unit_states, ServiceStatus as ServiceStatusEnum};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type")]
@@ -15,6 +18,16 @@ pub enum ServiceEvent {
         new_status: String,
         timestamp: String,
     },
+    EnabledChanged {
+        service_name: String,
+        enabled: bool,
+        timestamp: String,
+    },
+    ServiceFailed {
+        service_name: String,
+        tracked: bool,
+        timestamp: String,
+    },
     ServiceAdded {
         service_name: String,
         status: String,
@@ -26,6 +39,7 @@ pub enum ServiceEvent {
     },
     ServicesRefreshed {
         count: usize,
+        running: usize,
         timestamp: String,
     },
     DatabaseUpdated {
@@ -33,6 +47,80 @@ pub enum ServiceEvent {
         service_name: String,
         timestamp: String,
     },
+    MetricsUpdated {
+        metrics: Vec<ServiceMetricsSample>,
+        timestamp: String,
+    },
+    ServiceDiscovered {
+        service_name: String,
+        timestamp: String,
+    },
+    ServiceUninstalled {
+        service_name: String,
+        timestamp: String,
+    },
+    ConfigChanged {
+        service_name: String,
+        // Empty when several keys changed at once; config_keys always lists them all
+        config_key: String,
+        #[serde(default)]
+        config_keys: Vec<String>,
+        timestamp: String,
+    },
+    ConfigReloaded {
+        service_name: String,
+        path: String,
+        success: bool,
+        message: String,
+        timestamp: String,
+    },
+    LogFloodDetected {
+        service_name: String,
+        // Journal entries per second over the last window
+        rate: f64,
+        threshold: f64,
+        timestamp: String,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum EventSeverity {
+    Info,
+    Warning,
+    Critical,
+}
+
+impl ServiceEvent {
+    pub fn severity(&self) -> EventSeverity {
+        match self {
+            ServiceEvent::StatusChanged { new_status, .. } => match new_status.as_str() {
+                "Failed" => EventSeverity::Critical,
+                "Stopped" => EventSeverity::Warning,
+                _ => EventSeverity::Info,
+            },
+            ServiceEvent::ServiceFailed { .. } => EventSeverity::Critical,
+            ServiceEvent::ConfigReloaded { success: false, .. } => EventSeverity::Warning,
+            ServiceEvent::LogFloodDetected { .. } => EventSeverity::Warning,
+            _ => EventSeverity::Info,
+        }
+    }
+}
+
+// What listeners receive: the tagged event with its severity alongside the other fields
+#[derive(Debug, Clone, Serialize)]
+pub struct EventPayload<'a> {
+    #[serde(flatten)]
+    pub event: &'a ServiceEvent,
+    pub severity: EventSeverity,
+    // The service's alerts are snoozed: the UI records the event but doesn't notify
+    pub snoozed: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServiceMetricsSample {
+    pub service_name: String,
+    pub cpu_usage: f32,
+    pub memory_usage: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -41,21 +129,167 @@ pub struct ServiceStatusInfo {
     pub status: ServiceStatusEnum,
     pub enabled: bool,
     pub last_check: String,
+    // Watched only through subscribe_service, not tracked in the database
+    #[serde(default)]
+    pub subscribed_only: bool,
+}
+
+// Services the UI has subscribed to, monitored even when they aren't tracked
+pub type ServiceSubscriptions = Arc<Mutex<HashSet<String>>>;
+
+// Metrics are heavier to collect than status, so they run on a slower cadence
+const METRICS_INTERVAL_SECS: u64 = 15;
+
+// Samples older than this are dropped from metrics_history
+const METRICS_HISTORY_RETENTION_DAYS: i64 = 7;
+
+// Installs and uninstalls are rare, so the unit-file list is diffed even less often
+const DISCOVERY_INTERVAL_SECS: u64 = 30;
+
+// Log rates are measured over this window, which is also how often they're checked
+const LOG_RATE_WINDOW_SECS: u64 = 60;
+
+// Entries per second that count as a flood for services without their own threshold
+pub const DEFAULT_LOG_FLOOD_THRESHOLD: f64 = 20.0;
+
+// With nothing tracked, subscribed or monitor_all set, the status loop sleeps this long
+// between checks unless wake_monitor is called first
+const IDLE_POLL_SECS: u64 = 60;
+
+static MONITOR_WAKE: Notify = Notify::const_new();
+
+// The status polling loop, kept so health checks can tell whether it's still alive
+static MONITOR_TASK: OnceLock<JoinHandle<()>> = OnceLock::new();
+
+// app_settings key controlling whether untracked services are watched for failures
+pub const MONITOR_ALL_SETTING: &str = "monitor_all";
+
+// Called when something starts needing the monitor, so an idle loop checks right away
+pub fn wake_monitor() {
+    MONITOR_WAKE.notify_one();
+}
+
+// False before monitoring starts or if the polling loop has died
+pub fn monitor_running() -> bool {
+    MONITOR_TASK.get().is_some_and(|task| !task.is_finished())
+}
+
+// Single exit point for ServiceEvents so every transport sees the same payloads
+pub fn emit_service_event(app_handle: &AppHandle, event: &ServiceEvent) -> tauri::Result<()> {
+    emit_alert_event(app_handle, event, false)
+}
+
+// Like emit_service_event, for alerts that a maintenance snooze may silence
+pub fn emit_alert_event(app_handle: &AppHandle, event: &ServiceEvent, snoozed: bool) -> tauri::Result<()> {
+    let payload = EventPayload {
+        event,
+        severity: event.severity(),
+        snoozed,
+    };
+
+    #[cfg(feature = "event-stream")]
+    crate::event_stream::publish(&payload);
+
+    app_handle.emit("service-event", payload)
+}
+
+// Lets commands notify the UI after they change tracked data
+pub fn emit_database_updated(app_handle: &AppHandle, operation: &str, service_name: &str) {
+    let event = ServiceEvent::DatabaseUpdated {
+        operation: operation.to_string(),
+        service_name: service_name.to_string(),
+        timestamp: chrono::Utc::now().to_rfc3339(),
+    };
+
+    if let Err(e) = emit_service_event(app_handle, &event) {
+        log::error!("❌ Failed to emit database updated event: {}", e);
+    } else {
+        log::debug!("📡 Emitted database updated event: {} {}", operation, service_name);
+    }
+}
+
+// Keeps every open config editor in sync after set_service_config or delete_service_config
+pub fn emit_config_changed(app_handle: &AppHandle, service_name: &str, config_key: &str) {
+    emit_configs_changed(app_handle, service_name, &[config_key.to_string()]);
+}
+
+// One event for a batch of keys written together
+pub fn emit_configs_changed(app_handle: &AppHandle, service_name: &str, config_keys: &[String]) {
+    let event = ServiceEvent::ConfigChanged {
+        service_name: service_name.to_string(),
+        config_key: match config_keys {
+            [config_key] => config_key.clone(),
+            _ => String::new(),
+        },
+        config_keys: config_keys.to_vec(),
+        timestamp: chrono::Utc::now().to_rfc3339(),
+    };
+
+    if let Err(e) = emit_service_event(app_handle, &event) {
+        log::error!("❌ Failed to emit config changed event: {}", e);
+    } else {
+        log::debug!("📡 Emitted config changed event: {} {:?}", service_name, config_keys);
+    }
+}
+
+// Emitted once per output line while install_service or remove_service runs
+pub const INSTALL_PROGRESS_EVENT: &str = "install-progress";
+pub const REMOVE_PROGRESS_EVENT: &str = "remove-progress";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackageProgress {
+    pub package: String,
+    pub stream: String, // stdout, stderr
+    pub line: String,
+    pub timestamp: String,
+}
+
+// Package manager output goes out as its own event rather than a ServiceEvent; it's a live log,
+// not something the monitor or the SSE stream should keep
+pub fn emit_package_progress(app_handle: &AppHandle, event_name: &str, package: &str, stream: &str, line: &str) {
+    let progress = PackageProgress {
+        package: package.to_string(),
+        stream: stream.to_string(),
+        line: line.to_string(),
+        timestamp: chrono::Utc::now().to_rfc3339(),
+    };
+
+    if let Err(e) = app_handle.emit(event_name, progress) {
+        log::error!("❌ Failed to emit {} event: {}", event_name, e);
+    }
 }
 
 pub struct EventManager {
     app_handle: AppHandle,
     database: Arc<Mutex<Database>>,
+    subscriptions: ServiceSubscriptions,
+    cpu_samples: CpuSamples,
     last_known_statuses: Arc<Mutex<Vec<ServiceStatusInfo>>>,
+    // None until the first failed-unit snapshot, so pre-existing failures aren't reported
+    last_failed_units: Arc<Mutex<Option<HashSet<String>>>>,
+    // None until the first unit-file snapshot, so existing units aren't reported as new
+    last_unit_files: Arc<Mutex<Option<HashSet<String>>>>,
+    // Services currently over their log rate threshold, so a flood is reported once
+    flooding_services: Arc<Mutex<HashSet<String>>>,
 }
 
 impl EventManager {
-    pub fn new(app_handle: AppHandle, database: Arc<Mutex<Database>>) -> Self {
+    pub fn new(
+        app_handle: AppHandle,
+        database: Arc<Mutex<Database>>,
+        subscriptions: ServiceSubscriptions,
+        cpu_samples: CpuSamples,
+    ) -> Self {
         log::info!("📡 Creating new EventManager instance");
         Self {
             app_handle,
             database,
+            subscriptions,
+            cpu_samples,
             last_known_statuses: Arc::new(Mutex::new(Vec::new())),
+            last_failed_units: Arc::new(Mutex::new(None)),
+            last_unit_files: Arc::new(Mutex::new(None)),
+            flooding_services: Arc::new(Mutex::new(HashSet::new())),
         }
     }
 
@@ -64,21 +298,97 @@ impl EventManager {
         
         let app_handle = self.app_handle.clone();
         let database = self.database.clone();
+        let subscriptions = self.subscriptions.clone();
         let last_known_statuses = self.last_known_statuses.clone();
+        let last_failed_units = self.last_failed_units.clone();
 
-        tokio::spawn(async move {
+        let monitor_task = tokio::spawn(async move {
             log::info!("🔄 Service monitoring background task started");
             let mut interval = interval(Duration::from_secs(5)); // Check every 5 seconds
             
             loop {
                 interval.tick().await;
                 
-                if let Err(e) = Self::check_service_changes(
+                // Errors count as busy so a flaky database doesn't put the loop to sleep
+                let watching = match Self::check_service_changes(
                     &app_handle,
                     &database,
+                    &subscriptions,
                     &last_known_statuses,
                 ).await {
-                    log::error!("❌ Error checking service changes: {}", e);
+                    Ok(watching) => watching,
+                    Err(e) => {
+                        log::error!("❌ Error checking service changes: {}", e);
+                        true
+                    }
+                };
+                
+                let watching_all = match Self::check_untracked_failures(
+                    &app_handle,
+                    &database,
+                    &last_failed_units,
+                ).await {
+                    Ok(monitor_all) => monitor_all,
+                    Err(e) => {
+                        log::error!("❌ Error checking untracked service failures: {}", e);
+                        true
+                    }
+                };
+                
+                if !watching && !watching_all {
+                    log::debug!("💤 Nothing to monitor, idling until a service is tracked");
+                    let _ = tokio::time::timeout(Duration::from_secs(IDLE_POLL_SECS), MONITOR_WAKE.notified()).await;
+                    interval.reset_immediately();
+                }
+            }
+        });
+        if MONITOR_TASK.set(monitor_task).is_err() {
+            log::warn!("⚠️ Service monitoring was started more than once");
+        }
+
+        let app_handle = self.app_handle.clone();
+        let database = self.database.clone();
+        let subscriptions = self.subscriptions.clone();
+        let cpu_samples = self.cpu_samples.clone();
+
+        tokio::spawn(async move {
+            log::info!("📊 Metrics monitoring background task started");
+            let mut interval = interval(Duration::from_secs(METRICS_INTERVAL_SECS));
+            
+            loop {
+                interval.tick().await;
+                Self::emit_subscribed_metrics(&app_handle, &database, &subscriptions, &cpu_samples).await;
+            }
+        });
+
+        let app_handle = self.app_handle.clone();
+        let database = self.database.clone();
+        let last_unit_files = self.last_unit_files.clone();
+
+        tokio::spawn(async move {
+            log::info!("🔭 Service discovery background task started");
+            let mut interval = interval(Duration::from_secs(DISCOVERY_INTERVAL_SECS));
+            
+            loop {
+                interval.tick().await;
+                if let Err(e) = Self::check_unit_file_changes(&app_handle, &database, &last_unit_files).await {
+                    log::error!("❌ Error checking for installed services: {}", e);
+                }
+            }
+        });
+
+        let app_handle = self.app_handle.clone();
+        let database = self.database.clone();
+        let flooding_services = self.flooding_services.clone();
+
+        tokio::spawn(async move {
+            log::info!("🌊 Log rate monitoring background task started");
+            let mut interval = interval(Duration::from_secs(LOG_RATE_WINDOW_SECS));
+            
+            loop {
+                interval.tick().await;
+                if let Err(e) = Self::check_log_rates(&app_handle, &database, &flooding_services).await {
+                    log::error!("❌ Error checking service log rates: {}", e);
                 }
             }
         });
@@ -86,17 +396,154 @@ impl EventManager {
         log::info!("✅ Service monitoring started - checking every 5 seconds");
     }
 
+    // Collects metrics for subscribed services, emits them as a single batch and keeps them
+    // in metrics_history
+    async fn emit_subscribed_metrics(
+        app_handle: &AppHandle,
+        database: &Arc<Mutex<Database>>,
+        subscriptions: &ServiceSubscriptions,
+        cpu_samples: &CpuSamples,
+    ) {
+        // Copy the names out so the lock isn't held while metrics are collected
+        let names: Vec<String> = subscriptions.lock().await.iter().cloned().collect();
+        if names.is_empty() {
+            return;
+        }
+
+        let mut metrics = Vec::with_capacity(names.len());
+        let mut snapshots = Vec::with_capacity(names.len());
+        for name in names {
+            match service_metrics(name.clone(), cpu_samples, &CancelToken::default()).await {
+                Ok(sample) => {
+                    snapshots.push(MetricsSnapshot {
+                        service_name: name.clone(),
+                        cpu_usage: sample.cpu_usage,
+                        memory_usage: sample.memory_usage,
+                        network_in: sample.network_in,
+                        network_out: sample.network_out,
+                        disk_read: sample.disk_read,
+                        disk_write: sample.disk_write,
+                        recorded_at: sample.timestamp,
+                    });
+                    metrics.push(ServiceMetricsSample {
+                        service_name: name,
+                        cpu_usage: sample.cpu_usage,
+                        memory_usage: sample.memory_usage,
+                    });
+                }
+                Err(e) => log::debug!("⚠️ Failed to collect metrics for {}: {}", name, e),
+            }
+        }
+
+        if metrics.is_empty() {
+            return;
+        }
+
+        {
+            let db = database.lock().await;
+            if let Err(e) = db.record_metrics_snapshots(&snapshots).await {
+                log::warn!("⚠️ Failed to record metrics history: {}", e);
+            }
+            let cutoff = chrono::Utc::now() - chrono::Duration::days(METRICS_HISTORY_RETENTION_DAYS);
+            match db.prune_metrics_history(cutoff).await {
+                Ok(0) => {}
+                Ok(pruned) => log::debug!("🧹 Pruned {} old metrics samples", pruned),
+                Err(e) => log::warn!("⚠️ Failed to prune metrics history: {}", e),
+            }
+        }
+
+        let count = metrics.len();
+        let event = ServiceEvent::MetricsUpdated {
+            metrics,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+        };
+
+        if let Err(e) = emit_service_event(app_handle, &event) {
+            log::error!("❌ Failed to emit metrics event: {}", e);
+        } else {
+            log::debug!("📡 Emitted metrics for {} subscribed services", count);
+        }
+    }
+
+    // Diffs the installed unit files against the last snapshot to spot installs and uninstalls,
+    // then reports the refreshed totals
+    async fn check_unit_file_changes(
+        app_handle: &AppHandle,
+        database: &Arc<Mutex<Database>>,
+        last_unit_files: &Arc<Mutex<Option<HashSet<String>>>>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let unit_files = tokio::task::spawn_blocking(list_service_unit_files).await??;
+        let current: HashSet<String> = unit_files
+            .into_iter()
+            .map(|(unit, _)| unit.trim_end_matches(".service").to_string())
+            .collect();
+
+        let mut last = last_unit_files.lock().await;
+        if let Some(previous) = last.as_ref() {
+            let timestamp = chrono::Utc::now().to_rfc3339();
+
+            for name in current.difference(previous) {
+                log::info!("🆕 Service installed: {}", name);
+                let event = ServiceEvent::ServiceDiscovered {
+                    service_name: name.clone(),
+                    timestamp: timestamp.clone(),
+                };
+                if let Err(e) = emit_service_event(app_handle, &event) {
+                    log::error!("❌ Failed to emit service discovered event: {}", e);
+                }
+            }
+
+            for name in previous.difference(&current) {
+                log::info!("🗑️ Service uninstalled: {}", name);
+                let event = ServiceEvent::ServiceUninstalled {
+                    service_name: name.clone(),
+                    timestamp: timestamp.clone(),
+                };
+                if let Err(e) = emit_service_event(app_handle, &event) {
+                    log::error!("❌ Failed to emit service uninstalled event: {}", e);
+                }
+            }
+        } else {
+            log::debug!("📋 Recorded baseline of {} installed services", current.len());
+        }
+
+        // Templates and blocklisted units aren't listed, so they aren't counted either
+        let count = count_visible_services(database, current.iter().map(String::as_str)).await;
+        *last = Some(current);
+        drop(last);
+
+        let unit_states = tokio::task::spawn_blocking(list_service_unit_states).await?;
+        let running = unit_states
+            .values()
+            .filter(|(_, _, sub_state)| sub_state == "running")
+            .count();
+
+        let event = ServiceEvent::ServicesRefreshed {
+            count,
+            running,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+        };
+        if let Err(e) = emit_service_event(app_handle, &event) {
+            log::error!("❌ Failed to emit services refreshed event: {}", e);
+        } else {
+            log::debug!("📡 Emitted services refreshed event: {} installed, {} running", count, running);
+        }
+
+        Ok(())
+    }
+
     async fn check_service_changes(
         app_handle: &AppHandle,
         database: &Arc<Mutex<Database>>,
+        subscriptions: &ServiceSubscriptions,
         last_known_statuses: &Arc<Mutex<Vec<ServiceStatusInfo>>>,
-    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    ) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
         log::debug!("🔍 Checking for service status changes");
         
         // Get tracked services from database
-        let tracked_services = {
+        let (tracked_services, snoozed) = {
             let db = database.lock().await;
-            match db.get_tracked_services().await {
+            let services = match db.get_tracked_services().await {
                 Ok(services) => {
                     log::debug!("📋 Retrieved {} tracked services from database", services.len());
                     services
@@ -105,42 +552,77 @@ impl EventManager {
                     log::error!("❌ Failed to get tracked services from database: {}", e);
                     return Err(Box::new(e));
                 }
-            }
+            };
+            (services, Self::snoozed_services(&db).await?)
         };
 
         let mut current_statuses = Vec::new();
         let timestamp = chrono::Utc::now().to_rfc3339();
 
-        // Check status for each tracked and enabled service
-        let enabled_services: Vec<_> = tracked_services.iter().filter(|ts| ts.enabled).collect();
-        log::debug!("🔍 Checking status for {} enabled tracked services", enabled_services.len());
+        // Check status for each tracked and enabled service, plus any subscribed ones
+        let mut monitored: Vec<(String, bool)> = tracked_services
+            .iter()
+            .filter(|ts| ts.enabled)
+            .map(|ts| (ts.name.clone(), false))
+            .collect();
+        for name in subscriptions.lock().await.iter() {
+            if !tracked_services.iter().any(|ts| ts.enabled && &ts.name == name) {
+                monitored.push((name.clone(), true));
+            }
+        }
+        log::debug!("🔍 Checking status for {} monitored services", monitored.len());
         
-        for tracked_service in enabled_services {
-            log::debug!("🔍 Checking status for service: {}", tracked_service.name);
-            
-            match get_service_status_internal(&tracked_service.name).await {
-                Ok(service) => {
+        // Nothing watched now or last time means there are no statuses to read or diff
+        if monitored.is_empty() && last_known_statuses.lock().await.is_empty() {
+            return Ok(false);
+        }
+        
+        // One systemctl show covers every monitored unit instead of several spawns per service
+        let names: Vec<String> = monitored.iter().map(|(name, _)| name.clone()).collect();
+        let services = match local_service_statuses(names).await {
+            Ok(services) => services.into_iter().map(Some).collect(),
+            Err(e) => {
+                log::warn!("⚠️ Failed to get status for monitored services: {}", e);
+                vec![None; monitored.len()]
+            }
+        };
+        
+        for ((name, subscribed_only), service) in monitored.into_iter().zip(services) {
+            match service {
+                // Names that don't resolve come back from the batch as Unknown and not enabled
+                Some(service) => {
                     log::debug!("✅ Service {} status: {:?}", service.name, service.status);
                     current_statuses.push(ServiceStatusInfo {
-                        name: service.name.clone(),
-                        status: service.status.clone(),
+                        name,
+                        status: service.status,
                         enabled: service.enabled,
                         last_check: timestamp.clone(),
+                        subscribed_only,
                     });
                 }
-                Err(e) => {
-                    log::warn!("⚠️ Failed to get status for service {}: {}", tracked_service.name, e);
+                None => {
                     // Add with unknown status
                     current_statuses.push(ServiceStatusInfo {
-                        name: tracked_service.name.clone(),
+                        name,
                         status: ServiceStatusEnum::Unknown,
                         enabled: false,
                         last_check: timestamp.clone(),
+                        subscribed_only,
                     });
                 }
             }
         }
 
+        // Persist what was seen so the next launch can show it before the first poll
+        let readings: Vec<(String, String)> = current_statuses
+            .iter()
+            .filter(|status| !status.subscribed_only)
+            .map(|status| (status.name.clone(), format!("{:?}", status.status)))
+            .collect();
+        if let Err(e) = database.lock().await.record_service_statuses(&readings).await {
+            log::warn!("⚠️ Failed to record service statuses: {}", e);
+        }
+
         // Compare with last known statuses
         let mut last_statuses = last_known_statuses.lock().await;
         log::debug!("🔄 Comparing {} current statuses with {} last known statuses", 
@@ -150,8 +632,13 @@ impl EventManager {
             if let Some(last) = last_statuses.iter().find(|s| s.name == current.name) {
                 // Check if status changed
                 if last.status != current.status {
-                    log::info!("🔄 Service {} status changed: {:?} -> {:?}", 
-                             current.name, last.status, current.status);
+                    if current.status.is_transitional() || last.status.is_transitional() {
+                        log::debug!("⏳ Service {} transitioning: {:?} -> {:?}",
+                                  current.name, last.status, current.status);
+                    } else {
+                        log::info!("🔄 Service {} status changed: {:?} -> {:?}", 
+                                 current.name, last.status, current.status);
+                    }
                     
                     let event = ServiceEvent::StatusChanged {
                         service_name: current.name.clone(),
@@ -160,12 +647,34 @@ impl EventManager {
                         timestamp: timestamp.clone(),
                     };
                     
-                    if let Err(e) = app_handle.emit("service-event", &event) {
+                    if let Err(e) = emit_alert_event(app_handle, &event, snoozed.contains(&current.name)) {
                         log::error!("❌ Failed to emit service status change event: {}", e);
                     } else {
                         log::debug!("📡 Emitted status change event for service: {}", current.name);
                     }
                 }
+
+                // A failed status lookup reports enabled=false, so only diff real readings
+                let readings_valid = last.status != ServiceStatusEnum::Unknown
+                    && current.status != ServiceStatusEnum::Unknown;
+                if readings_valid && last.enabled != current.enabled {
+                    log::info!("🔄 Service {} enabled state changed: {} -> {}",
+                             current.name, last.enabled, current.enabled);
+
+                    let event = ServiceEvent::EnabledChanged {
+                        service_name: current.name.clone(),
+                        enabled: current.enabled,
+                        timestamp: timestamp.clone(),
+                    };
+
+                    if let Err(e) = emit_service_event(app_handle, &event) {
+                        log::error!("❌ Failed to emit enabled change event: {}", e);
+                    } else {
+                        log::debug!("📡 Emitted enabled change event for service: {}", current.name);
+                    }
+                }
+            } else if current.subscribed_only {
+                log::debug!("👀 Started watching subscribed service: {}", current.name);
             } else {
                 // New service detected
                 log::info!("🆕 New tracked service detected: {}", current.name);
@@ -176,7 +685,7 @@ impl EventManager {
                     timestamp: timestamp.clone(),
                 };
                 
-                if let Err(e) = app_handle.emit("service-event", &event) {
+                if let Err(e) = emit_service_event(app_handle, &event) {
                     log::error!("❌ Failed to emit service added event: {}", e);
                 } else {
                     log::debug!("📡 Emitted service added event for: {}", current.name);
@@ -186,6 +695,12 @@ impl EventManager {
 
         // Check for removed services
         for last in last_statuses.iter() {
+            if last.subscribed_only {
+                if !current_statuses.iter().any(|s| s.name == last.name) {
+                    log::debug!("👀 Stopped watching subscribed service: {}", last.name);
+                }
+                continue;
+            }
             if !current_statuses.iter().any(|s| s.name == last.name) {
                 log::info!("🗑️ Service removed from tracking: {}", last.name);
                 
@@ -194,7 +709,7 @@ impl EventManager {
                     timestamp: timestamp.clone(),
                 };
                 
-                if let Err(e) = app_handle.emit("service-event", &event) {
+                if let Err(e) = emit_service_event(app_handle, &event) {
                     log::error!("❌ Failed to emit service removed event: {}", e);
                 } else {
                     log::debug!("📡 Emitted service removed event for: {}", last.name);
@@ -203,9 +718,142 @@ impl EventManager {
         }
 
         // Update last known statuses
+        let watching = !current_statuses.is_empty();
         *last_statuses = current_statuses;
         log::debug!("✅ Service status check completed successfully");
 
+        Ok(watching)
+    }
+
+    // Reports tracked services whose journal rate crossed their threshold since the last check
+    async fn check_log_rates(
+        app_handle: &AppHandle,
+        database: &Arc<Mutex<Database>>,
+        flooding_services: &Arc<Mutex<HashSet<String>>>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let (tracked_services, snoozed) = {
+            let db = database.lock().await;
+            (db.get_tracked_services().await?, Self::snoozed_services(&db).await?)
+        };
+
+        let mut flooding = flooding_services.lock().await;
+        let mut still_flooding = HashSet::new();
+        let timestamp = chrono::Utc::now().to_rfc3339();
+
+        // Containers don't log to the journal
+        for service in tracked_services.iter().filter(|ts| ts.enabled && !ts.name.starts_with(DOCKER_PREFIX)) {
+            let name = service.name.clone();
+            let rate = match tokio::task::spawn_blocking(move || log_entry_rate(&name, LOG_RATE_WINDOW_SECS)).await? {
+                Ok(rate) => rate,
+                Err(e) => {
+                    log::debug!("⚠️ Failed to measure log rate for {}: {}", service.name, e);
+                    continue;
+                }
+            };
+
+            let threshold = service.log_flood_threshold.unwrap_or(DEFAULT_LOG_FLOOD_THRESHOLD);
+            if rate <= threshold {
+                continue;
+            }
+
+            still_flooding.insert(service.name.clone());
+            if flooding.contains(&service.name) {
+                continue;
+            }
+
+            log::warn!("🌊 {} is logging {:.1} entries/s (threshold {:.1})", service.name, rate, threshold);
+            let event = ServiceEvent::LogFloodDetected {
+                service_name: service.name.clone(),
+                rate,
+                threshold,
+                timestamp: timestamp.clone(),
+            };
+            if let Err(e) = emit_alert_event(app_handle, &event, snoozed.contains(&service.name)) {
+                log::error!("❌ Failed to emit log flood event: {}", e);
+            } else {
+                log::debug!("📡 Emitted log flood event for: {}", service.name);
+            }
+        }
+
+        *flooding = still_flooding;
         Ok(())
     }
+
+    // Names of services whose alerts are currently snoozed
+    async fn snoozed_services(db: &Database) -> Result<HashSet<String>, sqlx::Error> {
+        Ok(db
+            .get_snoozed_alerts()
+            .await?
+            .into_iter()
+            .map(|snooze| snooze.service_name)
+            .collect())
+    }
+
+    // Watches every service for new failures with a single list-units call when monitor_all is
+    // on; returns whether it is
+    async fn check_untracked_failures(
+        app_handle: &AppHandle,
+        database: &Arc<Mutex<Database>>,
+        last_failed_units: &Arc<Mutex<Option<HashSet<String>>>>,
+    ) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+        let (monitor_all, tracked_names, snoozed) = {
+            let db = database.lock().await;
+            let monitor_all = db.get_app_setting(MONITOR_ALL_SETTING).await?.as_deref() == Some("true");
+            let tracked: HashSet<String> = if monitor_all {
+                db.get_tracked_services().await?.into_iter().map(|ts| ts.name).collect()
+            } else {
+                HashSet::new()
+            };
+            (monitor_all, tracked, Self::snoozed_services(&db).await?)
+        };
+
+        let mut last_failed = last_failed_units.lock().await;
+        if !monitor_all {
+            // Drop the baseline so re-enabling doesn't report failures from while it was off
+            *last_failed = None;
+            return Ok(false);
+        }
+
+        let output = tokio::process::Command::new("systemctl")
+            .args(&["list-units", "--state=failed", "--type=service", "--no-legend", "--plain", "--no-pager"])
+            .output()
+            .await?;
+
+        let current_failed: HashSet<String> = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| line.split_whitespace().next())
+            .filter(|unit| unit.ends_with(".service"))
+            .map(|unit| unit.to_string())
+            .collect();
+
+        if let Some(previous) = last_failed.as_ref() {
+            let timestamp = chrono::Utc::now().to_rfc3339();
+            for unit in current_failed.difference(previous) {
+                let name = unit.trim_end_matches(".service");
+                // Tracked services already get full status diffing
+                if tracked_names.contains(name) || tracked_names.contains(unit) {
+                    continue;
+                }
+
+                log::warn!("💥 Untracked service failed: {}", unit);
+                let event = ServiceEvent::ServiceFailed {
+                    service_name: name.to_string(),
+                    tracked: false,
+                    timestamp: timestamp.clone(),
+                };
+
+                let is_snoozed = snoozed.contains(name) || snoozed.contains(unit);
+                if let Err(e) = emit_alert_event(app_handle, &event, is_snoozed) {
+                    log::error!("❌ Failed to emit service failed event: {}", e);
+                } else {
+                    log::debug!("📡 Emitted service failed event for: {}", unit);
+                }
+            }
+        } else {
+            log::debug!("📋 Recorded baseline of {} failed units", current_failed.len());
+        }
+
+        *last_failed = Some(current_failed);
+        Ok(true)
+    }
 }
\ No newline at end of file
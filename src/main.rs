@@ -1,23 +1,28 @@
 // Prevents additional console window on Windows in release, DO NOT REMOVE!!
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+mod app_log;
 mod services;
 mod database;
 mod events;
+mod docker;
+mod config_watch;
+mod error;
+mod ssh;
+#[cfg(feature = "event-stream")]
+mod event_stream;
 
 use services::*;
 use database::Database;
-use events::EventManager;
+use config_watch::ConfigWatches;
+use events::{EventManager, ServiceSubscriptions};
 use std::sync::Arc;
 use tokio::sync::Mutex;
-use tauri::Manager;
+use tauri::{Manager, RunEvent};
 
 fn main() {
-    // Initialize logging with more detailed configuration
-    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info"))
-        .format_timestamp_millis()
-        .format_module_path(false)
-        .init();
+    // Logs go to stderr and to the buffer behind get_app_logs
+    app_log::init();
     
     log::info!("🚀 Starting Dev Services Manager application");
     log::info!("📋 Version: {}", env!("CARGO_PKG_VERSION"));
@@ -56,11 +61,114 @@ fn main() {
             update_service_tracking_status,
             get_all_system_services,
             set_service_config,
-            get_service_configs
+            get_service_configs,
+            get_unit_file,
+            get_unit_dropins,
+            write_unit_dropin,
+            reload_daemon,
+            get_service_properties,
+            get_boot_timing,
+            get_boot_summary,
+            get_timers,
+            get_sockets,
+            get_oom_events,
+            cancel_terminal_command,
+            get_terminal_job_status,
+            get_multi_service_logs,
+            search_service_logs,
+            export_service_logs,
+            get_service_environment,
+            set_service_environment,
+            unset_service_environment,
+            get_docker_containers,
+            get_container_logs,
+            get_container_metrics,
+            get_service_processes,
+            kill_service_process,
+            get_system_summary,
+            get_monitor_all,
+            set_monitor_all,
+            start_service_transient,
+            subscribe_service,
+            unsubscribe_service,
+            reorder_tracked_services,
+            get_tracked_services_by_tag,
+            add_service_tag,
+            remove_service_tag,
+            get_service_tags,
+            set_service_note,
+            generate_status_report,
+            check_port_open,
+            backup_database,
+            restore_database,
+            check_database_integrity,
+            vacuum_database,
+            get_service_blocklist,
+            set_service_blocklist,
+            start_event_stream,
+            get_disk_usage,
+            get_hosts,
+            add_host,
+            remove_host,
+            get_boots,
+            enable_multiple_services,
+            disable_multiple_services,
+            watch_service_config,
+            unwatch_service_config,
+            snooze_service_alerts,
+            unsnooze_service_alerts,
+            get_snoozed_services,
+            delete_service_config,
+            get_targets,
+            get_default_target,
+            set_default_target,
+            isolate_target,
+            get_restart_leaderboard,
+            get_backend_info,
+            get_tracked_service,
+            update_tracked_service,
+            get_service_config_or_default,
+            set_service_configs,
+            schedule_service,
+            unschedule_service,
+            get_schedules,
+            find_service_by_port,
+            ensure_service_state,
+            stop_session_services,
+            get_session_services,
+            get_stop_on_exit,
+            set_stop_on_exit,
+            cancel_request,
+            get_service_statuses,
+            export_service_inventory,
+            get_service_limits,
+            set_service_limit,
+            get_app_logs,
+            get_kernel_logs,
+            set_log_flood_threshold,
+            diff_service_metrics,
+            get_undo_stack,
+            undo_last_operation,
+            get_service_config_files,
+            get_readable_roots,
+            read_text_file,
+            restart_failed_services,
+            set_service_critical,
+            get_process_info,
+            stop_process_by_pid,
+            diff_services,
+            diff_service_across_hosts,
+            get_log_defaults,
+            set_log_defaults,
+            check_service_update,
+            install_service,
+            get_service_package
         ])
         .setup(|app| {
             log::info!("🔧 Setting up application components");
             
+            services::detect_backend();
+            
             // Initialize database
             let rt = tokio::runtime::Runtime::new().unwrap();
             let db = rt.block_on(async {
@@ -81,9 +189,18 @@ fn main() {
             app.manage(db_arc.clone());
             log::info!("📦 Database instance managed in app state");
             
+            app.manage(TerminalJobs::default());
+            app.manage(PendingRequests::default());
+            let cpu_samples = CpuSamples::default();
+            app.manage(cpu_samples.clone());
+            app.manage(ConfigWatches::default());
+            
+            let subscriptions = ServiceSubscriptions::default();
+            app.manage(subscriptions.clone());
+            
             // Initialize event manager and start monitoring
             log::info!("📡 Initializing event manager");
-            let event_manager = EventManager::new(app.handle().clone(), db_arc);
+            let event_manager = EventManager::new(app.handle().clone(), db_arc, subscriptions, cpu_samples);
             rt.spawn(async move {
                 log::info!("🔄 Starting service monitoring background task");
                 event_manager.start_monitoring().await;
@@ -93,6 +210,38 @@ fn main() {
             log::info!("🎯 Application ready to handle requests");
             Ok(())
         })
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app_handle, event| {
+            if let RunEvent::Exit = event {
+                // Dropping the watchers stops their threads before the runtime shuts down
+                let watches = app_handle.state::<ConfigWatches>();
+                let count = watches.blocking_lock().drain().count();
+                if count > 0 {
+                    log::info!("🛑 Stopped {} config watches", count);
+                }
+
+                // Opt-in: stop whatever this session started so dev servers don't outlive the app
+                let db = app_handle.state::<Arc<Mutex<Database>>>();
+                let stop_on_exit = tauri::async_runtime::block_on(async {
+                    db.lock().await.get_app_setting(STOP_ON_EXIT_SETTING).await
+                });
+                match stop_on_exit {
+                    Ok(Some(value)) if value == "true" => {
+                        // There's no one to ask for a password now, so this relies on cached sudo credentials
+                        let results = stop_session_services_blocking(None);
+                        let failed = results.iter().filter(|result| !result.success).count();
+                        log::info!("🛑 Stopped {} session services on exit ({} failed)", results.len() - failed, failed);
+                    }
+                    Ok(_) => {}
+                    Err(e) => log::warn!("⚠️ Failed to read stop-on-exit setting: {}", e),
+                }
+
+                // Transient overrides must not survive into the next start; this also relies on cached sudo credentials
+                let removed = remove_pending_transient_dropins_blocking(None);
+                if removed > 0 {
+                    log::info!("🧹 Removed {} transient override drop-ins on exit", removed);
+                }
+            }
+        });
 }
\ No newline at end of file
@@ -1,15 +1,36 @@
 import { Component, createContext, useContext, ParentComponent, onMount, onCleanup, createSignal } from 'solid-js'
 import { listen, UnlistenFn } from '@tauri-apps/api/event'
 
+export interface ServiceMetricsSample {
+  service_name: string
+  cpu_usage: number
+  memory_usage: number
+}
+
+export type EventSeverity = 'Info' | 'Warning' | 'Critical'
+
 export interface ServiceEvent {
-  type: 'StatusChanged' | 'ServiceAdded' | 'ServiceRemoved' | 'ServicesRefreshed' | 'DatabaseUpdated'
+  type: 'StatusChanged' | 'EnabledChanged' | 'ServiceFailed' | 'ServiceAdded' | 'ServiceRemoved' | 'ServicesRefreshed' | 'DatabaseUpdated' | 'MetricsUpdated' | 'ConfigReloaded' | 'ServiceDiscovered' | 'ServiceUninstalled' | 'ConfigChanged' | 'LogFloodDetected'
   service_name?: string
   old_status?: string
   new_status?: string
   status?: string
+  enabled?: boolean
+  tracked?: boolean
   timestamp: string
+  severity: EventSeverity
+  snoozed: boolean
   count?: number
+  running?: number
   operation?: string
+  metrics?: ServiceMetricsSample[]
+  path?: string
+  config_key?: string
+  config_keys?: string[]
+  success?: boolean
+  message?: string
+  rate?: number
+  threshold?: number
 }
 
 interface EventContextType {
@@ -17,10 +38,16 @@ interface EventContextType {
   isConnected: () => boolean
   eventCount: () => number
   onServiceStatusChange: (callback: (event: ServiceEvent) => void) => void
+  onEnabledChanged: (callback: (event: ServiceEvent) => void) => void
   onServiceAdded: (callback: (event: ServiceEvent) => void) => void
   onServiceRemoved: (callback: (event: ServiceEvent) => void) => void
   onServicesRefreshed: (callback: (event: ServiceEvent) => void) => void
   onDatabaseUpdated: (callback: (event: ServiceEvent) => void) => void
+  onMetricsUpdated: (callback: (event: ServiceEvent) => void) => void
+  onConfigReloaded: (callback: (event: ServiceEvent) => void) => void
+  onServiceInstallChanged: (callback: (event: ServiceEvent) => void) => void
+  onConfigChanged: (callback: (event: ServiceEvent) => void) => void
+  onLogFloodDetected: (callback: (event: ServiceEvent) => void) => void
 }
 
 const EventContext = createContext<EventContextType>()
@@ -32,10 +59,16 @@ export const EventProvider: ParentComponent = (props) => {
   
   // Callback registries
   let statusChangeCallbacks: ((event: ServiceEvent) => void)[] = []
+  let enabledChangeCallbacks: ((event: ServiceEvent) => void)[] = []
   let serviceAddedCallbacks: ((event: ServiceEvent) => void)[] = []
   let serviceRemovedCallbacks: ((event: ServiceEvent) => void)[] = []
   let servicesRefreshedCallbacks: ((event: ServiceEvent) => void)[] = []
   let databaseUpdatedCallbacks: ((event: ServiceEvent) => void)[] = []
+  let metricsUpdatedCallbacks: ((event: ServiceEvent) => void)[] = []
+  let configReloadedCallbacks: ((event: ServiceEvent) => void)[] = []
+  let installChangedCallbacks: ((event: ServiceEvent) => void)[] = []
+  let configChangedCallbacks: ((event: ServiceEvent) => void)[] = []
+  let logFloodCallbacks: ((event: ServiceEvent) => void)[] = []
   
   let unlisten: UnlistenFn | null = null
 
@@ -47,8 +80,12 @@ export const EventProvider: ParentComponent = (props) => {
     // Dispatch to appropriate callback lists
     switch (event.type) {
       case 'StatusChanged':
+      case 'ServiceFailed':
         statusChangeCallbacks.forEach(callback => callback(event))
         break
+      case 'EnabledChanged':
+        enabledChangeCallbacks.forEach(callback => callback(event))
+        break
       case 'ServiceAdded':
         serviceAddedCallbacks.forEach(callback => callback(event))
         break
@@ -61,6 +98,22 @@ export const EventProvider: ParentComponent = (props) => {
       case 'DatabaseUpdated':
         databaseUpdatedCallbacks.forEach(callback => callback(event))
         break
+      case 'MetricsUpdated':
+        metricsUpdatedCallbacks.forEach(callback => callback(event))
+        break
+      case 'ConfigReloaded':
+        configReloadedCallbacks.forEach(callback => callback(event))
+        break
+      case 'ServiceDiscovered':
+      case 'ServiceUninstalled':
+        installChangedCallbacks.forEach(callback => callback(event))
+        break
+      case 'ConfigChanged':
+        configChangedCallbacks.forEach(callback => callback(event))
+        break
+      case 'LogFloodDetected':
+        logFloodCallbacks.forEach(callback => callback(event))
+        break
     }
   }
 
@@ -68,6 +121,10 @@ export const EventProvider: ParentComponent = (props) => {
     statusChangeCallbacks.push(callback)
   }
 
+  const onEnabledChanged = (callback: (event: ServiceEvent) => void) => {
+    enabledChangeCallbacks.push(callback)
+  }
+
   const onServiceAdded = (callback: (event: ServiceEvent) => void) => {
     serviceAddedCallbacks.push(callback)
   }
@@ -84,6 +141,26 @@ export const EventProvider: ParentComponent = (props) => {
     databaseUpdatedCallbacks.push(callback)
   }
 
+  const onMetricsUpdated = (callback: (event: ServiceEvent) => void) => {
+    metricsUpdatedCallbacks.push(callback)
+  }
+
+  const onConfigReloaded = (callback: (event: ServiceEvent) => void) => {
+    configReloadedCallbacks.push(callback)
+  }
+
+  const onServiceInstallChanged = (callback: (event: ServiceEvent) => void) => {
+    installChangedCallbacks.push(callback)
+  }
+
+  const onConfigChanged = (callback: (event: ServiceEvent) => void) => {
+    configChangedCallbacks.push(callback)
+  }
+
+  const onLogFloodDetected = (callback: (event: ServiceEvent) => void) => {
+    logFloodCallbacks.push(callback)
+  }
+
   onMount(async () => {
     try {
       // Listen for service events from the backend
@@ -112,10 +189,16 @@ export const EventProvider: ParentComponent = (props) => {
     
     // Clear callback arrays
     statusChangeCallbacks = []
+    enabledChangeCallbacks = []
     serviceAddedCallbacks = []
     serviceRemovedCallbacks = []
     servicesRefreshedCallbacks = []
     databaseUpdatedCallbacks = []
+    metricsUpdatedCallbacks = []
+    configReloadedCallbacks = []
+    installChangedCallbacks = []
+    configChangedCallbacks = []
+    logFloodCallbacks = []
   })
 
   const value: EventContextType = {
@@ -123,10 +206,16 @@ export const EventProvider: ParentComponent = (props) => {
     isConnected,
     eventCount,
     onServiceStatusChange,
+    onEnabledChanged,
     onServiceAdded,
     onServiceRemoved,
     onServicesRefreshed,
     onDatabaseUpdated,
+    onMetricsUpdated,
+    onConfigReloaded,
+    onServiceInstallChanged,
+    onConfigChanged,
+    onLogFloodDetected,
   }
 
   return (
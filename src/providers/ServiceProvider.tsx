@@ -1,7 +1,7 @@
 import { Component, createContext, createSignal, useContext, ParentComponent, onMount, createEffect } from 'solid-js'
 import { invoke } from '@tauri-apps/api/core'
 import toast from 'solid-toast'
-import { Service, ServiceOperation } from '../types/service'
+import { Service, ServiceOperation, errorMessage, isCommandError } from '../types/service'
 import { useEvents } from './EventProvider'
 
 interface AuthRequest {
@@ -98,7 +98,7 @@ export const ServiceProvider: ParentComponent = (props) => {
       setTotalServicesCount(enabledTrackedServices.length)
       
     } catch (error) {
-      console.error(`Failed to load services: ${error}`)
+      console.error(`Failed to load services: ${errorMessage(error)}`)
       // Fallback to original method if database fails
       try {
         const fallbackServices = await invoke<Service[]>('get_services', { limit: 8, showAll: false })
@@ -117,7 +117,7 @@ export const ServiceProvider: ParentComponent = (props) => {
       setShowingAll(true)
       await refreshServices(true) // Use existing refresh logic but skip the loading state since we already set it
     } catch (error) {
-      console.error(`Failed to load all services: ${error}`)
+      console.error(`Failed to load all services: ${errorMessage(error)}`)
       setShowingAll(false) // Reset on error
     } finally {
       setLoading(false)
@@ -131,7 +131,7 @@ export const ServiceProvider: ParentComponent = (props) => {
       setDisplayLimit(newLimit)
       await refreshServices(true) // Refresh with new limit
     } catch (error) {
-      console.error(`Failed to load more services: ${error}`)
+      console.error(`Failed to load more services: ${errorMessage(error)}`)
     }
   }
 
@@ -155,7 +155,7 @@ export const ServiceProvider: ParentComponent = (props) => {
     try {
       return await invoke<AuthRequest>('check_auth_required', { serviceName })
     } catch (error) {
-      console.error(`Failed to check auth requirements: ${error}`)
+      console.error(`Failed to check auth requirements: ${errorMessage(error)}`)
       return { requires_auth: true, message: 'Authentication required' }
     }
   }
@@ -163,10 +163,18 @@ export const ServiceProvider: ParentComponent = (props) => {
   const handleServiceOperation = async (
     operation: string,
     serviceName: string,
-    invokeCommand: string
+    invokeCommand: string,
+    confirmed?: boolean
   ) => {
     try {
-      const result = await invoke<ServiceOperation>(invokeCommand, { serviceName })
+      const result = await invoke<ServiceOperation>(invokeCommand, { serviceName, confirmed })
+      if (!result.success && !confirmed && result.message.includes('requires confirmation')) {
+        // Critical services are refused until the user confirms
+        if (window.confirm(`${result.message}. Continue?`)) {
+          await handleServiceOperation(operation, serviceName, invokeCommand, true)
+        }
+        return
+      }
       if (result.success) {
         console.log(`Success: ${result.message}`)
         
@@ -195,8 +203,8 @@ export const ServiceProvider: ParentComponent = (props) => {
         })
       }
     } catch (error) {
-      console.error(`Failed to ${operation} ${serviceName}: ${error}`)
-      toast.error(`Failed to ${operation} service: ${error}`, {
+      console.error(`Failed to ${operation} ${serviceName}: ${errorMessage(error)}`)
+      toast.error(`Failed to ${operation} service: ${errorMessage(error)}`, {
         duration: 5000,
         icon: '❌'
       })
@@ -206,8 +214,9 @@ export const ServiceProvider: ParentComponent = (props) => {
   const handleAuthenticatedOperation = async (
     serviceName: string,
     action: string,
-    password?: string
-  ) => {
+    password?: string,
+    confirmed?: boolean
+  ): Promise<void> => {
     try {
       // if (!password) {
       //   throw new Error('Password required')
@@ -218,11 +227,19 @@ export const ServiceProvider: ParentComponent = (props) => {
       if (action === 'start') {
         result = await invoke<ServiceOperation>('start_service_with_auth', { serviceName, password })
       } else if (action === 'stop') {
-        result = await invoke<ServiceOperation>('stop_service_with_auth', { serviceName, password })
+        result = await invoke<ServiceOperation>('stop_service_with_auth', { serviceName, password, confirmed })
       } else {
         throw new Error(`Unsupported action: ${action}`)
       }
 
+      if (!result.success && !confirmed && result.message.includes('requires confirmation')) {
+        // Critical services are refused until the user confirms
+        if (window.confirm(`${result.message}. Continue?`)) {
+          await handleAuthenticatedOperation(serviceName, action, password, true)
+        }
+        return
+      }
+
       if (result.success) {
         console.log(`Success: ${result.message}`)
         
@@ -262,9 +279,9 @@ export const ServiceProvider: ParentComponent = (props) => {
         }
       }
     } catch (error) {
-      const errorStr = String(error)
+      const errorStr = errorMessage(error)
       console.log('errorStr', errorStr)
-      if (errorStr.includes('Authentication failed') || errorStr.includes('Password required') || errorStr.includes('authentication')  || errorStr.includes('Authentication') || errorStr.includes('password') || errorStr.includes('Password') || errorStr.includes('Authentication required')) {
+      if ((isCommandError(error) && error.code === 'PermissionDenied') || errorStr.includes('Authentication failed') || errorStr.includes('Password required') || errorStr.includes('authentication')  || errorStr.includes('Authentication') || errorStr.includes('password') || errorStr.includes('Password') || errorStr.includes('Authentication required')) {
         // Show password dialog
         setCurrentAuthAction({
           serviceName,
@@ -273,8 +290,8 @@ export const ServiceProvider: ParentComponent = (props) => {
         })
         setShowPasswordDialog(true)
       } else {
-        console.error(`Failed to ${action} ${serviceName}: ${error}`)
-        toast.error(`Failed to ${action} service: ${error}`, {
+        console.error(`Failed to ${action} ${serviceName}: ${errorMessage(error)}`)
+        toast.error(`Failed to ${action} service: ${errorMessage(error)}`, {
           duration: 5000,
           icon: '❌'
         })
@@ -331,7 +348,7 @@ export const ServiceProvider: ParentComponent = (props) => {
       
       clearSelection()
     } catch (error) {
-      console.error(`Failed to start services: ${error}`)
+      console.error(`Failed to start services: ${errorMessage(error)}`)
       // Fallback to refresh on error
       await refreshServices(true)
     }
@@ -359,7 +376,7 @@ export const ServiceProvider: ParentComponent = (props) => {
         })
       )
     } catch (error) {
-      console.error(`Failed to stop all services: ${error}`)
+      console.error(`Failed to stop all services: ${errorMessage(error)}`)
       // Fallback to refresh on error
       await refreshServices(true)
     }
@@ -2,8 +2,14 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::process::{Command, Stdio};
 use std::io::Write;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock};
 use chrono::{DateTime, Utc};
 use anyhow::Result;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+use crate::error::CommandError;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Service {
@@ -14,6 +20,16 @@ pub struct Service {
     pub uptime: Option<String>,
     pub last_started: Option<DateTime<Utc>>,
     pub description: String,
+    #[serde(default)]
+    pub restart_count: u32,
+    #[serde(default)]
+    pub last_result: Option<String>,
+    // Whether the service is in tracked_services; only get_services fills this in
+    #[serde(default)]
+    pub tracked: bool,
+    // The tracked display_name; name stays the unit name so commands keep working
+    #[serde(default)]
+    pub display_name: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
@@ -22,6 +38,17 @@ pub enum ServiceStatus {
     Stopped,
     Failed,
     Unknown,
+    SocketActivated,
+    Activating,
+    Deactivating,
+    Reloading,
+}
+
+impl ServiceStatus {
+    // Transitional states are expected to settle on their own shortly
+    pub fn is_transitional(&self) -> bool {
+        matches!(self, ServiceStatus::Activating | ServiceStatus::Deactivating | ServiceStatus::Reloading)
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -31,6 +58,59 @@ pub struct ServiceLogs {
     pub timestamp: DateTime<Utc>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogEntry {
+    pub service_name: String,
+    pub unit: String,
+    pub timestamp: DateTime<Utc>,
+    pub message: String,
+    pub priority: Option<u8>,
+    pub pid: Option<u32>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct KernelLogEntry {
+    pub timestamp: DateTime<Utc>,
+    // syslog level name, e.g. "err" or "warning"
+    pub level: String,
+    pub priority: u8,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MultiServiceLogs {
+    pub entries: Vec<LogEntry>,
+    pub skipped: Vec<String>,
+    pub timestamp: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LogSearchResult {
+    pub service_name: String,
+    pub query: String,
+    pub matches: Vec<LogMatch>,
+    // max_results was reached before the start of the window, so older matches may exist
+    pub truncated: bool,
+    pub timestamp: DateTime<Utc>,
+}
+
+// A matching entry with the entries logged just before and after it, oldest first
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LogMatch {
+    pub entry: LogEntry,
+    pub before: Vec<LogEntry>,
+    pub after: Vec<LogEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LogExport {
+    pub service_name: String,
+    pub path: String,
+    pub lines: usize,
+    pub bytes: u64,
+    pub compressed: bool,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SystemLogs {
     pub service_name: String,
@@ -59,11 +139,144 @@ pub struct SystemMetrics {
     pub disk_write: u64,
     pub process_count: u32,
     pub open_files: u32,
+    // open_files was extrapolated from a subset of the processes
+    #[serde(default)]
+    pub open_files_sampled: bool,
+    pub timestamp: DateTime<Utc>,
+    pub display: SystemMetricsDisplay,
+}
+
+// Preformatted figures so every consumer renders metrics the same way
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct SystemMetricsDisplay {
+    pub memory_usage_human: String,
+    pub memory_total_human: String,
+    pub memory_percent: f32,
+    pub network_in_human: String,
+    pub network_out_human: String,
+    pub disk_read_human: String,
+    pub disk_write_human: String,
+}
+
+impl SystemMetrics {
+    // Fills in the display block from the raw counters
+    pub fn with_display(mut self) -> Self {
+        self.display = SystemMetricsDisplay {
+            memory_usage_human: format_bytes(self.memory_usage),
+            memory_total_human: format_bytes(self.memory_total),
+            memory_percent: if self.memory_total > 0 {
+                self.memory_usage as f32 / self.memory_total as f32 * 100.0
+            } else {
+                0.0
+            },
+            network_in_human: format_bytes(self.network_in),
+            network_out_human: format_bytes(self.network_out),
+            disk_read_human: format_bytes(self.disk_read),
+            disk_write_human: format_bytes(self.disk_write),
+        };
+        self
+    }
+}
+
+// Formats a byte count with binary units, e.g. 1503238553 -> "1.4 GiB"
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 6] = ["B", "KiB", "MiB", "GiB", "TiB", "PiB"];
+
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
+// Formats a duration in seconds as days/hours/minutes, e.g. 93784 -> "1d 2h 3m"
+pub fn format_duration(seconds: u64) -> String {
+    let days = seconds / 86_400;
+    let hours = (seconds % 86_400) / 3_600;
+    let minutes = (seconds % 3_600) / 60;
+
+    if days > 0 {
+        format!("{}d {}h {}m", days, hours, minutes)
+    } else if hours > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else {
+        format!("{}m", minutes)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DiskUsage {
+    pub mount_point: String,
+    pub device: String,
+    pub fs_type: String,
+    pub total: u64,
+    pub used: u64,
+    pub available: u64,
+    pub percent: f32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SystemSummary {
+    pub cpu_usage: f32,
+    pub memory_used: u64,
+    pub memory_total: u64,
+    pub load_average_1: f32,
+    pub load_average_5: f32,
+    pub load_average_15: f32,
+    pub uptime_seconds: u64,
+    pub services_running: u32,
+    pub services_stopped: u32,
+    pub services_failed: u32,
     pub timestamp: DateTime<Utc>,
 }
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum ReportFormat {
+    Markdown,
+    Html,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProcessNode {
+    pub pid: u32,
+    pub ppid: u32,
+    pub command: String,
+    pub cpu: f32,
+    pub rss: u64,
+    pub children: Vec<ProcessNode>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProcessInfo {
+    pub pid: u32,
+    pub name: String,
+    // Empty for kernel threads
+    pub command_line: String,
+    pub state: String,
+    pub kernel_thread: bool,
+    // Clock ticks after boot (stat field 22); tells a reused PID apart from the process seen here
+    pub start_time: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProcessStopResult {
+    pub pid: u32,
+    pub name: String,
+    pub command_line: String,
+    pub signal: String,
+    // The process exited (or is a zombie) within a few seconds of the signal
+    pub terminated: bool,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TerminalCommand {
+    pub job_id: String,
     pub command: String,
     pub output: String,
     pub exit_code: i32,
@@ -77,12 +290,429 @@ pub struct TerminalSession {
     pub current_directory: String,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub enum TerminalJobState {
+    Running,
+    Exited,
+    Killed,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TerminalJobStatus {
+    pub job_id: String,
+    pub command: String,
+    pub state: TerminalJobState,
+    pub exit_code: Option<i32>,
+    pub output: String,
+    pub started_at: DateTime<Utc>,
+    pub duration_ms: u64,
+}
+
+// A terminal command whose child process is still owned by the app
+pub struct TerminalJob {
+    command: String,
+    child: tokio::process::Child,
+    // Raw bytes, so a character split across two reads is decoded whole
+    stdout: Arc<Mutex<Vec<u8>>>,
+    stderr: Arc<Mutex<Vec<u8>>>,
+    readers: Vec<tokio::task::JoinHandle<()>>,
+    state: TerminalJobState,
+    exit_code: Option<i32>,
+    started_at: DateTime<Utc>,
+    started: std::time::Instant,
+    finished_after: Option<std::time::Duration>,
+    // Set by cancel_terminal_command so the exit is reported as Killed
+    kill_requested: bool,
+    strip_ansi: bool,
+    // Nothing waits on a background job, so it stays in the map until its status is collected
+    background: bool,
+}
+
+impl TerminalJob {
+    // Combine stdout and stderr the same way foreground commands always have, stripped of
+    // escape sequences when the caller asked for that
+    async fn combined_output(&self) -> String {
+        let mut combined = String::from_utf8_lossy(&self.stdout.lock().await).into_owned();
+        let stderr = self.stderr.lock().await;
+        let stderr = String::from_utf8_lossy(&stderr);
+        if !stderr.is_empty() {
+            if !combined.is_empty() {
+                combined.push('\n');
+            }
+            combined.push_str(&stderr);
+        }
+        if self.strip_ansi {
+            combined = strip_ansi_codes(&combined);
+        }
+        combined
+    }
+}
+
+pub type TerminalJobs = Arc<Mutex<HashMap<Uuid, TerminalJob>>>;
+
+// Flag a long-running command polls so it can stop once nobody wants its result
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    fn check(&self) -> Result<(), CommandError> {
+        if self.is_cancelled() {
+            Err(CommandError::Cancelled("Request was cancelled".to_string()))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+// Cancellable requests in flight, keyed by the request id the frontend passed. A std
+// Mutex because entries are removed from Drop and the lock is never held across an await.
+pub type PendingRequests = Arc<std::sync::Mutex<HashMap<String, CancelToken>>>;
+
+// Keeps a request's token registered for as long as the command runs
+struct RegisteredRequest {
+    requests: PendingRequests,
+    request_id: Option<String>,
+    token: CancelToken,
+}
+
+impl RegisteredRequest {
+    fn new(requests: &PendingRequests, request_id: Option<String>) -> Self {
+        let token = CancelToken::default();
+        if let Some(id) = &request_id {
+            requests
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .insert(id.clone(), token.clone());
+        }
+        Self {
+            requests: requests.clone(),
+            request_id,
+            token,
+        }
+    }
+}
+
+impl Drop for RegisteredRequest {
+    fn drop(&mut self) {
+        if let Some(id) = &self.request_id {
+            self.requests.lock().unwrap_or_else(|e| e.into_inner()).remove(id);
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct AuthRequest {
     pub requires_auth: bool,
     pub message: String,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ServiceProperties {
+    pub service_name: String,
+    pub load_state: Option<String>,
+    pub active_state: Option<String>,
+    pub sub_state: Option<String>,
+    pub unit_file_state: Option<String>,
+    pub fragment_path: Option<String>,
+    pub need_daemon_reload: bool,
+    pub recently_oom_killed: bool,
+    pub documentation: Vec<String>,
+    pub documentation_links: Vec<DocumentationLink>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum DocumentationKind {
+    Man,
+    Web,
+    File,
+    Info,
+    Other,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DocumentationLink {
+    pub url: String,
+    pub kind: DocumentationKind,
+}
+
+impl DocumentationLink {
+    fn classify(url: &str) -> Self {
+        let scheme = url.split(':').next().unwrap_or_default().to_ascii_lowercase();
+        let kind = match scheme.as_str() {
+            "man" => DocumentationKind::Man,
+            "http" | "https" => DocumentationKind::Web,
+            "file" => DocumentationKind::File,
+            "info" => DocumentationKind::Info,
+            _ => DocumentationKind::Other,
+        };
+        Self { url: url.to_string(), kind }
+    }
+}
+
+// Resource caps from the unit's cgroup settings; None means unlimited or not set
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ServiceLimits {
+    pub service_name: String,
+    // Bytes
+    pub memory_max: Option<u64>,
+    pub memory_high: Option<u64>,
+    // Percent of one CPU, so 200.0 allows two full cores
+    pub cpu_quota_percent: Option<f32>,
+    // The same quota as a percent of all cores, the scale cpu_usage is reported on
+    pub cpu_quota_share: Option<f32>,
+    pub tasks_max: Option<u64>,
+    pub open_files_max: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ServiceLimitUpdate {
+    pub success: bool,
+    pub message: String,
+    // The new value only reaches the running process after a restart
+    pub restart_required: bool,
+    pub limits: Option<ServiceLimits>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PortCheck {
+    pub host: String,
+    pub port: u16,
+    pub open: bool,
+    pub latency_ms: Option<u64>,
+    pub error: Option<String>,
+}
+
+// Change between two recorded metrics samples; the snapshots carry the times actually used
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MetricsDiff {
+    pub service_name: String,
+    pub from: MetricsSnapshot,
+    pub to: MetricsSnapshot,
+    pub elapsed_secs: i64,
+    // Percentage points
+    pub cpu_change: f32,
+    pub memory_change: i64,
+    // I/O counters are cumulative per process, so a restart in between can make these negative
+    pub disk_read_delta: i64,
+    pub disk_write_delta: i64,
+    pub network_in_delta: i64,
+    pub network_out_delta: i64,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum PackageManager {
+    Apt,
+    Dnf,
+    Yum,
+    Zypper,
+    Pacman,
+}
+
+// The package remove_service would purge for a unit, shown to the user before removing it
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ServicePackage {
+    pub service_name: String,
+    pub package: String,
+    pub package_manager: PackageManager,
+    pub unit_file: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PackageInstall {
+    pub package: String,
+    pub success: bool,
+    pub message: String,
+    // Units the package shipped, so the UI can offer to track them
+    pub services: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ServiceUpdate {
+    pub service_name: String,
+    pub package: String,
+    pub package_manager: PackageManager,
+    pub current_version: String,
+    // None when the package is already at the newest version the package cache knows of
+    pub candidate_version: Option<String>,
+    pub update_available: bool,
+}
+
+// One field that differs between two views of a service; None when a side has no value
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ServiceFieldDiff {
+    pub field: String,
+    pub a_value: Option<String>,
+    pub b_value: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct InventoryExport {
+    pub path: String,
+    pub services: usize,
+    pub bytes: u64,
+    pub include_metrics: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ServiceReconcile {
+    pub service_name: String,
+    pub success: bool,
+    pub actions: Vec<String>, // start, stop, enable, disable in the order they ran
+    pub message: String,
+    pub service: Option<Service>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PortOwner {
+    pub port: u16,
+    pub protocol: String, // tcp, udp
+    pub local_address: String,
+    pub pid: Option<u32>,
+    pub process: Option<String>,
+    pub service_name: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OomEvent {
+    pub service_name: String,
+    pub timestamp: String,
+    pub source: String, // kernel, unit
+    pub pid: Option<u32>,
+    pub process_name: Option<String>,
+    pub total_vm_kb: Option<u64>,
+    pub anon_rss_kb: Option<u64>,
+    pub file_rss_kb: Option<u64>,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BootTimingEntry {
+    pub service_name: String,
+    pub time_ms: u64,
+    pub started_at_ms: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BootTiming {
+    pub blame: Vec<BootTimingEntry>,
+    pub critical_chain: Vec<BootTimingEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BootSummary {
+    pub firmware_ms: Option<u64>,
+    pub loader_ms: Option<u64>,
+    pub kernel_ms: Option<u64>,
+    pub initrd_ms: Option<u64>,
+    pub userspace_ms: Option<u64>,
+    pub total_ms: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BootEntry {
+    pub index: i32,
+    pub boot_id: String,
+    pub first_entry: String,
+    pub last_entry: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BackendInfo {
+    // "systemd" when systemctl is available, otherwise "none"
+    pub backend: String,
+    pub version: Option<String>,
+    pub systemctl_available: bool,
+    pub journalctl_available: bool,
+    pub docker_available: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RestartLeaderboardEntry {
+    pub service_name: String,
+    pub restart_count: u32,
+    pub last_restart: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SystemTarget {
+    pub name: String,
+    pub description: String,
+    pub load_state: String,
+    pub active_state: String,
+    pub sub_state: String,
+    pub is_default: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TimerUnit {
+    pub unit: String,
+    pub activates: Option<String>,
+    pub next: Option<String>,
+    pub left: Option<String>,
+    pub last: Option<String>,
+    pub passed: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ServiceSchedule {
+    pub service_name: String,
+    pub action: String,
+    pub calendar_spec: String,
+    pub timer_unit: String,
+    pub next: Option<String>,
+    pub left: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SocketUnit {
+    pub unit: String,
+    pub listen: String,
+    pub activates: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UnitFile {
+    pub service_name: String,
+    pub path: String,
+    pub contents: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TextFileContents {
+    pub path: String,
+    pub contents: String,
+    pub size: u64,
+    pub modified: Option<DateTime<Utc>>,
+    // The file is larger than max_bytes and contents holds only its start
+    pub truncated: bool,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum ConfigFileSource {
+    // Passed with -c, --config or a similar flag
+    ExecStartFlag,
+    // A bare ExecStart argument that looks like a config file
+    ExecStartArgument,
+    EnvironmentFile,
+    // A conventional location such as /etc/<name>/<name>.conf
+    Convention,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ConfigFileCandidate {
+    pub path: String,
+    pub source: ConfigFileSource,
+    pub exists: bool,
+}
+
 // Define available services - Now dynamically discovered from system
 fn get_service_definitions() -> Vec<(String, String, u32)> {
     // This function is now deprecated in favor of dynamic discovery
@@ -101,31 +731,42 @@ fn check_service_status(service_name: &str) -> Result<ServiceStatus> {
     log::debug!("🔍 Checking status for service: {}", service_name);
     
     let output = Command::new("systemctl")
-        .args(&["is-active", service_name])
+        .args(&["show", service_name, "-p", "ActiveState", "-p", "SubState"])
         .output()?;
 
-    let status = match output.stdout.as_slice() {
-        b"active\n" => {
-            log::debug!("✅ Service {} is running", service_name);
-            ServiceStatus::Running
-        },
-        b"inactive\n" => {
-            log::debug!("⏹️ Service {} is stopped", service_name);
-            ServiceStatus::Stopped
-        },
-        b"failed\n" => {
-            log::warn!("❌ Service {} has failed", service_name);
-            ServiceStatus::Failed
-        },
-        _ => {
-            log::warn!("❓ Service {} status unknown", service_name);
-            ServiceStatus::Unknown
-        },
-    };
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut active_state = "";
+    let mut sub_state = "";
+    for line in stdout.lines() {
+        if let Some(value) = line.strip_prefix("ActiveState=") {
+            active_state = value.trim();
+        } else if let Some(value) = line.strip_prefix("SubState=") {
+            sub_state = value.trim();
+        }
+    }
+
+    let status = status_from_states(active_state, sub_state);
+    match status {
+        ServiceStatus::Failed => log::warn!("❌ Service {} has failed", service_name),
+        ServiceStatus::Unknown => log::warn!("❓ Service {} status unknown ({}/{})", service_name, active_state, sub_state),
+        _ => log::debug!("🔍 Service {} is {:?} ({}/{})", service_name, status, active_state, sub_state),
+    }
     
     Ok(status)
 }
 
+fn status_from_states(active_state: &str, sub_state: &str) -> ServiceStatus {
+    match (active_state, sub_state) {
+        ("reloading", _) | ("active", "reload") => ServiceStatus::Reloading,
+        ("active", _) => ServiceStatus::Running,
+        ("activating", _) => ServiceStatus::Activating,
+        ("deactivating", _) => ServiceStatus::Deactivating,
+        ("inactive", _) => ServiceStatus::Stopped,
+        ("failed", _) => ServiceStatus::Failed,
+        _ => ServiceStatus::Unknown,
+    }
+}
+
 fn check_service_enabled(service_name: &str) -> bool {
     log::debug!("🔍 Checking if service {} is enabled", service_name);
     
@@ -150,7 +791,25 @@ fn check_service_enabled(service_name: &str) -> bool {
     }
 }
 
-fn execute_sudo_command(args: &[&str], password: Option<String>, is_sudo: bool) -> Result<std::process::Output, String> {
+// What sudo prints on its own stderr lines when it won't run the command for this user
+const SUDO_REFUSAL_PATTERNS: [&str; 6] = [
+    "incorrect password",
+    "a password is required",
+    "no password was provided",
+    "a terminal is required",
+    "is not in the sudoers file",
+    "is not allowed to execute",
+];
+
+// Returns sudo's own refusal line, so a command that merely failed isn't taken for a bad password
+fn sudo_refusal(stderr: &str) -> Option<&str> {
+    stderr.lines().map(str::trim).find(|line| {
+        let lowercase = line.to_lowercase();
+        lowercase.starts_with("sudo:") && SUDO_REFUSAL_PATTERNS.iter().any(|pattern| lowercase.contains(pattern))
+    })
+}
+
+fn execute_sudo_command(args: &[&str], password: Option<String>, is_sudo: bool) -> Result<std::process::Output, CommandError> {
     let command_str = args.join(" ");
     log::debug!("🔧 Executing command: {}", command_str);
     
@@ -167,22 +826,29 @@ fn execute_sudo_command(args: &[&str], password: Option<String>, is_sudo: bool)
             .spawn()
             .map_err(|e| {
                 log::error!("❌ Failed to spawn sudo command: {}", e);
-                format!("Failed to spawn sudo command: {}", e)
+                CommandError::Internal(format!("Failed to spawn sudo command: {}", e))
             })?;
 
         if let Some(stdin) = child.stdin.as_mut() {
             writeln!(stdin, "{}", pwd)
                 .map_err(|e| {
                     log::error!("❌ Failed to write password: {}", e);
-                    format!("Failed to write password: {}", e)
+                    CommandError::Internal(format!("Failed to write password: {}", e))
                 })?;
         }
 
         let output = child.wait_with_output()
             .map_err(|e| {
                 log::error!("❌ Failed to execute command: {}", e);
-                format!("Failed to execute command: {}", e)
+                CommandError::Internal(format!("Failed to execute command: {}", e))
             })?;
+
+        if !output.status.success() {
+            if let Some(refusal) = sudo_refusal(&String::from_utf8_lossy(&output.stderr)) {
+                log::warn!("🔐 sudo refused to run {}: {}", command_str, refusal);
+                return Err(CommandError::PermissionDenied(format!("Authentication failed: {}", refusal)));
+            }
+        }
             
         log::debug!("✅ Command executed successfully (exit code: {})", output.status);
         Ok(output)
@@ -201,32 +867,139 @@ fn execute_sudo_command(args: &[&str], password: Option<String>, is_sudo: bool)
             }
             _ => {
                 log::warn!("⚠️ User-level command failed, authentication required for system service");
-                Err("Authentication required for system service management".to_string())
+                Err(CommandError::PermissionDenied("Authentication required for system service management".to_string()))
             }
         }
     }
 }
 
-fn find_service_name(service_name: &str) -> Result<String, String> {
-    log::debug!("🔍 Finding systemd service name for: {}", service_name);
-    
-    // For dynamic discovery, we assume the service name is already the systemd service name
-    // or we append .service if it's not already there
-    let systemd_service = if service_name.ends_with(".service") {
-        service_name.to_string()
+// Unit types that can be managed directly by name; anything else is treated as a service
+const MANAGED_UNIT_SUFFIXES: &[&str] = &[".service", ".timer", ".socket"];
+
+fn has_unit_suffix(service_name: &str) -> bool {
+    MANAGED_UNIT_SUFFIXES.iter().any(|suffix| service_name.ends_with(suffix))
+}
+
+// Service and container names only ever need these characters; anything else could end up
+// in a shell or be taken as a command-line option, so it is rejected before any command runs
+pub fn validate_service_name(service_name: &str) -> Result<(), CommandError> {
+    let valid = !service_name.is_empty()
+        && service_name.len() <= 256
+        && !service_name.starts_with('-')
+        && !service_name.strip_prefix(crate::docker::DOCKER_PREFIX).is_some_and(|container| container.starts_with('-'))
+        && !service_name.contains("..")
+        && service_name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '@' | ':' | '-'));
+
+    if valid {
+        Ok(())
     } else {
-        format!("{}.service", service_name)
-    };
-    
-    log::debug!("🔍 Checking if service exists: {}", systemd_service);
-    
+        log::warn!("🚫 Rejected invalid service name: {:?}", service_name);
+        Err(CommandError::InvalidInput(format!("Invalid service name: {:?}", service_name)))
+    }
+}
+
+static SYSTEMCTL_PATH: OnceLock<Option<PathBuf>> = OnceLock::new();
+static JOURNALCTL_PATH: OnceLock<Option<PathBuf>> = OnceLock::new();
+
+fn find_on_path(binary: &str) -> Option<PathBuf> {
+    std::env::var_os("PATH").and_then(|paths| {
+        std::env::split_paths(&paths)
+            .map(|dir| dir.join(binary))
+            .find(|candidate| candidate.is_file())
+    })
+}
+
+fn systemctl_path() -> Option<&'static PathBuf> {
+    SYSTEMCTL_PATH.get_or_init(|| find_on_path("systemctl")).as_ref()
+}
+
+fn journalctl_path() -> Option<&'static PathBuf> {
+    JOURNALCTL_PATH.get_or_init(|| find_on_path("journalctl")).as_ref()
+}
+
+fn backend_unavailable(binary: &str) -> CommandError {
+    CommandError::BackendUnavailable(format!("{} was not found on PATH; this system does not appear to use systemd", binary))
+}
+
+fn require_systemctl() -> Result<(), CommandError> {
+    systemctl_path().map(|_| ()).ok_or_else(|| backend_unavailable("systemctl"))
+}
+
+fn require_journalctl() -> Result<(), CommandError> {
+    journalctl_path().map(|_| ()).ok_or_else(|| backend_unavailable("journalctl"))
+}
+
+// Run once at startup so a missing systemd is reported in the log right away
+pub fn detect_backend() {
+    match (systemctl_path(), journalctl_path()) {
+        (Some(systemctl), Some(journalctl)) => {
+            log::info!("⚙️ systemd backend: {} and {}", systemctl.display(), journalctl.display());
+        }
+        (systemctl, journalctl) => {
+            if systemctl.is_none() {
+                log::warn!("⚠️ systemctl not found on PATH, service management is unavailable");
+            }
+            if journalctl.is_none() {
+                log::warn!("⚠️ journalctl not found on PATH, logs are unavailable");
+            }
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn get_backend_info() -> Result<BackendInfo, CommandError> {
+    let systemctl_available = systemctl_path().is_some();
+
+    // First line is "systemd 252 (252.22-1~deb12u1)"
+    let version = if systemctl_available {
+        Command::new("systemctl")
+            .arg("--version")
+            .output()
+            .ok()
+            .and_then(|output| {
+                String::from_utf8_lossy(&output.stdout)
+                    .lines()
+                    .next()
+                    .map(|line| line.trim().to_string())
+            })
+            .filter(|line| !line.is_empty())
+    } else {
+        None
+    };
+
+    Ok(BackendInfo {
+        backend: if systemctl_available { "systemd" } else { "none" }.to_string(),
+        version,
+        systemctl_available,
+        journalctl_available: journalctl_path().is_some(),
+        docker_available: DockerBackend::is_available(),
+    })
+}
+
+fn find_service_name(service_name: &str) -> Result<String, CommandError> {
+    require_systemctl()?;
+
+    log::debug!("🔍 Finding systemd service name for: {}", service_name);
+    
+    // For dynamic discovery, we assume the service name is already the systemd unit name
+    // or we append .service if it doesn't carry a managed unit suffix
+    let systemd_service = if has_unit_suffix(service_name) {
+        service_name.to_string()
+    } else {
+        format!("{}.service", service_name)
+    };
+    
+    log::debug!("🔍 Checking if service exists: {}", systemd_service);
+    
     // Verify the service exists in the system
     if is_service_installed(&systemd_service) {
         log::debug!("✅ Service found: {}", systemd_service);
         Ok(systemd_service)
     } else {
         log::error!("❌ Service not found: {}", systemd_service);
-        Err(format!("Service '{}' not found in system", service_name))
+        Err(CommandError::NotFound(format!("Service '{}' not found in system", service_name)))
     }
 }
 
@@ -274,14 +1047,97 @@ fn get_service_uptime(service_name: &str) -> Option<String> {
     Some(timestamp_str.trim().to_string())
 }
 
+fn get_unit_property(service_name: &str, property: &str) -> Option<String> {
+    let output = Command::new("systemctl")
+        .args(&["show", service_name, "-p", property, "--value"])
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let value = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if value.is_empty() || value == "n/a" {
+        None
+    } else {
+        Some(value)
+    }
+}
+
+fn validate_unit_contents(contents: &str) -> Result<(), CommandError> {
+    let mut has_section = false;
+
+    for (index, raw_line) in contents.lines().enumerate() {
+        let line = raw_line.trim();
+        let line_number = index + 1;
+
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+
+        if line.starts_with('[') {
+            if !line.ends_with(']') || line.len() < 3 {
+                return Err(CommandError::InvalidInput(format!("Line {}: malformed section header '{}'", line_number, line)));
+            }
+            has_section = true;
+            continue;
+        }
+
+        if !has_section {
+            return Err(CommandError::InvalidInput(format!("Line {}: setting appears before any [Section] header", line_number)));
+        }
+
+        match line.split_once('=') {
+            Some((key, _)) if !key.trim().is_empty() => {}
+            _ => return Err(CommandError::InvalidInput(format!("Line {}: expected Key=Value, found '{}'", line_number, line))),
+        }
+    }
+
+    if !has_section {
+        return Err(CommandError::InvalidInput("Unit contents must contain at least one [Section] header".to_string()));
+    }
+
+    Ok(())
+}
+
 #[tauri::command]
-pub async fn get_services(limit: Option<u32>, show_all: Option<bool>) -> Result<Vec<Service>, String> {
-    log::info!("📋 Getting services list (limit: {:?}, show_all: {:?})", limit, show_all);
+pub async fn get_services(
+    db: State<'_, Arc<Mutex<Database>>>,
+    limit: Option<u32>,
+    show_all: Option<bool>,
+    include_docker: Option<bool>,
+    host_id: Option<i64>,
+) -> Result<Vec<Service>, CommandError> {
+    log::info!("📋 Getting services list (limit: {:?}, show_all: {:?}, include_docker: {:?}, host: {:?})", limit, show_all, include_docker, host_id);
     
     // Get all system services dynamically
-    let all_system_services = get_all_system_services().await?;
+    let blocklist = load_service_blocklist(&db).await;
+    let remote = remote_backend(&db, host_id).await?;
+    let local = remote.is_none();
+    let all_system_services = match remote {
+        Some(backend) => {
+            let blocklist = blocklist.clone();
+            run_blocking(move || remote_system_services(&backend, &blocklist)).await?
+        }
+        None => list_system_services(&blocklist, &CancelToken::default())?,
+    };
     log::debug!("🔍 Found {} total system services", all_system_services.len());
     
+    // Tracking is per local machine, so remote listings never show as tracked
+    let tracked_services: HashMap<String, TrackedService> = if local {
+        db.lock()
+            .await
+            .get_tracked_services()
+            .await
+            .map_err(|e| format!("Failed to get tracked services: {}", e))?
+            .into_iter()
+            .map(|tracked| (tracked.name.clone(), tracked))
+            .collect()
+    } else {
+        HashMap::new()
+    };
+    
     let mut services = Vec::new();
 
     // Convert to Service objects
@@ -311,10 +1167,33 @@ pub async fn get_services(limit: Option<u32>, show_all: Option<bool>) -> Result<
                 uptime: None,
                 last_started: None,
                 description,
+                restart_count: 0,
+                last_result: None,
+                tracked: false,
+                display_name: None,
             });
         }
     }
 
+    // Containers are only listed for the local machine
+    if include_docker.unwrap_or(false) && local {
+        match DockerBackend::list_containers() {
+            Ok(containers) => services.extend(containers),
+            Err(e) => log::warn!("⚠️ Skipping docker containers: {}", e),
+        }
+    }
+
+    // Tracked metadata wins over what systemd reports; null fields keep the systemd values
+    for service in &mut services {
+        if let Some(tracked) = tracked_services.get(&service.name) {
+            service.tracked = true;
+            service.display_name = Some(tracked.display_name.clone());
+            if let Some(description) = &tracked.description {
+                service.description = description.clone();
+            }
+        }
+    }
+
     // Sort by name for consistency
     services.sort_by(|a, b| a.name.cmp(&b.name));
 
@@ -333,22 +1212,45 @@ pub async fn get_services(limit: Option<u32>, show_all: Option<bool>) -> Result<
 }
 
 #[tauri::command]
-pub async fn get_installed_services_count() -> Result<u32, String> {
-    let all_system_services = get_all_system_services().await?;
-    Ok(all_system_services.len() as u32)
+pub async fn get_installed_services_count(
+    db: State<'_, Arc<Mutex<Database>>>,
+) -> Result<u32, CommandError> {
+    // Counting only needs the unit file list, not each unit's runtime status
+    let unit_files = list_service_unit_files()?;
+    let count = count_visible_services(&db, unit_files.iter().map(|(unit, _)| unit.trim_end_matches(".service"))).await;
+    Ok(count as u32)
+}
+
+// How many of the named services the service list would show, leaving out templates and
+// blocklisted prefixes; shared with the monitor's refresh totals
+pub async fn count_visible_services<'a>(
+    db: &Arc<Mutex<Database>>,
+    names: impl Iterator<Item = &'a str>,
+) -> usize {
+    let blocklist = load_service_blocklist(db).await;
+    names.filter(|name| !is_hidden_system_service(name, &blocklist)).count()
 }
 
 // Internal function for use by event system
-pub async fn get_service_status_internal(service_name: &str) -> Result<Service, String> {
+pub async fn get_service_status_internal(service_name: &str) -> Result<Service, CommandError> {
+    if let Some(container) = DockerBackend::container_name(service_name) {
+        return DockerBackend::get_container(container);
+    }
+
     let systemd_service = find_service_name(service_name)?;
 
-    let status = check_service_status(&systemd_service)
+    let mut status = check_service_status(&systemd_service)
         .map_err(|e| format!("Failed to check status: {}", e))?;
+    if status == ServiceStatus::Stopped && is_socket_activated(&systemd_service) {
+        log::debug!("🔌 Service {} is waiting for a socket connection", systemd_service);
+        status = ServiceStatus::SocketActivated;
+    }
     let enabled = check_service_enabled(&systemd_service);
     let uptime = get_service_uptime(&systemd_service);
     
     // Generate description based on service name
     let description = generate_service_description(service_name);
+    let (restart_count, last_result) = get_restart_info(&systemd_service);
 
     Ok(Service {
         name: service_name.to_string(),
@@ -358,9 +1260,59 @@ pub async fn get_service_status_internal(service_name: &str) -> Result<Service,
         uptime,
         last_started: None,
         description,
+        restart_count,
+        last_result,
+        tracked: false,
+        display_name: None,
     })
 }
 
+// Reads NRestarts and the outcome of the last run; defaults to 0/None when unavailable
+fn get_restart_info(systemd_service: &str) -> (u32, Option<String>) {
+    let Ok(output) = Command::new("systemctl")
+        .args(&["show", systemd_service, "-p", "NRestarts", "-p", "ExecMainStatus", "-p", "Result"])
+        .output()
+    else {
+        return (0, None);
+    };
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut restart_count = 0;
+    let mut exit_status = None;
+    let mut result = None;
+    for line in stdout.lines() {
+        if let Some(value) = line.strip_prefix("NRestarts=") {
+            restart_count = value.trim().parse().unwrap_or(0);
+        } else if let Some(value) = line.strip_prefix("ExecMainStatus=") {
+            exit_status = value.trim().parse::<i32>().ok();
+        } else if let Some(value) = line.strip_prefix("Result=") {
+            let value = value.trim();
+            if !value.is_empty() {
+                result = Some(value.to_string());
+            }
+        }
+    }
+
+    let last_result = match (result, exit_status) {
+        (Some(result), Some(status)) if status != 0 => Some(format!("{} (status {})", result, status)),
+        (result, _) => result,
+    };
+
+    (restart_count, last_result)
+}
+
+// A service is socket-activated when one of the units triggering it is a listening socket
+fn is_socket_activated(systemd_service: &str) -> bool {
+    let Some(triggers) = get_unit_property(systemd_service, "TriggeredBy") else {
+        return false;
+    };
+
+    triggers
+        .split_whitespace()
+        .filter(|unit| unit.ends_with(".socket"))
+        .any(|socket| matches!(check_service_status(socket), Ok(ServiceStatus::Running)))
+}
+
 // Helper function to get service information from systemd
 fn get_service_info_from_systemd(service_name: &str) -> Option<String> {
     // Try to get service description from systemd
@@ -382,7 +1334,12 @@ fn get_service_info_from_systemd(service_name: &str) -> Option<String> {
 // Helper function to generate service descriptions dynamically
 fn generate_service_description(service_name: &str) -> String {
     // First try to get description from systemd
-    if let Some(systemd_desc) = get_service_info_from_systemd(&format!("{}.service", service_name)) {
+    let unit_name = if has_unit_suffix(service_name) {
+        service_name.to_string()
+    } else {
+        format!("{}.service", service_name)
+    };
+    if let Some(systemd_desc) = get_service_info_from_systemd(&unit_name) {
         return systemd_desc;
     }
     
@@ -435,14 +1392,287 @@ fn generate_service_description(service_name: &str) -> String {
 }
 
 #[tauri::command]
-pub async fn get_service_status(service_name: String) -> Result<Service, String> {
+pub async fn get_service_status(
+    db: State<'_, Arc<Mutex<Database>>>,
+    service_name: String,
+    host_id: Option<i64>,
+) -> Result<Service, CommandError> {
+    validate_service_name(&service_name)?;
+
+    if let Some(backend) = remote_backend(&db, host_id).await? {
+        return Ok(run_blocking(move || remote_service_status(&backend, &service_name)).await?);
+    }
+
     get_service_status_internal(&service_name).await
 }
 
+// Unit properties read for each service in get_service_statuses; unit names are appended
+const SHOW_STATUS_ARGS: [&str; 24] = [
+    "systemctl", "show",
+    "-p", "Id", "-p", "LoadState", "-p", "ActiveState", "-p", "SubState", "-p", "UnitFileState",
+    "-p", "Description", "-p", "ActiveEnterTimestamp", "-p", "NRestarts", "-p", "ExecMainStatus",
+    "-p", "Result", "-p", "TriggeredBy",
+];
+
+// Statuses for a whole list, in the order the names were given. Names that are invalid or
+// don't resolve come back as Unknown instead of failing the batch.
+#[tauri::command]
+pub async fn get_service_statuses(
+    db: State<'_, Arc<Mutex<Database>>>,
+    names: Vec<String>,
+    host_id: Option<i64>,
+) -> Result<Vec<Service>, CommandError> {
+    let Some(backend) = remote_backend(&db, host_id).await? else {
+        return local_service_statuses(names).await;
+    };
+
+    // One session serves the whole list, a unit at a time
+    Ok(run_blocking(move || {
+        Ok(names
+            .into_iter()
+            .map(|name| {
+                validate_service_name(&name)
+                    .ok()
+                    .and_then(|_| remote_service_status(&backend, &name).ok())
+                    .unwrap_or_else(|| unknown_service(name))
+            })
+            .collect())
+    })
+    .await?)
+}
+
+// The local side of get_service_statuses, batched into one `systemctl show`; the monitor
+// calls it directly on every tick
+pub async fn local_service_statuses(names: Vec<String>) -> Result<Vec<Service>, CommandError> {
+    let mut statuses: Vec<Option<Service>> = vec![None; names.len()];
+    let mut units: Vec<(usize, String)> = Vec::new();
+    let mut containers: Vec<usize> = Vec::new();
+
+    for (index, name) in names.iter().enumerate() {
+        if validate_service_name(name).is_err() {
+            continue;
+        }
+        if DockerBackend::container_name(name).is_some() {
+            containers.push(index);
+        } else {
+            units.push((index, remote_unit_name(name)));
+        }
+    }
+
+    // One docker ps covers every container in the batch
+    if !containers.is_empty() {
+        let listed = DockerBackend::list_containers().unwrap_or_default();
+        for index in containers {
+            statuses[index] = listed.iter().find(|service| service.name == names[index]).cloned();
+        }
+    }
+
+    if !units.is_empty() {
+        require_systemctl()?;
+
+        let output = Command::new(SHOW_STATUS_ARGS[0])
+            .args(&SHOW_STATUS_ARGS[1..])
+            .args(units.iter().map(|(_, unit)| unit.as_str()))
+            .output()
+            .map_err(|e| format!("Failed to check status: {}", e))?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+
+        // systemctl prints one block per argument in argument order; Id can't be used to match
+        // them up because an alias reports the unit it points to
+        let blocks: Vec<HashMap<&str, &str>> = stdout
+            .split("\n\n")
+            .map(|block| block.lines().filter_map(|line| line.split_once('=')).collect())
+            .filter(|properties: &HashMap<&str, &str>| !properties.is_empty())
+            .collect();
+
+        // A missing block would shift every later status onto the wrong unit, so don't pair
+        // them up by position unless the counts agree
+        if blocks.len() != units.len() {
+            log::warn!("⚠️ Expected {} status blocks from systemctl, got {}; checking each unit", units.len(), blocks.len());
+            for (index, _) in &units {
+                statuses[*index] = get_service_status_internal(&names[*index]).await.ok();
+            }
+        } else {
+            for ((index, unit), properties) in units.iter().zip(&blocks) {
+                let property = |name: &str| properties.get(name).copied().filter(|value| !value.is_empty());
+                if property("LoadState").is_none_or(|state| state == "not-found") {
+                    continue;
+                }
+                statuses[*index] = Some(service_from_properties(&names[*index], unit, property));
+            }
+
+            // A stopped service waiting on a listening socket is socket-activated, as in get_service_status
+            let waiting: Vec<(usize, Vec<&str>)> = units
+                .iter()
+                .zip(&blocks)
+                .filter(|((index, _), _)| statuses[*index].as_ref().is_some_and(|service| service.status == ServiceStatus::Stopped))
+                .map(|((index, _), properties)| {
+                    let sockets = properties
+                        .get("TriggeredBy")
+                        .map(|triggers| triggers.split_whitespace().filter(|unit| unit.ends_with(".socket")).collect())
+                        .unwrap_or_default();
+                    (*index, sockets)
+                })
+                .filter(|(_, sockets): &(usize, Vec<&str>)| !sockets.is_empty())
+                .collect();
+            if !waiting.is_empty() {
+                let all_sockets: Vec<&str> = waiting.iter().flat_map(|(_, sockets)| sockets.iter().copied()).collect();
+                let listening = listening_sockets(&all_sockets);
+                for (index, sockets) in waiting {
+                    if sockets.iter().any(|socket| listening.contains(*socket)) {
+                        if let Some(service) = statuses[index].as_mut() {
+                            service.status = ServiceStatus::SocketActivated;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(names
+        .into_iter()
+        .zip(statuses)
+        .map(|(name, status)| status.unwrap_or_else(|| unknown_service(name)))
+        .collect())
+}
+
+// Socket units from the list that are currently listening
+fn listening_sockets(sockets: &[&str]) -> std::collections::HashSet<String> {
+    let Ok(output) = Command::new("systemctl")
+        .args(&["show", "-p", "Id", "-p", "ActiveState"])
+        .args(sockets)
+        .output()
+    else {
+        return std::collections::HashSet::new();
+    };
+
+    String::from_utf8_lossy(&output.stdout)
+        .split("\n\n")
+        .filter_map(|block| {
+            let properties: HashMap<&str, &str> = block.lines().filter_map(|line| line.split_once('=')).collect();
+            (properties.get("ActiveState") == Some(&"active"))
+                .then(|| properties.get("Id").map(|id| id.to_string()))
+                .flatten()
+        })
+        .collect()
+}
+
+// Placeholder for a name get_service_statuses couldn't resolve
+fn unknown_service(name: String) -> Service {
+    Service {
+        service_name: remote_unit_name(&name),
+        description: format!("Service '{}' not found", name),
+        name,
+        status: ServiceStatus::Unknown,
+        enabled: false,
+        uptime: None,
+        last_started: None,
+        restart_count: 0,
+        last_result: None,
+        tracked: false,
+        display_name: None,
+    }
+}
+
+// Builds a Service from `systemctl show` properties, for the batched and the remote status paths
+fn service_from_properties<'a>(service_name: &str, unit: &str, property: impl Fn(&str) -> Option<&'a str>) -> Service {
+    let status = status_from_states(
+        property("ActiveState").unwrap_or(""),
+        property("SubState").unwrap_or(""),
+    );
+    // The same states for which `systemctl is-enabled` succeeds
+    let enabled = matches!(
+        property("UnitFileState"),
+        Some("enabled" | "enabled-runtime" | "static" | "alias" | "indirect" | "generated" | "transient")
+    );
+    let uptime = if status == ServiceStatus::Running {
+        property("ActiveEnterTimestamp").map(|timestamp| timestamp.to_string())
+    } else {
+        None
+    };
+    let exit_status = property("ExecMainStatus").and_then(|status| status.parse::<i32>().ok());
+    let last_result = match (property("Result"), exit_status) {
+        (Some(result), Some(status)) if status != 0 => Some(format!("{} (status {})", result, status)),
+        (result, _) => result.map(|result| result.to_string()),
+    };
+
+    Service {
+        name: service_name.to_string(),
+        service_name: unit.to_string(),
+        status,
+        enabled,
+        uptime,
+        last_started: None,
+        description: property("Description")
+            .map(|description| description.to_string())
+            .unwrap_or_else(|| generate_service_description(service_name)),
+        restart_count: property("NRestarts").and_then(|n| n.parse().ok()).unwrap_or(0),
+        last_result,
+        tracked: false,
+        display_name: None,
+    }
+}
+
+// Runs a docker start/stop/restart and reports it the same way systemd operations are reported.
+// An undoable start or stop that changed the container's state goes on the undo stack.
+async fn docker_operation(service_name: &str, container: &str, action: &str, done: &str, undoable: bool) -> Result<ServiceOperation, CommandError> {
+    let previous_status = DockerBackend::get_container(container).ok().map(|service| service.status);
+    let output = DockerBackend::container_action(container, action)?;
+
+    if output.status.success() {
+        // A container that was already running, or whose state couldn't be read, isn't ours to stop on exit
+        let started = action == "start" && previous_status.as_ref().is_some_and(|previous| *previous != ServiceStatus::Running);
+        if started {
+            record_session_start(service_name, false);
+        }
+        let previous_status = previous_status.filter(|_| undoable);
+        match (action, previous_status) {
+            ("start", Some(previous)) if previous != ServiceStatus::Running => {
+                record_undo(service_name, UndoableAction::Start, &format!("{:?}", previous), false);
+            }
+            ("stop", Some(ServiceStatus::Running)) => record_undo(service_name, UndoableAction::Stop, "Running", false),
+            _ => {}
+        }
+        let service = get_service_status_internal(service_name).await.ok();
+        Ok(ServiceOperation {
+            success: true,
+            message: format!("{} {} successfully", service_name, done),
+            service,
+        })
+    } else {
+        let error = String::from_utf8_lossy(&output.stderr);
+        Ok(ServiceOperation {
+            success: false,
+            message: format!("Failed to {} {}: {}", action, service_name, error),
+            service: None,
+        })
+    }
+}
+
 #[tauri::command]
-pub async fn start_service(service_name: String) -> Result<ServiceOperation, String> {
+pub async fn get_docker_containers() -> Result<Vec<Service>, CommandError> {
+    DockerBackend::require_available()?;
+    DockerBackend::list_containers()
+}
+
+#[tauri::command]
+pub async fn start_service(
+    db: State<'_, Arc<Mutex<Database>>>,
+    service_name: String,
+    host_id: Option<i64>,
+) -> Result<ServiceOperation, CommandError> {
+    validate_service_name(&service_name)?;
+
     log::info!("🚀 Starting service: {}", service_name);
     
+    if let Some(backend) = remote_backend(&db, host_id).await? {
+        return Ok(run_blocking(move || remote_operation(&backend, &service_name, "start", "started")).await?);
+    }
+
+    if let Some(container) = DockerBackend::container_name(&service_name) {
+        return docker_operation(&service_name, container, "start", "started", true).await;
+    }
+
     let systemd_service = match find_service_name(&service_name) {
         Ok(service) => {
             log::debug!("✅ Found systemd service: {}", service);
@@ -455,7 +1685,8 @@ pub async fn start_service(service_name: String) -> Result<ServiceOperation, Str
     };
 
     // Check if already running
-    if let Ok(ServiceStatus::Running) = check_service_status(&systemd_service) {
+    let previous_status = check_service_status(&systemd_service);
+    if let Ok(ServiceStatus::Running) = previous_status {
         log::info!("ℹ️ Service {} is already running", service_name);
         return Ok(ServiceOperation {
             success: true,
@@ -471,9 +1702,11 @@ pub async fn start_service(service_name: String) -> Result<ServiceOperation, Str
         .args(&["--user", "start", &systemd_service])
         .output();
     
+    let mut user_unit = false;
     let output = match user_output {
         Ok(out) if out.status.success() => {
             log::debug!("✅ User-level start command succeeded");
+            user_unit = true;
             out
         }
         _ => {
@@ -484,7 +1717,7 @@ pub async fn start_service(service_name: String) -> Result<ServiceOperation, Str
                 Ok(out) => out,
                 Err(e) => {
                     log::error!("❌ Failed to execute start command: {}", e);
-                    return Err(format!("Failed to execute command: {}", e));
+                    return Err(format!("Failed to execute command: {}", e).into());
                 }
             }
         }
@@ -492,13 +1725,15 @@ pub async fn start_service(service_name: String) -> Result<ServiceOperation, Str
 
     if output.status.success() {
         log::info!("✅ Service {} started successfully", service_name);
+        record_session_start(&service_name, user_unit);
+        record_undo(&service_name, UndoableAction::Start, &status_label(&previous_status), user_unit);
         
         // Get updated service info
-        let service = match get_service_status(service_name.clone()).await {
+        let service = match get_service_status_internal(&service_name).await {
             Ok(service) => service,
             Err(e) => {
                 log::warn!("⚠️ Failed to get updated service status: {}", e);
-                return Err(format!("Failed to get updated status: {}", e));
+                return Err(format!("Failed to get updated status: {}", e).into());
             }
         };
 
@@ -518,54 +1753,214 @@ pub async fn start_service(service_name: String) -> Result<ServiceOperation, Str
     }
 }
 
+// Polls until the unit leaves its running states or the deadline passes
+async fn wait_for_stop(systemd_service: &str, timeout: std::time::Duration) -> bool {
+    let deadline = std::time::Instant::now() + timeout;
+    loop {
+        match check_service_status(systemd_service) {
+            Ok(ServiceStatus::Stopped) | Ok(ServiceStatus::Failed) | Ok(ServiceStatus::SocketActivated) => return true,
+            _ => {}
+        }
+        if std::time::Instant::now() >= deadline {
+            return false;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(250)).await;
+    }
+}
+
+// Services flagged critical are only stopped or restarted once the caller confirms; the
+// refusal is an unsuccessful operation so the UI can ask and retry with confirmed=true
+async fn require_confirmation(
+    db: &Arc<Mutex<Database>>,
+    service_name: &str,
+    action: &str,
+    confirmed: Option<bool>,
+) -> Option<ServiceOperation> {
+    if confirmed == Some(true) {
+        return None;
+    }
+
+    let critical = db.lock().await.is_service_critical(service_name).await.unwrap_or_else(|e| {
+        log::warn!("⚠️ Failed to check whether {} is critical: {}", service_name, e);
+        false
+    });
+    if !critical {
+        return None;
+    }
+
+    log::info!("🛡️ Not going to {} critical service {} without confirmation", action, service_name);
+    Some(ServiceOperation {
+        success: false,
+        message: format!("{} is marked critical; {} requires confirmation", service_name, action),
+        service: None,
+    })
+}
+
 #[tauri::command]
-pub async fn stop_service(service_name: String) -> Result<ServiceOperation, String> {
+pub async fn stop_service(
+    db: State<'_, Arc<Mutex<Database>>>,
+    service_name: String,
+    timeout_secs: Option<u64>,
+    force_after_timeout: Option<bool>,
+    host_id: Option<i64>,
+    confirmed: Option<bool>,
+) -> Result<ServiceOperation, CommandError> {
+    validate_service_name(&service_name)?;
+
+    if let Some(backend) = remote_backend(&db, host_id).await? {
+        // The graceful-stop timeout and SIGKILL fallback are only implemented locally
+        if timeout_secs.is_some() || force_after_timeout.is_some() {
+            return Err(CommandError::InvalidInput(
+                "timeout_secs and force_after_timeout are not supported for remote hosts".to_string(),
+            ));
+        }
+        return Ok(run_blocking(move || remote_operation(&backend, &service_name, "stop", "stopped")).await?);
+    }
+
+    // Critical flags describe local services, so a same-named remote unit isn't held back
+    if let Some(refused) = require_confirmation(&db, &service_name, "stop", confirmed).await {
+        return Ok(refused);
+    }
+
+    if let Some(container) = DockerBackend::container_name(&service_name) {
+        return docker_operation(&service_name, container, "stop", "stopped", true).await;
+    }
+
     let systemd_service = find_service_name(&service_name)?;
 
     // Check if already stopped
-    if let Ok(ServiceStatus::Stopped) = check_service_status(&systemd_service) {
+    let previous_status = check_service_status(&systemd_service);
+    if let Ok(ServiceStatus::Stopped) = previous_status {
         return Ok(ServiceOperation {
             success: true,
             message: format!("{} is already stopped", service_name),
             service: None,
         });
     }
+    // Stopping a failed or inactive unit leaves nothing for undo to start again
+    let was_running = matches!(previous_status, Ok(ServiceStatus::Running | ServiceStatus::Activating | ServiceStatus::Reloading));
+
+    let Some(timeout_secs) = timeout_secs else {
+        // No window requested: let the unit's own TimeoutStopSec govern the stop
+        let output = Command::new("sudo")
+            .args(&["systemctl", "stop", &systemd_service])
+            .output()
+            .map_err(|e| format!("Failed to execute command: {}", e))?;
+
+        return if output.status.success() {
+            if was_running {
+                record_undo(&service_name, UndoableAction::Stop, &status_label(&previous_status), false);
+            }
+            let service = get_service_status_internal(&service_name).await
+                .map_err(|e| format!("Failed to get updated status: {}", e))?;
 
+            Ok(ServiceOperation {
+                success: true,
+                message: format!("{} stopped successfully", service_name),
+                service: Some(service),
+            })
+        } else {
+            let error = String::from_utf8_lossy(&output.stderr);
+            Ok(ServiceOperation {
+                success: false,
+                message: format!("Failed to stop {}: {}", service_name, error),
+                service: None,
+            })
+        };
+    };
+
+    log::info!("⏹️ Stopping {} with a {}s window", service_name, timeout_secs);
     let output = Command::new("sudo")
-        .args(&["systemctl", "stop", &systemd_service])
+        .args(&["systemctl", "stop", "--no-block", &systemd_service])
         .output()
         .map_err(|e| format!("Failed to execute command: {}", e))?;
 
-    if output.status.success() {
-        let service = get_service_status(service_name.clone()).await
-            .map_err(|e| format!("Failed to get updated status: {}", e))?;
-
-        Ok(ServiceOperation {
-            success: true,
-            message: format!("{} stopped successfully", service_name),
-            service: Some(service),
-        })
-    } else {
+    if !output.status.success() {
         let error = String::from_utf8_lossy(&output.stderr);
-        Ok(ServiceOperation {
+        return Ok(ServiceOperation {
             success: false,
             message: format!("Failed to stop {}: {}", service_name, error),
             service: None,
-        })
+        });
     }
-}
 
-#[tauri::command]
-pub async fn restart_service(service_name: String) -> Result<ServiceOperation, String> {
-    let systemd_service = find_service_name(&service_name)?;
+    let message = if wait_for_stop(&systemd_service, std::time::Duration::from_secs(timeout_secs)).await {
+        format!("{} stopped gracefully", service_name)
+    } else if force_after_timeout.unwrap_or(false) {
+        log::warn!("⚠️ {} did not stop within {}s, sending SIGKILL", service_name, timeout_secs);
+        let kill_output = Command::new("sudo")
+            .args(&["systemctl", "kill", "-s", "SIGKILL", &systemd_service])
+            .output()
+            .map_err(|e| format!("Failed to execute command: {}", e))?;
 
-    let output = Command::new("sudo")
-        .args(&["systemctl", "restart", &systemd_service])
-        .output()
-        .map_err(|e| format!("Failed to execute command: {}", e))?;
+        if !kill_output.status.success() || !wait_for_stop(&systemd_service, std::time::Duration::from_secs(5)).await {
+            let error = String::from_utf8_lossy(&kill_output.stderr);
+            return Ok(ServiceOperation {
+                success: false,
+                message: format!("{} did not stop within {}s and SIGKILL failed: {}", service_name, timeout_secs, error),
+                service: get_service_status_internal(&service_name).await.ok(),
+            });
+        }
+        format!("{} did not stop within {}s and was force-killed", service_name, timeout_secs)
+    } else {
+        return Ok(ServiceOperation {
+            success: false,
+            message: format!("{} did not stop within {}s; it may still be stopping", service_name, timeout_secs),
+            service: get_service_status_internal(&service_name).await.ok(),
+        });
+    };
+
+    log::info!("✅ {}", message);
+    if was_running {
+        record_undo(&service_name, UndoableAction::Stop, &status_label(&previous_status), false);
+    }
+    let service = get_service_status_internal(&service_name).await
+        .map_err(|e| format!("Failed to get updated status: {}", e))?;
+
+    Ok(ServiceOperation {
+        success: true,
+        message,
+        service: Some(service),
+    })
+}
+
+#[tauri::command]
+pub async fn restart_service(
+    db: State<'_, Arc<Mutex<Database>>>,
+    service_name: String,
+    host_id: Option<i64>,
+    confirmed: Option<bool>,
+) -> Result<ServiceOperation, CommandError> {
+    validate_service_name(&service_name)?;
+
+    if let Some(backend) = remote_backend(&db, host_id).await? {
+        return Ok(run_blocking(move || remote_operation(&backend, &service_name, "restart", "restarted")).await?);
+    }
+
+    if let Some(refused) = require_confirmation(&db, &service_name, "restart", confirmed).await {
+        return Ok(refused);
+    }
+
+    restart_local_service(&service_name).await
+}
+
+// Restarts a local unit or container without the critical-service check: restart_service has
+// already asked, and the config file watcher only restarts services the user set it up for
+pub async fn restart_local_service(service_name: &str) -> Result<ServiceOperation, CommandError> {
+    restart_with_password(service_name, None).await
+}
+
+async fn restart_with_password(service_name: &str, password: Option<String>) -> Result<ServiceOperation, CommandError> {
+    if let Some(container) = DockerBackend::container_name(service_name) {
+        return docker_operation(service_name, container, "restart", "restarted", false).await;
+    }
+
+    let systemd_service = find_service_name(service_name)?;
+
+    let output = execute_sudo_command(&["systemctl", "restart", &systemd_service], password, true)?;
 
     if output.status.success() {
-        let service = get_service_status(service_name.clone()).await
+        let service = get_service_status_internal(service_name).await
             .map_err(|e| format!("Failed to get updated status: {}", e))?;
 
         Ok(ServiceOperation {
@@ -583,1009 +1978,7197 @@ pub async fn restart_service(service_name: String) -> Result<ServiceOperation, S
     }
 }
 
-#[tauri::command]
-pub async fn enable_service(service_name: String) -> Result<ServiceOperation, String> {
-    let systemd_service = find_service_name(&service_name)?;
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RestartFailedSummary {
+    pub operations: Vec<ServiceOperation>,
+    // Restarted and no longer failed right after the restart
+    pub recovered: usize,
+    pub still_failed: usize,
+}
 
-    let output = Command::new("sudo")
-        .args(&["systemctl", "enable", &systemd_service])
+// Failed service units from one list-units call; template instances are left alone since
+// restarting foo@bar is rarely what a one-click recovery should do
+fn list_failed_units() -> Result<Vec<String>, String> {
+    let output = Command::new("systemctl")
+        .args(&["list-units", "--type=service", "--state=failed", "--no-legend", "--plain", "--no-pager"])
         .output()
-        .map_err(|e| format!("Failed to execute command: {}", e))?;
+        .map_err(|e| format!("Failed to list failed units: {}", e))?;
 
-    if output.status.success() {
-        let service = get_service_status(service_name.clone()).await
-            .map_err(|e| format!("Failed to get updated status: {}", e))?;
+    let mut units: Vec<String> = Vec::new();
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        // UNIT LOAD ACTIVE SUB DESCRIPTION
+        let Some(unit) = line.split_whitespace().next() else {
+            continue;
+        };
+        if unit.contains('@') || units.iter().any(|known| known == unit) {
+            continue;
+        }
+        units.push(unit.to_string());
+    }
 
-        Ok(ServiceOperation {
-            success: true,
-            message: format!("{} enabled for auto-start", service_name),
-            service: Some(service),
-        })
-    } else {
-        let error = String::from_utf8_lossy(&output.stderr);
-        Ok(ServiceOperation {
-            success: false,
-            message: format!("Failed to enable {}: {}", service_name, error),
-            service: None,
-        })
+    Ok(units)
+}
+
+// Critical services among the failed ones are refused unless confirmed covers the whole batch
+#[tauri::command]
+pub async fn restart_failed_services(
+    db: State<'_, Arc<Mutex<Database>>>,
+    password: Option<String>,
+    confirmed: Option<bool>,
+) -> Result<RestartFailedSummary, CommandError> {
+    let units = list_failed_units()?;
+    log::info!("🔄 Restarting {} failed services", units.len());
+
+    let mut operations = Vec::with_capacity(units.len());
+    for unit in units {
+        let service_name = unit.trim_end_matches(".service").to_string();
+        if let Some(refused) = require_confirmation(&db, &service_name, "restart", confirmed).await {
+            operations.push(refused);
+            continue;
+        }
+        let operation = match restart_with_password(&service_name, password.clone()).await {
+            Ok(operation) => operation,
+            Err(e) => ServiceOperation {
+                success: false,
+                message: format!("Failed to restart {}: {}", service_name, e),
+                service: None,
+            },
+        };
+        operations.push(operation);
     }
+
+    let recovered = operations
+        .iter()
+        .filter(|operation| {
+            operation.success
+                && operation.service.as_ref().is_some_and(|service| !matches!(service.status, ServiceStatus::Failed))
+        })
+        .count();
+    let still_failed = operations.len() - recovered;
+    log::info!("✅ Restarted failed services: {} recovered, {} still failed", recovered, still_failed);
+
+    Ok(RestartFailedSummary {
+        operations,
+        recovered,
+        still_failed,
+    })
+}
+
+#[tauri::command]
+pub async fn enable_service(service_name: String) -> Result<ServiceOperation, CommandError> {
+    set_service_enabled(service_name, None, true, true).await
 }
 
 #[tauri::command]
-pub async fn disable_service(service_name: String) -> Result<ServiceOperation, String> {
+pub async fn disable_service(service_name: String) -> Result<ServiceOperation, CommandError> {
+    set_service_enabled(service_name, None, false, true).await
+}
+
+// Runs systemctl enable|disable; an undoable call that flipped the state goes on the undo stack
+async fn set_service_enabled(
+    service_name: String,
+    password: Option<String>,
+    enable: bool,
+    undoable: bool,
+) -> Result<ServiceOperation, CommandError> {
+    validate_service_name(&service_name)?;
+
     let systemd_service = find_service_name(&service_name)?;
+    let action = if enable { "enable" } else { "disable" };
+    let was_enabled = check_service_enabled(&systemd_service);
 
-    let output = Command::new("sudo")
-        .args(&["systemctl", "disable", &systemd_service])
-        .output()
-        .map_err(|e| format!("Failed to execute command: {}", e))?;
+    let output = execute_sudo_command(&["systemctl", action, &systemd_service], password, true)?;
 
     if output.status.success() {
-        let service = get_service_status(service_name.clone()).await
+        if undoable && was_enabled != enable {
+            let (undo_action, previous_state) = if enable {
+                (UndoableAction::Enable, "disabled")
+            } else {
+                (UndoableAction::Disable, "enabled")
+            };
+            record_undo(&service_name, undo_action, previous_state, false);
+        }
+
+        let service = get_service_status_internal(&service_name).await
             .map_err(|e| format!("Failed to get updated status: {}", e))?;
 
         Ok(ServiceOperation {
             success: true,
-            message: format!("{} disabled from auto-start", service_name),
+            message: if enable {
+                format!("{} enabled for auto-start", service_name)
+            } else {
+                format!("{} disabled from auto-start", service_name)
+            },
             service: Some(service),
         })
     } else {
         let error = String::from_utf8_lossy(&output.stderr);
         Ok(ServiceOperation {
             success: false,
-            message: format!("Failed to disable {}: {}", service_name, error),
+            message: format!("Failed to {} {}: {}", action, service_name, error),
             service: None,
         })
     }
 }
 
 #[tauri::command]
-pub async fn get_service_logs(service_name: String, lines: Option<u32>) -> Result<ServiceLogs, String> {
-    let systemd_service = find_service_name(&service_name)?;
-
-    let lines_arg = format!("{}", lines.unwrap_or(50));
-    
-    let output = Command::new("journalctl")
-        .args(&["-u", &systemd_service, "--no-pager", "-n", &lines_arg, "--since", "1 hour ago"])
-        .output()
-        .map_err(|e| format!("Failed to get logs: {}", e))?;
+pub async fn get_container_logs(container: String, lines: Option<u32>) -> Result<ServiceLogs, CommandError> {
+    validate_service_name(&container)?;
 
-    let logs_text = String::from_utf8_lossy(&output.stdout);
-    let logs: Vec<String> = logs_text.lines().map(|s| s.to_string()).collect();
+    let name = DockerBackend::container_name(&container).unwrap_or(&container);
+    let logs = DockerBackend::container_logs(name, lines.unwrap_or(50))?;
 
     Ok(ServiceLogs {
-        service_name,
+        service_name: container,
         logs,
         timestamp: Utc::now(),
     })
 }
 
 #[tauri::command]
-pub async fn get_system_logs(service_name: String, lines: Option<u32>) -> Result<SystemLogs, String> {
-    let systemd_service = find_service_name(&service_name)?;
-    let lines_count = lines.unwrap_or(50);
-    let lines_arg = format!("{}", lines_count);
-    
-    // Get system logs related to the service
-    let system_logs_output = Command::new("journalctl")
-        .args(&["--system", "-n", &lines_arg, "--since", "1 hour ago"])
-        .output()
-        .map_err(|e| format!("Failed to get system logs: {}", e))?;
-    
-    // Get kernel logs that might be related to the service
-    let kernel_logs_output = Command::new("dmesg")
-        .args(&["-T"])
-        .output();
-    
-    // Get boot logs
-    let boot_logs_output = Command::new("journalctl")
-        .args(&["-b", "0", "-n", "100", "--no-pager"])
-        .output();
-    
-    let system_logs_text = String::from_utf8_lossy(&system_logs_output.stdout);
-    let system_logs: Vec<String> = system_logs_text
-        .lines()
-        .filter(|line| line.contains(&service_name) || line.contains(&systemd_service))
-        .map(|s| s.to_string())
-        .collect();
-    
-    let kernel_logs: Vec<String> = match kernel_logs_output {
-        Ok(output) => {
-            let kernel_logs_text = String::from_utf8_lossy(&output.stdout);
-            kernel_logs_text
-                .lines()
-                .filter(|line| {
-                    let lower_line = line.to_lowercase();
-                    lower_line.contains(&service_name.to_lowercase()) || 
-                    lower_line.contains("error") || 
-                    lower_line.contains("warning") ||
-                    lower_line.contains("fail")
-                })
-                .take(20) // Limit kernel logs to avoid noise
-                .map(|s| s.to_string())
-                .collect()
+pub async fn cancel_request(
+    requests: State<'_, PendingRequests>,
+    request_id: String,
+) -> Result<bool, CommandError> {
+    let requests = requests.lock().unwrap_or_else(|e| e.into_inner());
+    match requests.get(&request_id) {
+        Some(token) => {
+            log::debug!("🛑 Cancelling request {}", request_id);
+            token.cancel();
+            Ok(true)
+        }
+        // It already finished, or never registered
+        None => Ok(false),
+    }
+}
+
+#[tauri::command]
+pub async fn get_container_metrics(container: String) -> Result<SystemMetrics, CommandError> {
+    validate_service_name(&container)?;
+
+    let name = DockerBackend::container_name(&container).unwrap_or(&container);
+    DockerBackend::container_metrics(&container, name)
+}
+
+// app_settings keys for what get_service_logs uses when lines or since is omitted
+const LOG_DEFAULT_LINES_SETTING: &str = "log_default_lines";
+const LOG_DEFAULT_SINCE_SETTING: &str = "log_default_since";
+const DEFAULT_LOG_LINES: u32 = 50;
+const DEFAULT_LOG_SINCE: &str = "1 hour ago";
+const MAX_LOG_DEFAULT_LINES: u32 = 10000;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LogDefaults {
+    pub lines: u32,
+    // A systemd time spec, e.g. "1 hour ago", "today" or "2024-01-01 12:00"
+    pub since: String,
+}
+
+// Stored values that no longer validate fall back to the built-in defaults
+async fn load_log_defaults(db: &Arc<Mutex<Database>>) -> LogDefaults {
+    let db = db.lock().await;
+    let lines = match db.get_app_setting(LOG_DEFAULT_LINES_SETTING).await {
+        Ok(value) => value
+            .and_then(|value| value.parse::<u32>().ok())
+            .filter(|lines| (1..=MAX_LOG_DEFAULT_LINES).contains(lines))
+            .unwrap_or(DEFAULT_LOG_LINES),
+        Err(e) => {
+            log::warn!("⚠️ Failed to load default log lines: {}", e);
+            DEFAULT_LOG_LINES
         }
-        Err(_) => Vec::new(),
     };
-    
-    let boot_logs: Vec<String> = match boot_logs_output {
-        Ok(output) => {
-            let boot_logs_text = String::from_utf8_lossy(&output.stdout);
-            boot_logs_text
-                .lines()
-                .filter(|line| line.contains(&service_name) || line.contains(&systemd_service))
-                .take(10) // Limit boot logs
-                .map(|s| s.to_string())
-                .collect()
+    let since = match db.get_app_setting(LOG_DEFAULT_SINCE_SETTING).await {
+        Ok(value) => value.filter(|since| !since.trim().is_empty()).unwrap_or_else(|| DEFAULT_LOG_SINCE.to_string()),
+        Err(e) => {
+            log::warn!("⚠️ Failed to load default log window: {}", e);
+            DEFAULT_LOG_SINCE.to_string()
         }
-        Err(_) => Vec::new(),
     };
-    
-    Ok(SystemLogs {
-        service_name,
-        system_logs,
-        kernel_logs,
-        boot_logs,
-        timestamp: Utc::now(),
-    })
+
+    LogDefaults { lines, since }
 }
 
-#[tauri::command]
-pub async fn start_multiple_services(service_names: Vec<String>) -> Result<Vec<ServiceOperation>, String> {
-    let mut results = Vec::new();
+// systemd-analyze parses timestamps exactly like journalctl --since does; "--" keeps a
+// relative spec such as "-1h" from being read as an option
+fn validate_time_spec(spec: &str) -> Result<(), CommandError> {
+    let spec = spec.trim();
+    if spec.is_empty() {
+        return Err(CommandError::InvalidInput("The log window can't be empty".to_string()));
+    }
 
-    for service_name in service_names {
-        let result = start_service(service_name).await;
-        match result {
-            Ok(operation) => results.push(operation),
-            Err(e) => results.push(ServiceOperation {
-                success: false,
-                message: e,
-                service: None,
-            }),
-        }
+    let output = Command::new("systemd-analyze")
+        .args(&["timestamp", "--", spec])
+        .output()
+        .map_err(|e| CommandError::Internal(format!("Failed to run systemd-analyze to check '{}': {}", spec, e)))?;
+    if !output.status.success() {
+        return Err(CommandError::InvalidInput(format!("Invalid time spec '{}': {}", spec, String::from_utf8_lossy(&output.stderr).trim())));
     }
 
-    Ok(results)
+    Ok(())
 }
 
 #[tauri::command]
-pub async fn stop_all_services() -> Result<Vec<ServiceOperation>, String> {
-    let services = get_services(None, Some(true)).await?;
-    let mut results = Vec::new();
+pub async fn get_log_defaults(db: State<'_, Arc<Mutex<Database>>>) -> Result<LogDefaults, CommandError> {
+    Ok(load_log_defaults(&db).await)
+}
 
-    for service in services {
-        if service.status == ServiceStatus::Running {
-            let result = stop_service(service.name).await;
-            match result {
-                Ok(operation) => results.push(operation),
-                Err(e) => results.push(ServiceOperation {
-                    success: false,
-                    message: e,
-                    service: None,
-                }),
-            }
+// Either value may be omitted to leave it unchanged
+#[tauri::command]
+pub async fn set_log_defaults(
+    db: State<'_, Arc<Mutex<Database>>>,
+    lines: Option<u32>,
+    since: Option<String>,
+) -> Result<LogDefaults, CommandError> {
+    if let Some(lines) = lines {
+        if !(1..=MAX_LOG_DEFAULT_LINES).contains(&lines) {
+            return Err(CommandError::InvalidInput(format!(
+                "Default log lines must be between 1 and {}, got {}", MAX_LOG_DEFAULT_LINES, lines
+            )));
         }
     }
+    let since = since.map(|since| since.trim().to_string());
+    if let Some(since) = &since {
+        validate_time_spec(since)?;
+    }
 
-    Ok(results)
+    {
+        let db = db.lock().await;
+        if let Some(lines) = lines {
+            db.set_app_setting(LOG_DEFAULT_LINES_SETTING, &lines.to_string())
+                .await
+                .map_err(|e| format!("Failed to save default log lines: {}", e))?;
+        }
+        if let Some(since) = &since {
+            db.set_app_setting(LOG_DEFAULT_SINCE_SETTING, since)
+                .await
+                .map_err(|e| format!("Failed to save default log window: {}", e))?;
+        }
+    }
+
+    Ok(load_log_defaults(&db).await)
 }
 
 #[tauri::command]
-pub async fn check_auth_required(service_name: String) -> Result<AuthRequest, String> {
+pub async fn get_service_logs(
+    db: State<'_, Arc<Mutex<Database>>>,
+    service_name: String,
+    lines: Option<u32>,
+    since: Option<String>,
+    include_children: Option<bool>,
+) -> Result<ServiceLogs, CommandError> {
+    validate_service_name(&service_name)?;
+
+    let defaults = load_log_defaults(&db).await;
+    let lines = lines.unwrap_or(defaults.lines);
+
+    if DockerBackend::container_name(&service_name).is_some() {
+        return get_container_logs(service_name, Some(lines)).await;
+    }
+
     let systemd_service = find_service_name(&service_name)?;
+    require_journalctl()?;
 
-    // Try to check if we can manage this service without sudo
-    let user_check = Command::new("systemctl")
-        .args(&["--user", "is-active", &systemd_service])
-        .output();
+    let lines_arg = format!("{}", lines);
+    let since_arg = since.unwrap_or(defaults.since);
 
-    match user_check {
-        Ok(output) if output.status.success() => {
-            Ok(AuthRequest {
-                requires_auth: false,
-                message: "Service can be managed without authentication".to_string(),
-            })
-        }
-        _ => {
-            Ok(AuthRequest {
-                requires_auth: true,
-                message: "Administrator privileges required to manage system services".to_string(),
-            })
+    if include_children.unwrap_or(false) {
+        let children = child_units(&systemd_service);
+        if !children.is_empty() {
+            log::debug!("📜 Including logs of {} child units of {}", children.len(), systemd_service);
+            let logs = unit_family_logs(&service_name, &systemd_service, children, &lines_arg, &since_arg)?;
+            return Ok(ServiceLogs {
+                service_name,
+                logs,
+                timestamp: Utc::now(),
+            });
         }
     }
+    
+    let output = Command::new("journalctl")
+        .args(&["-u", &systemd_service, "--no-pager", "-n", &lines_arg, "--since", &since_arg])
+        .output()
+        .map_err(|e| format!("Failed to get logs: {}", e))?;
+
+    let logs_text = String::from_utf8_lossy(&output.stdout);
+    let logs: Vec<String> = logs_text.lines().map(|s| s.to_string()).collect();
+
+    Ok(ServiceLogs {
+        service_name,
+        logs,
+        timestamp: Utc::now(),
+    })
 }
 
-#[tauri::command]
-pub async fn start_service_with_auth(service_name: String, password: Option<String>) -> Result<ServiceOperation, String> {
-    let systemd_service = find_service_name(&service_name)?;
+// Template instances sharing the service's base name, e.g. foo@1.service and foo@2.service for foo
+fn child_units(systemd_service: &str) -> Vec<String> {
+    let base = systemd_service
+        .trim_end_matches(".service")
+        .split('@')
+        .next()
+        .unwrap_or_default();
+    let pattern = format!("{}@*.service", base);
 
-    // Check if already running
-    if let Ok(ServiceStatus::Running) = check_service_status(&systemd_service) {
-        return Ok(ServiceOperation {
-            success: true,
-            message: format!("{} is already running", service_name),
-            service: None,
-        });
+    let Ok(output) = Command::new("systemctl")
+        .args(&["list-units", "--all", "--type=service", "--no-legend", "--plain", "--no-pager", &pattern])
+        .output()
+    else {
+        return Vec::new();
+    };
+
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| line.split_whitespace().next())
+        .filter(|unit| *unit != systemd_service)
+        .map(|unit| unit.to_string())
+        .collect()
+}
+
+// Interleaved journal of the service and its children, each line tagged with its unit
+fn unit_family_logs(
+    service_name: &str,
+    systemd_service: &str,
+    children: Vec<String>,
+    lines_arg: &str,
+    since_arg: &str,
+) -> Result<Vec<String>, String> {
+    let mut units = vec![(service_name.to_string(), systemd_service.to_string())];
+    units.extend(children.into_iter().map(|unit| (unit.clone(), unit)));
+
+    let mut args = vec!["--no-pager", "-o", "json", "-n", lines_arg, "--since", since_arg];
+    for (_, unit) in &units {
+        args.push("-u");
+        args.push(unit);
     }
 
-    let args = ["systemctl", "start", &systemd_service];
-    let output = execute_sudo_command(&args, password, true)?;
+    let output = Command::new("journalctl")
+        .args(&args)
+        .output()
+        .map_err(|e| format!("Failed to get logs: {}", e))?;
 
-    if output.status.success() {
-        let service = get_service_status(service_name.clone()).await
-            .map_err(|e| format!("Failed to get updated status: {}", e))?;
+    let mut entries: Vec<LogEntry> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| parse_journal_entry(line, &units))
+        .collect();
+    entries.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
 
-        Ok(ServiceOperation {
-            success: true,
-            message: format!("{} started successfully", service_name),
-            service: Some(service),
-        })
-    } else {
-        let error = String::from_utf8_lossy(&output.stderr);
-        Ok(ServiceOperation {
-            success: false,
-            message: format!("Failed to start {}: {}", service_name, error),
-            service: None,
+    // Same timestamp layout as journalctl's short output
+    Ok(entries
+        .into_iter()
+        .map(|entry| {
+            let timestamp = entry.timestamp.with_timezone(&chrono::Local).format("%b %d %H:%M:%S");
+            format!("{} [{}] {}", timestamp, entry.unit, entry.message)
         })
+        .collect())
+}
+
+// Journal fields are strings, but MESSAGE can be a byte array when it isn't valid UTF-8
+fn journal_field_string(entry: &serde_json::Value, field: &str) -> Option<String> {
+    match entry.get(field)? {
+        serde_json::Value::String(value) => Some(value.clone()),
+        serde_json::Value::Array(bytes) => {
+            let bytes: Vec<u8> = bytes.iter().filter_map(|b| b.as_u64().map(|b| b as u8)).collect();
+            Some(String::from_utf8_lossy(&bytes).to_string())
+        }
+        _ => None,
     }
 }
 
-#[tauri::command]
-pub async fn stop_service_with_auth(service_name: String, password: Option<String>) -> Result<ServiceOperation, String> {
-    let systemd_service = find_service_name(&service_name)?;
+fn parse_journal_entry(line: &str, units: &[(String, String)]) -> Option<LogEntry> {
+    let entry: serde_json::Value = serde_json::from_str(line).ok()?;
 
-    let args = ["systemctl", "stop", &systemd_service];
-    let output = execute_sudo_command(&args, password, true)?;
+    // _SYSTEMD_UNIT is set for the unit's own output, UNIT for systemd's messages about it
+    let unit = journal_field_string(&entry, "_SYSTEMD_UNIT")
+        .filter(|u| units.iter().any(|(_, unit)| unit == u))
+        .or_else(|| journal_field_string(&entry, "UNIT"))?;
+    let service_name = units
+        .iter()
+        .find(|(_, systemd_unit)| *systemd_unit == unit)
+        .map(|(name, _)| name.clone())
+        .unwrap_or_else(|| unit.clone());
 
-    if output.status.success() {
-        let service = get_service_status(service_name.clone()).await
-            .map_err(|e| format!("Failed to get updated status: {}", e))?;
+    let micros: i64 = journal_field_string(&entry, "__REALTIME_TIMESTAMP")?.parse().ok()?;
 
-        Ok(ServiceOperation {
-            success: true,
-            message: format!("{} stopped successfully", service_name),
-            service: Some(service),
-        })
-    } else {
-        let error = String::from_utf8_lossy(&output.stderr);
-        Ok(ServiceOperation {
-            success: false,
-            message: format!("Failed to stop {}: {}", service_name, error),
-            service: None,
-        })
-    }
+    Some(LogEntry {
+        service_name,
+        unit,
+        timestamp: DateTime::from_timestamp_micros(micros)?,
+        message: journal_field_string(&entry, "MESSAGE").unwrap_or_default(),
+        priority: journal_field_string(&entry, "PRIORITY").and_then(|p| p.parse().ok()),
+        pid: journal_field_string(&entry, "_PID").and_then(|p| p.parse().ok()),
+    })
 }
 
 #[tauri::command]
-pub async fn get_service_metrics(service_name: String) -> Result<SystemMetrics, String> {
-    let systemd_service = find_service_name(&service_name)?;
-    
-    // Get CPU and memory usage for the service and all its child processes
-    let mut cpu_usage = 0.0;
-    let mut memory_usage = 0;
-    let mut process_count = 0;
-    let mut open_files = 0;
-    let mut network_in = 0;
-    let mut network_out = 0;
-    let mut disk_read = 0;
-    let mut disk_write = 0;
-    
-    // Get all PIDs for the service (including child processes)
-    let mut all_pids = Vec::new();
-    
-    // Get main PID
-    let pid_output = Command::new("systemctl")
-        .args(&["show", "--property=MainPID", &systemd_service])
-        .output()
-        .map_err(|e| format!("Failed to get service PID: {}", e))?;
-    
-    if let Ok(pid_str) = String::from_utf8(pid_output.stdout) {
-        for line in pid_str.lines() {
-            if line.starts_with("MainPID=") {
-                if let Ok(pid) = line.replace("MainPID=", "").parse::<u32>() {
-                    if pid > 0 {
-                        all_pids.push(pid);
-                    }
-                }
-            }
-        }
+pub async fn get_multi_service_logs(
+    service_names: Vec<String>,
+    lines: Option<u32>,
+    since: Option<String>,
+) -> Result<MultiServiceLogs, CommandError> {
+    for service_name in &service_names {
+        validate_service_name(service_name)?;
     }
-    
-    // Get cgroup PIDs for more comprehensive tracking
-    if let Ok(cgroup_output) = Command::new("systemctl")
-        .args(&["show", "--property=ControlGroup", &systemd_service])
-        .output()
-    {
-        if let Ok(cgroup_str) = String::from_utf8(cgroup_output.stdout) {
-            for line in cgroup_str.lines() {
-                if line.starts_with("ControlGroup=") {
-                    let cgroup_path = line.replace("ControlGroup=", "");
-                    if !cgroup_path.is_empty() && cgroup_path != "/" {
-                        // Try to get PIDs from cgroup
-                        let cgroup_procs_path = format!("/sys/fs/cgroup{}/cgroup.procs", cgroup_path);
-                        if let Ok(procs_content) = std::fs::read_to_string(&cgroup_procs_path) {
-                            for pid_line in procs_content.lines() {
-                                if let Ok(pid) = pid_line.trim().parse::<u32>() {
-                                    if pid > 0 && !all_pids.contains(&pid) {
-                                        all_pids.push(pid);
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
+    require_journalctl()?;
+
+    log::info!("📜 Getting interleaved logs for {} services", service_names.len());
+
+    let mut units = Vec::new();
+    let mut skipped = Vec::new();
+    for service_name in service_names {
+        match find_service_name(&service_name) {
+            Ok(unit) => units.push((service_name, unit)),
+            Err(e) => {
+                log::warn!("⚠️ Skipping {} in multi-service logs: {}", service_name, e);
+                skipped.push(service_name);
             }
         }
     }
-    
-    // If no PIDs found, try alternative method
-    if all_pids.is_empty() {
-        if let Ok(pgrep_output) = Command::new("pgrep")
-            .args(&["-f", &service_name])
-            .output()
-        {
-            if let Ok(pgrep_str) = String::from_utf8(pgrep_output.stdout) {
-                for pid_line in pgrep_str.lines() {
-                    if let Ok(pid) = pid_line.trim().parse::<u32>() {
-                        if pid > 0 {
-                            all_pids.push(pid);
-                        }
-                    }
-                }
-            }
-        }
+
+    if units.is_empty() {
+        return Ok(MultiServiceLogs {
+            entries: Vec::new(),
+            skipped,
+            timestamp: Utc::now(),
+        });
     }
-    
-    // Aggregate metrics from all PIDs
-    for pid in &all_pids {
-        // Get CPU and memory from ps
-        if let Ok(ps_output) = Command::new("ps")
-            .args(&["-p", &pid.to_string(), "-o", "pcpu,rss,nlwp"])
-            .output()
-        {
-            if let Ok(ps_str) = String::from_utf8(ps_output.stdout) {
-                let lines: Vec<&str> = ps_str.lines().collect();
-                if lines.len() > 1 {
-                    let fields: Vec<&str> = lines[1].split_whitespace().collect();
-                    if fields.len() >= 3 {
-                        cpu_usage += fields[0].parse::<f32>().unwrap_or(0.0);
-                        memory_usage += fields[1].parse::<u64>().unwrap_or(0) * 1024; // RSS is in KB
-                        process_count += fields[2].parse::<u32>().unwrap_or(0);
-                    }
-                }
-            }
-        }
-        
-        // Get open files count
-        if let Ok(lsof_output) = Command::new("lsof")
-            .args(&["-p", &pid.to_string()])
-            .output()
-        {
-            if let Ok(lsof_str) = String::from_utf8(lsof_output.stdout) {
-                open_files += lsof_str.lines().count().saturating_sub(1) as u32; // Subtract header line
-            }
+
+    let lines_arg = format!("{}", lines.unwrap_or(200));
+    let since_arg = since.unwrap_or_else(|| "1 hour ago".to_string());
+    let mut args = vec!["--no-pager", "-o", "json", "-n", lines_arg.as_str(), "--since", since_arg.as_str()];
+    for (_, unit) in &units {
+        args.push("-u");
+        args.push(unit);
+    }
+
+    let output = Command::new("journalctl")
+        .args(&args)
+        .output()
+        .map_err(|e| format!("Failed to get logs: {}", e))?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut entries: Vec<LogEntry> = stdout
+        .lines()
+        .filter_map(|line| parse_journal_entry(line, &units))
+        .collect();
+    entries.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+
+    log::info!("✅ Collected {} log entries ({} services skipped)", entries.len(), skipped.len());
+    Ok(MultiServiceLogs {
+        entries,
+        skipped,
+        timestamp: Utc::now(),
+    })
+}
+
+const DEFAULT_LOG_SEARCH_CONTEXT: usize = 2;
+const MAX_LOG_SEARCH_CONTEXT: usize = 20;
+// Entries a search reads at most, newest first, however few of them match
+const MAX_LOG_SEARCH_ENTRIES: usize = 100_000;
+
+// Matches are found here rather than with journalctl --grep, which can't return the lines
+// around a hit. The query is therefore Rust regex syntax, not journalctl's PCRE2.
+#[tauri::command]
+pub async fn search_service_logs(
+    service_name: String,
+    query: String,
+    since: Option<String>,
+    max_results: Option<usize>,
+    context_lines: Option<usize>,
+) -> Result<LogSearchResult, CommandError> {
+    validate_service_name(&service_name)?;
+
+    log::info!("🔎 Searching journal of {} for: {}", service_name, query);
+    let systemd_service = find_service_name(&service_name)?;
+    require_journalctl()?;
+
+    let pattern = regex::Regex::new(&query)
+        .map_err(|e| CommandError::InvalidInput(format!("Invalid search pattern '{}': {}", query, e)))?;
+    if let Some(since) = &since {
+        validate_time_spec(since)?;
+    }
+    let max_results = max_results.unwrap_or(200);
+    let context = context_lines.unwrap_or(DEFAULT_LOG_SEARCH_CONTEXT).min(MAX_LOG_SEARCH_CONTEXT);
+    let since_arg = since.unwrap_or_else(|| "30 days ago".to_string());
+    let units = vec![(service_name.clone(), systemd_service.clone())];
+
+    let (matches, truncated) = tokio::task::spawn_blocking(move || {
+        search_journal(&systemd_service, &units, &pattern, &since_arg, max_results, context)
+    })
+    .await
+    .map_err(|e| format!("Log search failed: {}", e))??;
+
+    log::info!("✅ Found {} matching log entries for {}{}", matches.len(), service_name,
+        if truncated { " (truncated)" } else { "" });
+    Ok(LogSearchResult {
+        service_name,
+        query,
+        matches,
+        truncated,
+        timestamp: Utc::now(),
+    })
+}
+
+// Reads the unit's journal newest first, stopping once max_results matches have their context
+// or MAX_LOG_SEARCH_ENTRIES entries were read. Returns the matches oldest first and whether
+// the search stopped early.
+fn search_journal(
+    systemd_service: &str,
+    units: &[(String, String)],
+    pattern: &regex::Regex,
+    since: &str,
+    max_results: usize,
+    context: usize,
+) -> Result<(Vec<LogMatch>, bool), CommandError> {
+    use std::io::BufRead;
+
+    let limit = MAX_LOG_SEARCH_ENTRIES.to_string();
+    let mut child = Command::new("journalctl")
+        .args(&["-u", systemd_service, "--no-pager", "-o", "json", "--reverse", "-n", &limit, "--since", since])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("Failed to search logs: {}", e))?;
+    let stdout = child.stdout.take().ok_or("Failed to capture journalctl output")?;
+
+    // Entries already read are the ones logged after a hit; the ones still to come precede it
+    let mut recent: std::collections::VecDeque<LogEntry> = std::collections::VecDeque::with_capacity(context + 1);
+    let mut matches: Vec<LogMatch> = Vec::new();
+    let mut truncated = false;
+    let mut read = 0;
+    for line in std::io::BufReader::new(stdout).lines() {
+        let Ok(line) = line else {
+            break;
+        };
+        read += 1;
+        let Some(entry) = parse_journal_entry(&line, units) else {
+            continue;
+        };
+
+        for pending in matches.iter_mut().rev().take_while(|pending| pending.before.len() < context) {
+            pending.before.insert(0, entry.clone());
         }
-        
-        // Get network stats for this PID
-        let proc_net_path = format!("/proc/{}/net/dev", pid);
-        if let Ok(net_content) = std::fs::read_to_string(&proc_net_path) {
-            for line in net_content.lines().skip(2) {
-                let fields: Vec<&str> = line.split_whitespace().collect();
-                if fields.len() >= 10 && !fields[0].starts_with("lo:") {
-                    network_in += fields[1].parse::<u64>().unwrap_or(0);
-                    network_out += fields[9].parse::<u64>().unwrap_or(0);
-                }
-            }
+        if matches.len() >= max_results && matches.last().is_none_or(|last| last.before.len() >= context) {
+            truncated = true;
+            break;
         }
-        
-        // Get disk I/O stats for this PID
-        let proc_io_path = format!("/proc/{}/io", pid);
-        if let Ok(io_content) = std::fs::read_to_string(&proc_io_path) {
-            for line in io_content.lines() {
-                if line.starts_with("read_bytes: ") {
-                    disk_read += line.replace("read_bytes: ", "").parse::<u64>().unwrap_or(0);
-                } else if line.starts_with("write_bytes: ") {
-                    disk_write += line.replace("write_bytes: ", "").parse::<u64>().unwrap_or(0);
-                }
-            }
+
+        if matches.len() < max_results && pattern.is_match(&entry.message) {
+            matches.push(LogMatch {
+                entry: entry.clone(),
+                before: Vec::new(),
+                after: recent.iter().cloned().collect(),
+            });
         }
+        // Front is the oldest read so far, which is the closest to the next entry
+        recent.push_front(entry);
+        recent.truncate(context);
     }
-    
-    // Get system memory total for percentage calculation
-    let mut memory_total = 0;
-    if let Ok(meminfo) = std::fs::read_to_string("/proc/meminfo") {
-        for line in meminfo.lines() {
-            if line.starts_with("MemTotal:") {
-                if let Some(kb_str) = line.split_whitespace().nth(1) {
-                    if let Ok(kb) = kb_str.parse::<u64>() {
-                        memory_total = kb * 1024; // Convert KB to bytes
-                        break;
-                    }
-                }
-            }
-        }
+    let _ = child.kill();
+    let _ = child.wait();
+    matches.reverse();
+
+    Ok((matches, truncated || read >= MAX_LOG_SEARCH_ENTRIES))
+}
+
+// Rejects relative paths, parent-directory hops and missing parent directories
+fn sanitize_export_path(path: &str, overwrite: bool) -> Result<std::path::PathBuf, String> {
+    let path = std::path::PathBuf::from(path.trim());
+
+    if !path.is_absolute() {
+        return Err(format!("Export path must be absolute: {}", path.display()));
     }
-    
-    Ok(SystemMetrics {
+    if path.components().any(|c| matches!(c, std::path::Component::ParentDir)) {
+        return Err(format!("Export path must not contain '..': {}", path.display()));
+    }
+    if path.is_dir() {
+        return Err(format!("Export path is a directory: {}", path.display()));
+    }
+    match path.parent() {
+        Some(parent) if parent.is_dir() => {}
+        _ => return Err(format!("Parent directory does not exist for {}", path.display())),
+    }
+    if path.exists() && !overwrite {
+        return Err(format!("{} already exists; pass overwrite to replace it", path.display()));
+    }
+
+    Ok(path)
+}
+
+#[tauri::command]
+pub async fn export_service_logs(
+    service_name: String,
+    path: String,
+    since: Option<String>,
+    until: Option<String>,
+    compress: Option<bool>,
+    overwrite: Option<bool>,
+) -> Result<LogExport, CommandError> {
+    validate_service_name(&service_name)?;
+
+    log::info!("💾 Exporting logs for {} to {}", service_name, path);
+    let systemd_service = find_service_name(&service_name)?;
+    require_journalctl()?;
+    let export_path = sanitize_export_path(&path, overwrite.unwrap_or(false))?;
+    let compress = compress.unwrap_or(false);
+
+    let mut args = vec!["-u".to_string(), systemd_service, "--no-pager".to_string(), "-o".to_string(), "short-iso".to_string()];
+    if let Some(since) = since {
+        args.push("--since".to_string());
+        args.push(since);
+    }
+    if let Some(until) = until {
+        args.push("--until".to_string());
+        args.push(until);
+    }
+
+    let output = Command::new("journalctl")
+        .args(&args)
+        .output()
+        .map_err(|e| format!("Failed to get logs: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("journalctl failed: {}", String::from_utf8_lossy(&output.stderr)).into());
+    }
+
+    let lines = output.stdout.iter().filter(|&&b| b == b'\n').count();
+    let file = std::fs::File::create(&export_path)
+        .map_err(|e| format!("Failed to create {}: {}", export_path.display(), e))?;
+
+    if compress {
+        let mut encoder = flate2::write::GzEncoder::new(file, flate2::Compression::default());
+        encoder
+            .write_all(&output.stdout)
+            .and_then(|_| encoder.finish().map(|_| ()))
+            .map_err(|e| format!("Failed to write {}: {}", export_path.display(), e))?;
+    } else {
+        let mut file = file;
+        file.write_all(&output.stdout)
+            .map_err(|e| format!("Failed to write {}: {}", export_path.display(), e))?;
+    }
+
+    let bytes = std::fs::metadata(&export_path).map(|m| m.len()).unwrap_or(0);
+    log::info!("✅ Exported {} lines ({} bytes) to {}", lines, bytes, export_path.display());
+
+    Ok(LogExport {
         service_name,
-        cpu_usage,
-        memory_usage,
-        memory_total,
-        network_in,
-        network_out,
-        disk_read,
-        disk_write,
-        process_count,
-        open_files,
-        timestamp: Utc::now(),
+        path: export_path.to_string_lossy().to_string(),
+        lines,
+        bytes,
+        compressed: compress,
     })
 }
 
 #[tauri::command]
-pub async fn execute_terminal_command(command: String, working_dir: Option<String>) -> Result<TerminalCommand, String> {
-    log::info!("💻 Executing terminal command: {}", command);
-    log::debug!("📁 Working directory: {:?}", working_dir);
+pub async fn get_system_logs(
+    service_name: String,
+    lines: Option<u32>,
+    boot_offset: Option<i32>,
+    since: Option<String>,
+) -> Result<SystemLogs, CommandError> {
+    validate_service_name(&service_name)?;
+
+    let systemd_service = find_service_name(&service_name)?;
+    require_journalctl()?;
+    let lines_count = lines.unwrap_or(50);
+    let lines_arg = format!("{}", lines_count);
+    let boot_arg = boot_offset.unwrap_or(0).to_string();
     
-    let start_time = std::time::Instant::now();
-    let timestamp = Utc::now();
+    // PIDs only mean something for the boot they were assigned in
+    let pids = if boot_offset.unwrap_or(0) == 0 {
+        get_unit_pids(&systemd_service).unwrap_or_default()
+    } else {
+        Vec::new()
+    };
+    let journal_matches = service_journal_matches(&systemd_service, &pids);
     
-    // Parse command into parts
-    let parts: Vec<&str> = command.trim().split_whitespace().collect();
-    if parts.is_empty() {
-        log::warn!("⚠️ Empty command received");
-        return Err("Empty command".to_string());
+    // Get system logs related to the service; a past boot has no "last hour", so only
+    // default the window when looking at the current boot
+    let mut system_args = vec!["--system".to_string(), "-n".to_string(), lines_arg.clone(), "--no-pager".to_string()];
+    if boot_offset.is_some() {
+        system_args.push("-b".to_string());
+        system_args.push(boot_arg.clone());
     }
+    match since {
+        Some(since) => {
+            system_args.push("--since".to_string());
+            system_args.push(since);
+        }
+        None if boot_offset.is_none() => {
+            system_args.push("--since".to_string());
+            system_args.push("1 hour ago".to_string());
+        }
+        None => {}
+    }
+    system_args.extend(journal_matches.iter().cloned());
+    let system_logs_output = Command::new("journalctl")
+        .args(&system_args)
+        .output()
+        .map_err(|e| format!("Failed to get system logs: {}", e))?;
     
-    log::debug!("🔧 Command parts: {:?}", parts);
+    // Kernel messages carry no unit fields, so they're matched on the service's PIDs and name
+    let kernel_entries = read_kernel_logs(KERNEL_DEBUG_PRIORITY, 1000, None, &boot_arg);
     
-    let mut cmd = Command::new(parts[0]);
-    if parts.len() > 1 {
-        cmd.args(&parts[1..]);
-    }
+    // Get boot logs
+    let mut boot_args = vec!["-b".to_string(), boot_arg.clone(), "-n".to_string(), "10".to_string(), "--no-pager".to_string()];
+    boot_args.extend(journal_matches.iter().cloned());
+    let boot_logs_output = Command::new("journalctl")
+        .args(&boot_args)
+        .output();
     
-    // Set working directory if provided
-    if let Some(dir) = working_dir {
-        log::debug!("📁 Setting working directory: {}", dir);
-        cmd.current_dir(dir);
-    }
+    let system_logs: Vec<String> = journal_lines(&system_logs_output.stdout);
     
-    // Execute command
-    log::debug!("🚀 Executing command with output capture");
-    let output = match cmd
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .output() {
-        Ok(output) => {
-            log::debug!("✅ Command executed successfully (exit code: {})", 
-                       output.status.code().unwrap_or(-1));
-            output
-        }
+    let kernel_name = systemd_service.trim_end_matches(".service").to_lowercase();
+    let pid_strings: Vec<String> = pids.iter().map(|pid| pid.to_string()).collect();
+    let kernel_logs: Vec<String> = match kernel_entries {
+        Ok(entries) => entries
+            .into_iter()
+            .filter(|entry| {
+                let lower_message = entry.message.to_lowercase();
+                lower_message.contains(&kernel_name) ||
+                lower_message
+                    .split(|c: char| !c.is_ascii_digit())
+                    .any(|number| pid_strings.iter().any(|pid| pid == number))
+            })
+            .take(20) // Limit kernel logs to avoid noise
+            .map(|entry| {
+                let timestamp = entry.timestamp.with_timezone(&chrono::Local).format("%b %d %H:%M:%S");
+                format!("{} kernel: {}", timestamp, entry.message)
+            })
+            .collect(),
         Err(e) => {
-            log::error!("❌ Failed to execute command: {}", e);
-            return Err(format!("Failed to execute command: {}", e));
+            log::debug!("⚠️ Failed to read kernel logs: {}", e);
+            Vec::new()
         }
     };
     
-    let duration = start_time.elapsed();
-    log::debug!("⏱️ Command execution time: {:?}", duration);
+    let boot_logs: Vec<String> = match boot_logs_output {
+        Ok(output) => journal_lines(&output.stdout),
+        Err(_) => Vec::new(),
+    };
     
-    // Combine stdout and stderr
-    let mut combined_output = String::from_utf8_lossy(&output.stdout).to_string();
-    if !output.stderr.is_empty() {
-        if !combined_output.is_empty() {
-            combined_output.push('\n');
-        }
-        combined_output.push_str(&String::from_utf8_lossy(&output.stderr));
+    Ok(SystemLogs {
+        service_name,
+        system_logs,
+        kernel_logs,
+        boot_logs,
+        timestamp: Utc::now(),
+    })
+}
+
+// Journal entries per second a service wrote over the last `window_secs` seconds
+pub fn log_entry_rate(service_name: &str, window_secs: u64) -> Result<f64, CommandError> {
+    let systemd_service = find_service_name(service_name)?;
+    require_journalctl()?;
+
+    // json prints one line per entry, and asking for a single field keeps the output small
+    let since = format!("{} seconds ago", window_secs);
+    let output = Command::new("journalctl")
+        .args(&["-u", &systemd_service, "--since", &since, "--no-pager", "-q", "-o", "json", "--output-fields=PRIORITY"])
+        .output()
+        .map_err(|e| format!("Failed to count log entries for {}: {}", service_name, e))?;
+
+    let entries = output.stdout.iter().filter(|&&b| b == b'\n').count();
+    Ok(entries as f64 / window_secs.max(1) as f64)
+}
+
+// syslog severities in priority order, as journalctl -p accepts them
+const SYSLOG_LEVELS: [&str; 8] = ["emerg", "alert", "crit", "err", "warning", "notice", "info", "debug"];
+const KERNEL_DEBUG_PRIORITY: u8 = 7;
+
+// Accepts a level name, a common alias such as "error" or "warn", or a number from 0 to 7
+fn parse_syslog_level(level: &str) -> Result<u8, String> {
+    let level = level.trim().to_lowercase();
+    let name = match level.as_str() {
+        "emergency" | "panic" => "emerg",
+        "critical" => "crit",
+        "error" => "err",
+        "warn" => "warning",
+        other => other,
+    };
+
+    if let Some(index) = SYSLOG_LEVELS.iter().position(|known| *known == name) {
+        return Ok(index as u8);
+    }
+    match level.parse::<u8>() {
+        Ok(priority) if priority <= KERNEL_DEBUG_PRIORITY => Ok(priority),
+        _ => Err(format!("Unknown log level '{}'; expected one of {}", level, SYSLOG_LEVELS.join(", "))),
+    }
+}
+
+// Reads the newest `lines` kernel messages at `max_priority` or more severe from one boot.
+// The pattern goes to journalctl --grep, or is applied here on builds without PCRE support.
+fn read_kernel_logs(max_priority: u8, lines: u32, grep: Option<&regex::Regex>, boot_arg: &str) -> Result<Vec<KernelLogEntry>, String> {
+    let priority_arg = max_priority.to_string();
+    let lines_arg = lines.to_string();
+    let mut args = vec!["-k", "-b", boot_arg, "-p", &priority_arg, "-n", &lines_arg, "--no-pager", "-o", "json"];
+    if let Some(pattern) = grep {
+        args.push("--grep");
+        args.push(pattern.as_str());
+    }
+
+    let mut output = Command::new("journalctl")
+        .args(&args)
+        .output()
+        .map_err(|e| format!("Failed to get kernel logs: {}", e))?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr).to_lowercase();
+    let grep_unsupported = grep.is_some()
+        && !output.status.success()
+        && (stderr.contains("pattern matching support") || stderr.contains("unrecognized option"));
+    if grep_unsupported {
+        log::debug!("ℹ️ journalctl lacks --grep, filtering kernel logs in Rust");
+        args.truncate(args.len() - 2);
+        output = Command::new("journalctl")
+            .args(&args)
+            .output()
+            .map_err(|e| format!("Failed to get kernel logs: {}", e))?;
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(parse_kernel_entry)
+        .filter(|entry| !grep_unsupported || grep.map_or(true, |pattern| pattern.is_match(&entry.message)))
+        .collect())
+}
+
+fn parse_kernel_entry(line: &str) -> Option<KernelLogEntry> {
+    let entry: serde_json::Value = serde_json::from_str(line).ok()?;
+    let micros: i64 = journal_field_string(&entry, "__REALTIME_TIMESTAMP")?.parse().ok()?;
+    let priority: u8 = journal_field_string(&entry, "PRIORITY")
+        .and_then(|p| p.parse().ok())
+        .filter(|p| *p <= KERNEL_DEBUG_PRIORITY)
+        .unwrap_or(6);
+
+    Some(KernelLogEntry {
+        timestamp: DateTime::from_timestamp_micros(micros)?,
+        level: SYSLOG_LEVELS[priority as usize].to_string(),
+        priority,
+        message: journal_field_string(&entry, "MESSAGE").unwrap_or_default(),
+    })
+}
+
+#[tauri::command]
+pub async fn get_kernel_logs(
+    level: Option<String>,
+    lines: Option<u32>,
+    grep: Option<String>,
+) -> Result<Vec<KernelLogEntry>, CommandError> {
+    require_journalctl()?;
+
+    let max_priority = parse_syslog_level(level.as_deref().unwrap_or("warning"))
+        .map_err(CommandError::InvalidInput)?;
+    let pattern = match grep.as_deref().map(str::trim).filter(|grep| !grep.is_empty()) {
+        Some(grep) => Some(regex::Regex::new(grep)
+            .map_err(|e| CommandError::InvalidInput(format!("Invalid search pattern '{}': {}", grep, e)))?),
+        None => None,
+    };
+
+    log::info!("🐧 Reading kernel logs at {} and above", SYSLOG_LEVELS[max_priority as usize]);
+    let entries = read_kernel_logs(max_priority, lines.unwrap_or(100), pattern.as_ref(), "0")?;
+    log::debug!("✅ Read {} kernel log entries", entries.len());
+    Ok(entries)
+}
+
+// journalctl match arguments selecting a unit's own entries, systemd's messages about it,
+// and anything logged by its processes; "+" separates alternatives, repeated _PID= are ORed
+fn service_journal_matches(systemd_service: &str, pids: &[u32]) -> Vec<String> {
+    let mut matches = vec![
+        format!("_SYSTEMD_UNIT={}", systemd_service),
+        "+".to_string(),
+        format!("UNIT={}", systemd_service),
+    ];
+    if !pids.is_empty() {
+        matches.push("+".to_string());
+        matches.extend(pids.iter().map(|pid| format!("_PID={}", pid)));
+    }
+    matches
+}
+
+// Drops journalctl's "-- No entries --" and boot separator lines
+fn journal_lines(stdout: &[u8]) -> Vec<String> {
+    String::from_utf8_lossy(stdout)
+        .lines()
+        .filter(|line| !line.starts_with("-- "))
+        .map(|line| line.to_string())
+        .collect()
+}
+
+// journalctl reports entry times in microseconds since the epoch
+fn journal_usec_to_rfc3339(usec: u64) -> String {
+    DateTime::<Utc>::from_timestamp((usec / 1_000_000) as i64, ((usec % 1_000_000) * 1000) as u32)
+        .map(|dt| dt.to_rfc3339())
+        .unwrap_or_default()
+}
+
+// Parses the text form of `journalctl --list-boots`, used when -o json isn't supported:
+//   old: " -1 8f2c...e1 Mon 2024-01-01 10:00:00 UTC—Mon 2024-01-01 12:00:00 UTC"
+//   new: "IDX BOOT ID ... " header, then columns separated by spaces only
+fn parse_list_boots(output: &str) -> Vec<BootEntry> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let index = fields.next()?.parse::<i32>().ok()?;
+            let boot_id = fields.next()?.to_string();
+            let rest: Vec<&str> = fields.collect();
+            let rest = rest.join(" ");
+
+            let (first_entry, last_entry) = match rest.split_once('—') {
+                Some((first, last)) => (first.trim().to_string(), last.trim().to_string()),
+                None => {
+                    // Both timestamps have the same number of words, so split down the middle
+                    let words: Vec<&str> = rest.split_whitespace().collect();
+                    let (first, last) = words.split_at(words.len() / 2);
+                    (first.join(" "), last.join(" "))
+                }
+            };
+
+            Some(BootEntry { index, boot_id, first_entry, last_entry })
+        })
+        .collect()
+}
+
+// There's no status history yet, so counts come from systemd's NRestarts, which covers
+// automatic restarts since the unit was loaded. `since` drops services whose last
+// restart is older than the window.
+#[tauri::command]
+pub async fn get_restart_leaderboard(
+    since: Option<String>,
+    limit: Option<usize>,
+) -> Result<Vec<RestartLeaderboardEntry>, CommandError> {
+    let since = since
+        .map(|since| {
+            DateTime::parse_from_rfc3339(&since)
+                .map(|since| since.with_timezone(&Utc))
+                .map_err(|e| format!("Invalid since timestamp '{}': {}", since, e))
+        })
+        .transpose()?;
+
+    require_systemctl()?;
+    let unit_states = list_service_unit_states();
+    let units: Vec<&str> = unit_states.keys().map(|unit| unit.as_str()).collect();
+    if units.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let output = Command::new("systemctl")
+        .args(&["show", "-p", "Id", "-p", "NRestarts", "-p", "ActiveEnterTimestampMonotonic"])
+        .args(&units)
+        .output()
+        .map_err(|e| format!("Failed to read restart counts: {}", e))?;
+
+    // Monotonic timestamps are relative to boot
+    let boot_time = std::fs::read_to_string("/proc/uptime")
+        .ok()
+        .and_then(|uptime| parse_proc_uptime(&uptime))
+        .map(|uptime| Utc::now() - chrono::Duration::milliseconds((uptime * 1000.0) as i64));
+
+    let mut entries: Vec<RestartLeaderboardEntry> = String::from_utf8_lossy(&output.stdout)
+        .split("\n\n")
+        .filter_map(|block| {
+            let properties: HashMap<&str, &str> = block
+                .lines()
+                .filter_map(|line| line.split_once('='))
+                .collect();
+            let unit = properties.get("Id").filter(|id| !id.is_empty())?;
+            let restart_count: u32 = properties.get("NRestarts")?.trim().parse().ok()?;
+            if restart_count == 0 {
+                return None;
+            }
+
+            let last_restart = properties
+                .get("ActiveEnterTimestampMonotonic")
+                .and_then(|usec| usec.trim().parse::<i64>().ok())
+                .filter(|usec| *usec > 0)
+                .zip(boot_time)
+                .map(|(usec, boot_time)| boot_time + chrono::Duration::microseconds(usec));
+
+            Some(RestartLeaderboardEntry {
+                service_name: unit.trim_end_matches(".service").to_string(),
+                restart_count,
+                last_restart,
+            })
+        })
+        .filter(|entry| match (since, entry.last_restart) {
+            (Some(since), Some(last_restart)) => last_restart >= since,
+            (Some(_), None) => false,
+            (None, _) => true,
+        })
+        .collect();
+
+    entries.sort_by(|a, b| {
+        b.restart_count
+            .cmp(&a.restart_count)
+            .then_with(|| b.last_restart.cmp(&a.last_restart))
+    });
+    entries.truncate(limit.unwrap_or(10));
+
+    Ok(entries)
+}
+
+#[tauri::command]
+pub async fn get_boots() -> Result<Vec<BootEntry>, CommandError> {
+    require_journalctl()?;
+
+    let output = Command::new("journalctl")
+        .args(&["--list-boots", "--no-pager", "-o", "json"])
+        .output()
+        .map_err(|e| format!("Failed to list boots: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("Failed to list boots: {}", String::from_utf8_lossy(&output.stderr)).into());
+    }
+
+    if let Ok(entries) = serde_json::from_slice::<Vec<serde_json::Value>>(&output.stdout) {
+        let boots = entries
+            .iter()
+            .filter_map(|entry| {
+                Some(BootEntry {
+                    index: entry.get("index")?.as_i64()? as i32,
+                    boot_id: entry.get("boot_id")?.as_str()?.to_string(),
+                    first_entry: journal_usec_to_rfc3339(entry.get("first_entry")?.as_u64()?),
+                    last_entry: journal_usec_to_rfc3339(entry.get("last_entry")?.as_u64()?),
+                })
+            })
+            .collect();
+        return Ok(boots);
+    }
+
+    // Older journalctl ignores -o for --list-boots and prints the text table
+    Ok(parse_list_boots(&String::from_utf8_lossy(&output.stdout)))
+}
+
+#[tauri::command]
+pub async fn start_multiple_services(
+    db: State<'_, Arc<Mutex<Database>>>,
+    service_names: Vec<String>,
+) -> Result<Vec<ServiceOperation>, CommandError> {
+    // start_service validates each name, so a bad one is reported without stopping the batch
+    let mut results = Vec::new();
+
+    for service_name in service_names {
+        let result = start_service(db.clone(), service_name, None).await;
+        match result {
+            Ok(operation) => results.push(operation),
+            Err(e) => results.push(ServiceOperation {
+                success: false,
+                message: e.to_string(),
+                service: None,
+            }),
+        }
+    }
+
+    Ok(results)
+}
+
+#[tauri::command]
+pub async fn stop_all_services(
+    db: State<'_, Arc<Mutex<Database>>>,
+) -> Result<Vec<ServiceOperation>, CommandError> {
+    let services = get_services(db.clone(), None, Some(true), None, None).await?;
+    let mut results = Vec::new();
+
+    for service in services {
+        if service.status == ServiceStatus::Running {
+            // Critical services come back as unconfirmed refusals rather than being stopped
+            let result = stop_service(db.clone(), service.name, None, None, None, None).await;
+            match result {
+                Ok(operation) => results.push(operation),
+                Err(e) => results.push(ServiceOperation {
+                    success: false,
+                    message: e.to_string(),
+                    service: None,
+                }),
+            }
+        }
+    }
+
+    Ok(results)
+}
+
+#[tauri::command]
+pub async fn enable_multiple_services(
+    service_names: Vec<String>,
+    password: Option<String>,
+) -> Result<Vec<ServiceOperation>, CommandError> {
+    set_multiple_services_enabled(service_names, password, true).await
+}
+
+#[tauri::command]
+pub async fn disable_multiple_services(
+    service_names: Vec<String>,
+    password: Option<String>,
+) -> Result<Vec<ServiceOperation>, CommandError> {
+    set_multiple_services_enabled(service_names, password, false).await
+}
+
+// Passes every unit to a single `systemctl enable|disable`, then reads back each
+// unit's file state so a partial failure is reported per service
+async fn set_multiple_services_enabled(
+    service_names: Vec<String>,
+    password: Option<String>,
+    enable: bool,
+) -> Result<Vec<ServiceOperation>, CommandError> {
+    let action = if enable { "enable" } else { "disable" };
+    let mut failures: HashMap<String, String> = HashMap::new();
+    let mut units: Vec<(String, String)> = Vec::new();
+
+    for service_name in &service_names {
+        match validate_service_name(service_name).and_then(|_| find_service_name(service_name)) {
+            Ok(unit) => units.push((service_name.clone(), unit)),
+            Err(e) => {
+                failures.insert(service_name.clone(), e.to_string());
+            }
+        }
+    }
+
+    if !units.is_empty() {
+        log::info!("🔧 Running systemctl {} for {} services", action, units.len());
+        let mut output = run_systemctl_for_units(action, &units, password.clone())?;
+
+        // systemctl aborts the whole batch on the first bad unit; drop the units
+        // its errors name and retry the rest once
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+            let before = units.len();
+            units.retain(|(service_name, unit)| match unit_error_line(&stderr, unit) {
+                Some(line) => {
+                    failures.insert(service_name.clone(), format!("Failed to {} {}: {}", action, service_name, line));
+                    false
+                }
+                None => true,
+            });
+
+            if units.len() < before && !units.is_empty() {
+                output = run_systemctl_for_units(action, &units, password.clone())?;
+            }
+        }
+
+        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+        let unit_names: Vec<&str> = units.iter().map(|(_, unit)| unit.as_str()).collect();
+        let file_states = unit_file_states(&unit_names);
+
+        for (service_name, unit) in &units {
+            let state = file_states.get(unit).map(|state| state.as_str()).unwrap_or("");
+            let applied = if enable {
+                matches!(state, "enabled" | "enabled-runtime" | "alias" | "indirect" | "generated")
+            } else {
+                state == "disabled"
+            };
+
+            if !applied {
+                let reason = unit_error_line(&stderr, unit)
+                    .map(|line| line.to_string())
+                    .unwrap_or_else(|| match state {
+                        "static" => "unit has no [Install] section".to_string(),
+                        "" => stderr.trim().to_string(),
+                        state => format!("unit file state is {}", state),
+                    });
+                failures.insert(service_name.clone(), format!("Failed to {} {}: {}", action, service_name, reason));
+            }
+        }
+    }
+
+    // Report in the order the services were requested
+    let mut results = Vec::new();
+    for service_name in service_names {
+        if let Some(message) = failures.remove(&service_name) {
+            results.push(ServiceOperation {
+                success: false,
+                message,
+                service: None,
+            });
+            continue;
+        }
+
+        let service = get_service_status_internal(&service_name).await.ok();
+        results.push(ServiceOperation {
+            success: true,
+            message: if enable {
+                format!("{} enabled for auto-start", service_name)
+            } else {
+                format!("{} disabled from auto-start", service_name)
+            },
+            service,
+        });
+    }
+
+    Ok(results)
+}
+
+fn run_systemctl_for_units(
+    action: &str,
+    units: &[(String, String)],
+    password: Option<String>,
+) -> Result<std::process::Output, CommandError> {
+    let mut args = vec!["systemctl", action];
+    args.extend(units.iter().map(|(_, unit)| unit.as_str()));
+    execute_sudo_command(&args, password, true)
+}
+
+// First systemctl error line that names the unit
+fn unit_error_line<'a>(stderr: &'a str, unit: &str) -> Option<&'a str> {
+    stderr
+        .lines()
+        .map(|line| line.trim())
+        .find(|line| line.starts_with("Failed") && line.contains(unit))
+}
+
+// UnitFileState of each unit, keyed by unit name
+fn unit_file_states(units: &[&str]) -> HashMap<String, String> {
+    if units.is_empty() {
+        return HashMap::new();
+    }
+
+    let output = match Command::new("systemctl")
+        .args(&["show", "-p", "Id", "-p", "UnitFileState"])
+        .args(units)
+        .output()
+    {
+        Ok(output) => output,
+        Err(e) => {
+            log::warn!("⚠️ Failed to read unit file states: {}", e);
+            return HashMap::new();
+        }
+    };
+
+    String::from_utf8_lossy(&output.stdout)
+        .split("\n\n")
+        .filter_map(|block| {
+            let properties: HashMap<&str, &str> = block
+                .lines()
+                .filter_map(|line| line.split_once('='))
+                .collect();
+            let unit = properties.get("Id").filter(|id| !id.is_empty())?;
+            let state = properties.get("UnitFileState").copied().unwrap_or("");
+            Some((unit.to_string(), state.to_string()))
+        })
+        .collect()
+}
+
+#[tauri::command]
+pub async fn check_auth_required(service_name: String) -> Result<AuthRequest, CommandError> {
+    validate_service_name(&service_name)?;
+
+    let systemd_service = find_service_name(&service_name)?;
+
+    // Try to check if we can manage this service without sudo
+    let user_check = Command::new("systemctl")
+        .args(&["--user", "is-active", &systemd_service])
+        .output();
+
+    match user_check {
+        Ok(output) if output.status.success() => {
+            Ok(AuthRequest {
+                requires_auth: false,
+                message: "Service can be managed without authentication".to_string(),
+            })
+        }
+        _ => {
+            Ok(AuthRequest {
+                requires_auth: true,
+                message: "Administrator privileges required to manage system services".to_string(),
+            })
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn start_service_with_auth(service_name: String, password: Option<String>) -> Result<ServiceOperation, CommandError> {
+    start_with_password(service_name, password, true).await
+}
+
+async fn start_with_password(service_name: String, password: Option<String>, undoable: bool) -> Result<ServiceOperation, CommandError> {
+    validate_service_name(&service_name)?;
+
+    if let Some(container) = DockerBackend::container_name(&service_name) {
+        return docker_operation(&service_name, container, "start", "started", undoable).await;
+    }
+
+    let systemd_service = find_service_name(&service_name)?;
+
+    // Check if already running
+    let previous_status = check_service_status(&systemd_service);
+    if let Ok(ServiceStatus::Running) = previous_status {
+        return Ok(ServiceOperation {
+            success: true,
+            message: format!("{} is already running", service_name),
+            service: None,
+        });
+    }
+
+    let args = ["systemctl", "start", &systemd_service];
+    let output = execute_sudo_command(&args, password, true)?;
+
+    if output.status.success() {
+        record_session_start(&service_name, false);
+        if undoable {
+            record_undo(&service_name, UndoableAction::Start, &status_label(&previous_status), false);
+        }
+        let service = get_service_status_internal(&service_name).await
+            .map_err(|e| format!("Failed to get updated status: {}", e))?;
+
+        Ok(ServiceOperation {
+            success: true,
+            message: format!("{} started successfully", service_name),
+            service: Some(service),
+        })
+    } else {
+        let error = String::from_utf8_lossy(&output.stderr);
+        Ok(ServiceOperation {
+            success: false,
+            message: format!("Failed to start {}: {}", service_name, error),
+            service: None,
+        })
+    }
+}
+
+#[tauri::command]
+pub async fn stop_service_with_auth(
+    db: State<'_, Arc<Mutex<Database>>>,
+    service_name: String,
+    password: Option<String>,
+    confirmed: Option<bool>,
+) -> Result<ServiceOperation, CommandError> {
+    stop_with_password(&db, service_name, password, true, confirmed).await
+}
+
+async fn stop_with_password(
+    db: &Arc<Mutex<Database>>,
+    service_name: String,
+    password: Option<String>,
+    undoable: bool,
+    confirmed: Option<bool>,
+) -> Result<ServiceOperation, CommandError> {
+    validate_service_name(&service_name)?;
+
+    if let Some(refused) = require_confirmation(db, &service_name, "stop", confirmed).await {
+        return Ok(refused);
+    }
+
+    if let Some(container) = DockerBackend::container_name(&service_name) {
+        return docker_operation(&service_name, container, "stop", "stopped", undoable).await;
+    }
+
+    let systemd_service = find_service_name(&service_name)?;
+    let previous_status = check_service_status(&systemd_service);
+
+    let args = ["systemctl", "stop", &systemd_service];
+    let output = execute_sudo_command(&args, password, true)?;
+
+    if output.status.success() {
+        if undoable && matches!(previous_status, Ok(ServiceStatus::Running | ServiceStatus::Activating | ServiceStatus::Reloading)) {
+            record_undo(&service_name, UndoableAction::Stop, &status_label(&previous_status), false);
+        }
+        let service = get_service_status_internal(&service_name).await
+            .map_err(|e| format!("Failed to get updated status: {}", e))?;
+
+        Ok(ServiceOperation {
+            success: true,
+            message: format!("{} stopped successfully", service_name),
+            service: Some(service),
+        })
+    } else {
+        let error = String::from_utf8_lossy(&output.stderr);
+        Ok(ServiceOperation {
+            success: false,
+            message: format!("Failed to stop {}: {}", service_name, error),
+            service: None,
+        })
+    }
+}
+
+// Whether stopping a session service needs `systemctl --user`
+struct SessionStart {
+    service_name: String,
+    user_unit: bool,
+}
+
+// Services this app started since launch, in start order; pre-existing ones never land here
+static SESSION_STARTS: OnceLock<std::sync::Mutex<Vec<SessionStart>>> = OnceLock::new();
+
+pub const STOP_ON_EXIT_SETTING: &str = "stop_session_services_on_exit";
+
+fn session_starts() -> &'static std::sync::Mutex<Vec<SessionStart>> {
+    SESSION_STARTS.get_or_init(|| std::sync::Mutex::new(Vec::new()))
+}
+
+fn record_session_start(service_name: &str, user_unit: bool) {
+    let mut starts = session_starts().lock().unwrap_or_else(|e| e.into_inner());
+    starts.retain(|start| start.service_name != service_name);
+    starts.push(SessionStart {
+        service_name: service_name.to_string(),
+        user_unit,
+    });
+}
+
+// Stops the services started this session, newest first. Ones that were stopped or
+// are no longer running since are skipped, so only what the app left behind is touched.
+pub fn stop_session_services_blocking(password: Option<String>) -> Vec<ServiceOperation> {
+    let starts: Vec<SessionStart> = std::mem::take(&mut *session_starts().lock().unwrap_or_else(|e| e.into_inner()));
+    let mut results = Vec::new();
+
+    for start in starts.into_iter().rev() {
+        let name = start.service_name;
+        let output = if let Some(container) = DockerBackend::container_name(&name) {
+            DockerBackend::container_action(container, "stop")
+        } else {
+            let systemd_service = match find_service_name(&name) {
+                Ok(unit) => unit,
+                Err(e) => {
+                    results.push(ServiceOperation { success: false, message: e.to_string(), service: None });
+                    continue;
+                }
+            };
+            let running = if start.user_unit {
+                Command::new("systemctl")
+                    .args(&["--user", "is-active", "--quiet", &systemd_service])
+                    .status()
+                    .map(|status| status.success())
+                    .unwrap_or(false)
+            } else {
+                matches!(check_service_status(&systemd_service), Ok(ServiceStatus::Running | ServiceStatus::Activating | ServiceStatus::Reloading))
+            };
+            if !running {
+                log::debug!("ℹ️ {} is no longer running, skipping", name);
+                continue;
+            }
+
+            log::info!("⏹️ Stopping session service {}", systemd_service);
+            if start.user_unit {
+                Command::new("systemctl")
+                    .args(&["--user", "stop", &systemd_service])
+                    .output()
+                    .map_err(|e| CommandError::Internal(format!("Failed to execute command: {}", e)))
+            } else {
+                execute_sudo_command(&["systemctl", "stop", &systemd_service], password.clone(), true)
+            }
+        };
+
+        results.push(match output {
+            Ok(output) if output.status.success() => ServiceOperation {
+                success: true,
+                message: format!("{} stopped successfully", name),
+                service: None,
+            },
+            Ok(output) => ServiceOperation {
+                success: false,
+                message: format!("Failed to stop {}: {}", name, String::from_utf8_lossy(&output.stderr).trim()),
+                service: None,
+            },
+            Err(e) => ServiceOperation {
+                success: false,
+                message: format!("Failed to stop {}: {}", name, e),
+                service: None,
+            },
+        });
+    }
+
+    results
+}
+
+#[tauri::command]
+pub async fn stop_session_services(password: Option<String>) -> Result<Vec<ServiceOperation>, CommandError> {
+    Ok(stop_session_services_blocking(password))
+}
+
+#[tauri::command]
+pub async fn get_session_services() -> Result<Vec<String>, CommandError> {
+    let starts = session_starts().lock().unwrap_or_else(|e| e.into_inner());
+    Ok(starts.iter().map(|start| start.service_name.clone()).collect())
+}
+
+#[tauri::command]
+pub async fn get_stop_on_exit(
+    db: State<'_, Arc<Mutex<Database>>>,
+) -> Result<bool, CommandError> {
+    let db = db.lock().await;
+    Ok(db.get_app_setting(STOP_ON_EXIT_SETTING)
+        .await
+        .map(|value| value.as_deref() == Some("true"))
+        .map_err(|e| format!("Failed to get stop_on_exit setting: {}", e))?)
+}
+
+#[tauri::command]
+pub async fn set_stop_on_exit(
+    db: State<'_, Arc<Mutex<Database>>>,
+    enabled: bool,
+) -> Result<(), CommandError> {
+    let db = db.lock().await;
+    Ok(db.set_app_setting(STOP_ON_EXIT_SETTING, if enabled { "true" } else { "false" })
+        .await
+        .map_err(|e| format!("Failed to set stop_on_exit setting: {}", e))?)
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum UndoableAction {
+    Start,
+    Stop,
+    Enable,
+    Disable,
+}
+
+impl UndoableAction {
+    fn inverse(self) -> Self {
+        match self {
+            UndoableAction::Start => UndoableAction::Stop,
+            UndoableAction::Stop => UndoableAction::Start,
+            UndoableAction::Enable => UndoableAction::Disable,
+            UndoableAction::Disable => UndoableAction::Enable,
+        }
+    }
+}
+
+// An operation that changed a service's state, with the state it replaced
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UndoEntry {
+    pub service_name: String,
+    pub action: UndoableAction,
+    pub previous_state: String,
+    // Started through `systemctl --user`, so the inverse runs in the user manager too
+    pub user_unit: bool,
+    pub timestamp: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UndoResult {
+    pub undone: UndoEntry,
+    pub operation: ServiceOperation,
+}
+
+// Oldest entries fall off once the stack is this deep
+const UNDO_STACK_DEPTH: usize = 20;
+
+// Reversible operations performed this session, newest last
+static UNDO_STACK: OnceLock<std::sync::Mutex<Vec<UndoEntry>>> = OnceLock::new();
+
+fn undo_stack() -> &'static std::sync::Mutex<Vec<UndoEntry>> {
+    UNDO_STACK.get_or_init(|| std::sync::Mutex::new(Vec::new()))
+}
+
+fn status_label(status: &Result<ServiceStatus>) -> String {
+    match status {
+        Ok(status) => format!("{:?}", status),
+        Err(_) => "Unknown".to_string(),
+    }
+}
+
+// Only called once an operation actually changed the state, so undoing restores previous_state
+fn record_undo(service_name: &str, action: UndoableAction, previous_state: &str, user_unit: bool) {
+    let mut stack = undo_stack().lock().unwrap_or_else(|e| e.into_inner());
+    if stack.len() == UNDO_STACK_DEPTH {
+        stack.remove(0);
+    }
+    stack.push(UndoEntry {
+        service_name: service_name.to_string(),
+        action,
+        previous_state: previous_state.to_string(),
+        user_unit,
+        timestamp: Utc::now(),
+    });
+}
+
+#[tauri::command]
+pub async fn get_undo_stack() -> Result<Vec<UndoEntry>, CommandError> {
+    let stack = undo_stack().lock().unwrap_or_else(|e| e.into_inner());
+    Ok(stack.iter().rev().cloned().collect())
+}
+
+// Performs the inverse of the newest recorded operation. The entry is only popped once the
+// inverse succeeds, so a refused password can be retried.
+#[tauri::command]
+pub async fn undo_last_operation(
+    db: State<'_, Arc<Mutex<Database>>>,
+    password: Option<String>,
+    confirmed: Option<bool>,
+) -> Result<UndoResult, CommandError> {
+    let Some(entry) = undo_stack().lock().unwrap_or_else(|e| e.into_inner()).last().cloned() else {
+        return Err(CommandError::NotFound("There is no operation to undo".to_string()));
+    };
+
+    let inverse = entry.action.inverse();
+    log::info!("↩️ Undoing {:?} of {} with {:?}", entry.action, entry.service_name, inverse);
+
+    // The inverse itself isn't recorded, so undo can't undo its own undo
+    let service_name = entry.service_name.clone();
+    let operation = if entry.user_unit {
+        if inverse == UndoableAction::Stop {
+            if let Some(refused) = require_confirmation(&db, &service_name, "stop", confirmed).await {
+                return Ok(UndoResult { undone: entry, operation: refused });
+            }
+        }
+        user_unit_operation(service_name, inverse).await?
+    } else {
+        match inverse {
+            UndoableAction::Start => start_with_password(service_name, password, false).await?,
+            UndoableAction::Stop => stop_with_password(&db, service_name, password, false, confirmed).await?,
+            UndoableAction::Enable => set_service_enabled(service_name, password, true, false).await?,
+            UndoableAction::Disable => set_service_enabled(service_name, password, false, false).await?,
+        }
+    };
+
+    if operation.success {
+        let mut stack = undo_stack().lock().unwrap_or_else(|e| e.into_inner());
+        // Other operations may have been recorded while the inverse ran
+        if let Some(index) = stack.iter().rposition(|e| e.service_name == entry.service_name && e.timestamp == entry.timestamp) {
+            stack.remove(index);
+        }
+    }
+
+    Ok(UndoResult {
+        undone: entry,
+        operation,
+    })
+}
+
+// Runs an undo inverse against the user manager, which needs no password
+async fn user_unit_operation(service_name: String, action: UndoableAction) -> Result<ServiceOperation, CommandError> {
+    let systemd_service = find_service_name(&service_name)?;
+    let (verb, done) = match action {
+        UndoableAction::Start => ("start", "started"),
+        UndoableAction::Stop => ("stop", "stopped"),
+        UndoableAction::Enable => ("enable", "enabled"),
+        UndoableAction::Disable => ("disable", "disabled"),
+    };
+
+    let output = Command::new("systemctl")
+        .args(&["--user", verb, &systemd_service])
+        .output()
+        .map_err(|e| format!("Failed to execute command: {}", e))?;
+
+    if output.status.success() {
+        Ok(ServiceOperation {
+            success: true,
+            message: format!("{} {} successfully", service_name, done),
+            service: get_service_status_internal(&service_name).await.ok(),
+        })
+    } else {
+        let error = String::from_utf8_lossy(&output.stderr);
+        Ok(ServiceOperation {
+            success: false,
+            message: format!("Failed to {} {}: {}", verb, service_name, error),
+            service: None,
+        })
+    }
+}
+
+// Serializes ensure_service_state so two callers can't both see a stale state and act on it
+static RECONCILE_LOCK: Mutex<()> = Mutex::const_new(());
+
+#[tauri::command]
+pub async fn ensure_service_state(
+    db: State<'_, Arc<Mutex<Database>>>,
+    service_name: String,
+    desired_running: Option<bool>,
+    desired_enabled: Option<bool>,
+    password: Option<String>,
+    confirmed: Option<bool>,
+) -> Result<ServiceReconcile, CommandError> {
+    validate_service_name(&service_name)?;
+
+    if DockerBackend::container_name(&service_name).is_some() {
+        return Err(CommandError::InvalidInput("Containers are not managed by systemd; use start_service or stop_service".to_string()));
+    }
+
+    let systemd_service = find_service_name(&service_name)?;
+    let _guard = RECONCILE_LOCK.lock().await;
+
+    let status = check_service_status(&systemd_service)
+        .map_err(|e| format!("Failed to check status: {}", e))?;
+    // A unit that is already on its way up counts as running; starting it again would just wait
+    let running = matches!(status, ServiceStatus::Running | ServiceStatus::Activating | ServiceStatus::Reloading);
+    let enabled = check_service_enabled(&systemd_service);
+
+    // Enable before starting and stop before disabling, so a failure leaves the closer state
+    let mut actions_needed: Vec<&str> = Vec::new();
+    if desired_enabled == Some(true) && !enabled {
+        actions_needed.push("enable");
+    }
+    match desired_running {
+        Some(true) if !running => actions_needed.push("start"),
+        Some(false) if running => actions_needed.push("stop"),
+        _ => {}
+    }
+    if desired_enabled == Some(false) && enabled {
+        actions_needed.push("disable");
+    }
+
+    if actions_needed.is_empty() {
+        log::debug!("✅ {} is already in the desired state", systemd_service);
+        let service = get_service_status_internal(&service_name).await.ok();
+        return Ok(ServiceReconcile {
+            service_name: service_name.clone(),
+            success: true,
+            actions: Vec::new(),
+            message: format!("{} is already in the desired state", service_name),
+            service,
+        });
+    }
+
+    if actions_needed.contains(&"stop") {
+        if let Some(refused) = require_confirmation(&db, &service_name, "stop", confirmed).await {
+            return Ok(ServiceReconcile {
+                service_name,
+                success: false,
+                actions: Vec::new(),
+                message: refused.message,
+                service: None,
+            });
+        }
+    }
+
+    log::info!("🔧 Reconciling {}: {}", systemd_service, actions_needed.join(", "));
+    let mut actions = Vec::new();
+    for action in actions_needed {
+        let output = execute_sudo_command(&["systemctl", action, &systemd_service], password.clone(), true)?;
+        if !output.status.success() {
+            let error = String::from_utf8_lossy(&output.stderr);
+            log::warn!("⚠️ systemctl {} {} failed: {}", action, systemd_service, error.trim());
+            let service = get_service_status_internal(&service_name).await.ok();
+            return Ok(ServiceReconcile {
+                service_name: service_name.clone(),
+                success: false,
+                actions,
+                message: format!("Failed to {} {}: {}", action, service_name, error.trim()),
+                service,
+            });
+        }
+        if action == "start" {
+            record_session_start(&service_name, false);
+        }
+        actions.push(action.to_string());
+    }
+
+    let service = get_service_status_internal(&service_name).await
+        .map_err(|e| format!("Failed to get updated status: {}", e))?;
+
+    Ok(ServiceReconcile {
+        message: format!("{}: {}", service_name, actions.join(", ")),
+        service_name,
+        success: true,
+        actions,
+        service: Some(service),
+    })
+}
+
+// PIDs that systemd attributes to the unit: its main PID plus everything in its cgroup
+fn get_unit_pids(systemd_service: &str) -> Result<Vec<u32>, String> {
+    let mut all_pids = Vec::new();
+    
+    // Get main PID
+    let pid_output = Command::new("systemctl")
+        .args(&["show", "--property=MainPID", systemd_service])
+        .output()
+        .map_err(|e| format!("Failed to get service PID: {}", e))?;
+    
+    if let Ok(pid_str) = String::from_utf8(pid_output.stdout) {
+        for line in pid_str.lines() {
+            if line.starts_with("MainPID=") {
+                if let Ok(pid) = line.replace("MainPID=", "").parse::<u32>() {
+                    if pid > 0 {
+                        all_pids.push(pid);
+                    }
+                }
+            }
+        }
+    }
+    
+    // Get cgroup PIDs for more comprehensive tracking
+    if let Ok(cgroup_output) = Command::new("systemctl")
+        .args(&["show", "--property=ControlGroup", systemd_service])
+        .output()
+    {
+        if let Ok(cgroup_str) = String::from_utf8(cgroup_output.stdout) {
+            for line in cgroup_str.lines() {
+                if line.starts_with("ControlGroup=") {
+                    let cgroup_path = line.replace("ControlGroup=", "");
+                    if !cgroup_path.is_empty() && cgroup_path != "/" {
+                        // Try to get PIDs from cgroup
+                        let cgroup_procs_path = format!("/sys/fs/cgroup{}/cgroup.procs", cgroup_path);
+                        if let Ok(procs_content) = std::fs::read_to_string(&cgroup_procs_path) {
+                            for pid_line in procs_content.lines() {
+                                if let Ok(pid) = pid_line.trim().parse::<u32>() {
+                                    if pid > 0 && !all_pids.contains(&pid) {
+                                        all_pids.push(pid);
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+    
+    Ok(all_pids)
+}
+
+// Unit PIDs, falling back to a command-line match when systemd reports none
+fn discover_service_pids(systemd_service: &str, service_name: &str) -> Result<Vec<u32>, String> {
+    let mut all_pids = get_unit_pids(systemd_service)?;
+    
+    // If no PIDs found, try alternative method
+    if all_pids.is_empty() {
+        if let Ok(pgrep_output) = Command::new("pgrep")
+            .args(&["-f", service_name])
+            .output()
+        {
+            if let Ok(pgrep_str) = String::from_utf8(pgrep_output.stdout) {
+                for pid_line in pgrep_str.lines() {
+                    if let Ok(pid) = pid_line.trim().parse::<u32>() {
+                        if pid > 0 {
+                            all_pids.push(pid);
+                        }
+                    }
+                }
+            }
+        }
+    }
+    
+    Ok(all_pids)
+}
+
+const MAX_PROCESS_COMMAND_LEN: usize = 200;
+
+fn truncate_command(command: &str) -> String {
+    if command.chars().count() <= MAX_PROCESS_COMMAND_LEN {
+        command.to_string()
+    } else {
+        let truncated: String = command.chars().take(MAX_PROCESS_COMMAND_LEN).collect();
+        format!("{}…", truncated)
+    }
+}
+
+// Builds the process forest for the given PIDs from a single ps call
+fn build_process_tree(pids: &[u32]) -> Result<Vec<ProcessNode>, String> {
+    if pids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let pid_list = pids.iter().map(|p| p.to_string()).collect::<Vec<_>>().join(",");
+    let output = Command::new("ps")
+        .args(&["-o", "pid=,ppid=,pcpu=,rss=,args=", "-p", &pid_list])
+        .output()
+        .map_err(|e| format!("Failed to list processes: {}", e))?;
+
+    let mut nodes: Vec<ProcessNode> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let pid = fields.next()?.parse().ok()?;
+            let ppid = fields.next()?.parse().ok()?;
+            let cpu = fields.next()?.parse().unwrap_or(0.0);
+            let rss = fields.next()?.parse::<u64>().unwrap_or(0) * 1024; // RSS is in KB
+            let command = fields.collect::<Vec<_>>().join(" ");
+            Some(ProcessNode {
+                pid,
+                ppid,
+                command: truncate_command(&command),
+                cpu,
+                rss,
+                children: Vec::new(),
+            })
+        })
+        .collect();
+
+    // Processes whose parent isn't part of the service become roots; the rest hang below them
+    nodes.sort_by_key(|n| n.pid);
+    fn attach(node: &mut ProcessNode, all: &mut Vec<ProcessNode>) {
+        let mut i = 0;
+        while i < all.len() {
+            if all[i].ppid == node.pid {
+                let mut child = all.remove(i);
+                attach(&mut child, all);
+                node.children.push(child);
+            } else {
+                i += 1;
+            }
+        }
+    }
+
+    let known: std::collections::HashSet<u32> = nodes.iter().map(|n| n.pid).collect();
+    let (mut roots, mut rest): (Vec<_>, Vec<_>) = nodes.into_iter().partition(|n| !known.contains(&n.ppid));
+    for root in roots.iter_mut() {
+        attach(root, &mut rest);
+    }
+    Ok(roots)
+}
+
+#[tauri::command]
+pub async fn get_service_processes(service_name: String) -> Result<Vec<ProcessNode>, CommandError> {
+    validate_service_name(&service_name)?;
+
+    log::info!("🌳 Building process tree for service: {}", service_name);
+    let systemd_service = find_service_name(&service_name)?;
+    let pids = discover_service_pids(&systemd_service, &service_name)?;
+    Ok(build_process_tree(&pids)?)
+}
+
+// Only the two signals that mean "stop" are offered
+fn parse_kill_signal(signal: Option<&str>) -> Result<&'static str, CommandError> {
+    match signal.map(|s| s.trim().to_uppercase()) {
+        None => Ok("TERM"),
+        Some(s) if s == "TERM" || s == "SIGTERM" => Ok("TERM"),
+        Some(s) if s == "KILL" || s == "SIGKILL" => Ok("KILL"),
+        Some(other) => Err(CommandError::InvalidInput(format!("Unsupported signal '{}'; use SIGTERM or SIGKILL", other))),
+    }
+}
+
+// Tries an unprivileged kill first and only falls back to sudo when that is refused
+fn send_signal(pid: u32, signal: &str, password: Option<String>) -> Result<(), String> {
+    let pid_arg = pid.to_string();
+
+    let direct = Command::new("kill")
+        .args(&["-s", signal, &pid_arg])
+        .output()
+        .map_err(|e| format!("Failed to execute kill: {}", e))?;
+
+    if !direct.status.success() {
+        log::debug!("⚠️ Unprivileged kill failed, retrying with sudo");
+        let output = execute_sudo_command(&["kill", "-s", signal, &pid_arg], password, true)?;
+        if !output.status.success() {
+            return Err(format!("Failed to kill PID {}: {}", pid, String::from_utf8_lossy(&output.stderr)));
+        }
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn kill_service_process(
+    service_name: String,
+    pid: u32,
+    signal: Option<String>,
+    password: Option<String>,
+) -> Result<Vec<ProcessNode>, CommandError> {
+    validate_service_name(&service_name)?;
+
+    let systemd_service = find_service_name(&service_name)?;
+    let signal = parse_kill_signal(signal.as_deref())?;
+
+    // Only PIDs systemd attributes to the unit may be signalled
+    let unit_pids = get_unit_pids(&systemd_service)?;
+    if !unit_pids.contains(&pid) {
+        log::warn!("⚠️ Refusing to kill PID {}: not part of {}", pid, systemd_service);
+        return Err(CommandError::InvalidInput(format!("PID {} does not belong to {}", pid, service_name)));
+    }
+
+    log::info!("🔪 Sending SIG{} to PID {} of service {}", signal, pid, service_name);
+    send_signal(pid, signal, password)?;
+
+    // Give the process a moment to exit before reporting the new tree
+    tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+    let pids = discover_service_pids(&systemd_service, &service_name)?;
+    Ok(build_process_tree(&pids)?)
+}
+
+// PF_KTHREAD in the flags field of /proc/<pid>/stat
+const PF_KTHREAD: u64 = 0x0020_0000;
+const PROCESS_EXIT_WAIT_MS: u64 = 3000;
+
+// Name, command line and state of any process, for signalling ones outside a known unit
+fn read_process_info(pid: u32) -> Result<ProcessInfo, CommandError> {
+    let stat = std::fs::read_to_string(format!("/proc/{}/stat", pid))
+        .map_err(|_| CommandError::NotFound(format!("Process {} not found", pid)))?;
+    let (Some(open), Some(close)) = (stat.find('('), stat.rfind(')')) else {
+        return Err(format!("Unreadable stat for process {}", pid).into());
+    };
+    let name = stat[open + 1..close].to_string();
+    // The fields after the command name start at "state"; flags is the 7th of them and
+    // starttime the 20th
+    let fields: Vec<&str> = stat[close + 1..].split_whitespace().collect();
+    let flags: u64 = fields.get(6).and_then(|flags| flags.parse().ok()).unwrap_or(0);
+    let start_time: u64 = fields
+        .get(19)
+        .and_then(|start_time| start_time.parse().ok())
+        .ok_or_else(|| format!("Unreadable stat for process {}", pid))?;
+
+    let command_line = std::fs::read(format!("/proc/{}/cmdline", pid))
+        .map(|raw| {
+            raw.split(|byte| *byte == 0)
+                .filter(|arg| !arg.is_empty())
+                .map(|arg| String::from_utf8_lossy(arg).into_owned())
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .unwrap_or_default();
+
+    Ok(ProcessInfo {
+        pid,
+        name,
+        command_line,
+        state: fields.first().map(|state| state.to_string()).unwrap_or_default(),
+        kernel_thread: flags & PF_KTHREAD != 0,
+        start_time,
+    })
+}
+
+// Gone, a zombie or a new process under the same PID all mean this one has stopped running
+fn process_terminated(pid: u32, start_time: u64) -> bool {
+    read_process_info(pid).map(|info| info.state == "Z" || info.start_time != start_time).unwrap_or(true)
+}
+
+// Lets the UI show what a PID is before stop_process_by_pid signals it; its start_time must
+// be passed back so the signal can't reach a process that reused the PID
+#[tauri::command]
+pub async fn get_process_info(pid: u32) -> Result<ProcessInfo, CommandError> {
+    read_process_info(pid)
+}
+
+#[tauri::command]
+pub async fn stop_process_by_pid(
+    pid: u32,
+    start_time: u64,
+    signal: Option<String>,
+    password: Option<String>,
+) -> Result<ProcessStopResult, CommandError> {
+    let signal = parse_kill_signal(signal.as_deref())?;
+
+    if pid <= 1 {
+        return Err(CommandError::InvalidInput(format!("Refusing to signal PID {}", pid)));
+    }
+    let info = read_process_info(pid)?;
+    if info.start_time != start_time {
+        return Err(CommandError::NotFound(format!(
+            "PID {} now belongs to a different process ({}); look it up again", pid, info.name)));
+    }
+    if info.kernel_thread {
+        return Err(CommandError::InvalidInput(format!("PID {} ({}) is a kernel thread", pid, info.name)));
+    }
+
+    log::warn!("🔪 Sending SIG{} to PID {} ({})", signal, pid, info.command_line);
+    send_signal(pid, signal, password)?;
+
+    // Poll briefly; a process handling SIGTERM may take a moment to exit
+    let deadline = std::time::Instant::now() + std::time::Duration::from_millis(PROCESS_EXIT_WAIT_MS);
+    let mut terminated = process_terminated(pid, start_time);
+    while !terminated && std::time::Instant::now() < deadline {
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        terminated = process_terminated(pid, start_time);
+    }
+
+    if terminated {
+        log::info!("✅ PID {} ({}) terminated", pid, info.name);
+    } else {
+        log::warn!("⚠️ PID {} ({}) still running after SIG{}", pid, info.name, signal);
+    }
+
+    Ok(ProcessStopResult {
+        pid,
+        name: info.name,
+        command_line: info.command_line,
+        signal: format!("SIG{}", signal),
+        terminated,
+    })
+}
+
+#[tauri::command]
+pub async fn get_service_metrics(
+    requests: State<'_, PendingRequests>,
+    cpu_samples: State<'_, CpuSamples>,
+    service_name: String,
+    request_id: Option<String>,
+) -> Result<SystemMetrics, CommandError> {
+    let request = RegisteredRequest::new(&requests, request_id);
+    service_metrics(service_name, &cpu_samples, &request.token).await
+}
+
+pub async fn service_metrics(
+    service_name: String,
+    cpu_samples: &CpuSamples,
+    cancel: &CancelToken,
+) -> Result<SystemMetrics, CommandError> {
+    validate_service_name(&service_name)?;
+
+    if DockerBackend::container_name(&service_name).is_some() {
+        return get_container_metrics(service_name).await;
+    }
+
+    let systemd_service = find_service_name(&service_name)?;
+    
+    // Get CPU and memory usage for the service and all its child processes
+    let mut memory_usage = 0;
+    let mut process_count = 0;
+    let mut network_in = 0;
+    let mut network_out = 0;
+    let mut disk_read = 0;
+    let mut disk_write = 0;
+    
+    // Get all PIDs for the service (including child processes)
+    let all_pids = discover_service_pids(&systemd_service, &service_name)?;
+    cancel.check()?;
+    
+    // One ps call covers every PID instead of one spawn per process
+    if !all_pids.is_empty() {
+        let pid_list = all_pids.iter().map(|pid| pid.to_string()).collect::<Vec<_>>().join(",");
+        if let Ok(ps_output) = Command::new("ps")
+            .args(&["-o", "pid=,rss=,nlwp=", "-p", &pid_list])
+            .output()
+        {
+            for line in String::from_utf8_lossy(&ps_output.stdout).lines() {
+                let fields: Vec<&str> = line.split_whitespace().collect();
+                if fields.len() >= 3 {
+                    memory_usage += fields[1].parse::<u64>().unwrap_or(0) * 1024; // RSS is in KB
+                    process_count += fields[2].parse::<u32>().unwrap_or(0);
+                }
+            }
+        }
+    }
+    
+    // ps pcpu is averaged over each process's lifetime, so CPU is a rate between calls instead
+    let sample_cpu = || {
+        service_cpu_usage_usec(&systemd_service, &all_pids)
+            .map(|usage_usec| cpu_percent_since_last_sample(cpu_samples, &service_name, usage_usec))
+    };
+    let cpu_usage = match sample_cpu() {
+        Some(Some(percent)) => percent,
+        // The first reading is only a baseline, so take a second one shortly after
+        Some(None) => {
+            tokio::time::sleep(CPU_BASELINE_WINDOW).await;
+            sample_cpu().flatten().unwrap_or(0.0)
+        }
+        None => 0.0,
+    };
+    
+    let (open_files, open_files_sampled) = count_open_files(&all_pids, cancel);
+    cancel.check()?;
+    
+    // The rest comes straight from /proc, which is far cheaper than spawning tools
+    for pid in &all_pids {
+        cancel.check()?;
+
+        // Get network stats for this PID
+        let proc_net_path = format!("/proc/{}/net/dev", pid);
+        if let Ok(net_content) = std::fs::read_to_string(&proc_net_path) {
+            for line in net_content.lines().skip(2) {
+                let fields: Vec<&str> = line.split_whitespace().collect();
+                if fields.len() >= 10 && !fields[0].starts_with("lo:") {
+                    network_in += fields[1].parse::<u64>().unwrap_or(0);
+                    network_out += fields[9].parse::<u64>().unwrap_or(0);
+                }
+            }
+        }
+        
+        // Get disk I/O stats for this PID
+        let proc_io_path = format!("/proc/{}/io", pid);
+        if let Ok(io_content) = std::fs::read_to_string(&proc_io_path) {
+            for line in io_content.lines() {
+                if line.starts_with("read_bytes: ") {
+                    disk_read += line.replace("read_bytes: ", "").parse::<u64>().unwrap_or(0);
+                } else if line.starts_with("write_bytes: ") {
+                    disk_write += line.replace("write_bytes: ", "").parse::<u64>().unwrap_or(0);
+                }
+            }
+        }
+    }
+    
+    // Get system memory total for percentage calculation
+    let memory_total = read_meminfo_bytes("MemTotal").unwrap_or(0);
+    
+    Ok(SystemMetrics {
+        service_name,
+        cpu_usage,
+        memory_usage,
+        memory_total,
+        network_in,
+        network_out,
+        disk_read,
+        disk_write,
+        process_count,
+        open_files,
+        open_files_sampled,
+        timestamp: Utc::now(),
+        display: SystemMetricsDisplay::default(),
+    }
+    .with_display())
+}
+
+// Services with more processes than this have their descriptors sampled
+const MAX_FD_SCAN_PIDS: usize = 256;
+const FD_SCAN_BUDGET: std::time::Duration = std::time::Duration::from_millis(200);
+
+// Counts entries in /proc/<pid>/fd. Large services are sampled at an even stride, and the
+// scan stops once its time budget is spent; either way the total is extrapolated from the
+// processes actually read and the second value is true.
+fn count_open_files(pids: &[u32], cancel: &CancelToken) -> (u32, bool) {
+    if pids.is_empty() {
+        return (0, false);
+    }
+
+    let stride = pids.len().div_ceil(MAX_FD_SCAN_PIDS);
+    let started = std::time::Instant::now();
+    let mut scanned = 0usize;
+    let mut open_files = 0u64;
+    let mut timed_out = false;
+
+    for pid in pids.iter().step_by(stride) {
+        if cancel.is_cancelled() {
+            break;
+        }
+        if started.elapsed() >= FD_SCAN_BUDGET {
+            timed_out = true;
+            break;
+        }
+        // Processes we can't inspect still count towards the sample
+        if let Ok(fds) = std::fs::read_dir(format!("/proc/{}/fd", pid)) {
+            open_files += fds.count() as u64;
+        }
+        scanned += 1;
+    }
+
+    let sampled = stride > 1 || timed_out;
+    if sampled && scanned > 0 {
+        open_files = open_files * pids.len() as u64 / scanned as u64;
+    }
+    (open_files.min(u32::MAX as u64) as u32, sampled)
+}
+
+// Previous cumulative CPU reading per service
+pub struct CpuSample {
+    usage_usec: u64,
+    taken_at: std::time::Instant,
+}
+
+// Last CPU reading per service, managed as app state. A std Mutex because the lock is never
+// held across an await.
+pub type CpuSamples = Arc<std::sync::Mutex<HashMap<String, CpuSample>>>;
+
+// Readings older than this are dropped: the service is no longer being watched, and a rate
+// averaged over that long would say little about now
+const CPU_SAMPLE_TTL: std::time::Duration = std::time::Duration::from_secs(300);
+
+// Without an earlier reading, CPU is measured over this window instead of reported as 0
+const CPU_BASELINE_WINDOW: std::time::Duration = std::time::Duration::from_millis(250);
+
+// Share of the whole machine used since the previous reading for this service, 0-100.
+// None when there's no usable previous reading; this one becomes the baseline either way.
+fn cpu_percent_since_last_sample(samples: &CpuSamples, service_name: &str, usage_usec: u64) -> Option<f32> {
+    let now = std::time::Instant::now();
+    let mut samples = samples.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    samples.retain(|_, sample| now.duration_since(sample.taken_at) < CPU_SAMPLE_TTL);
+
+    let previous = samples.insert(service_name.to_string(), CpuSample { usage_usec, taken_at: now })?;
+
+    let wall_usec = now.duration_since(previous.taken_at).as_micros() as f64;
+    // The counter drops when a process exits and the service falls back to /proc sums
+    if wall_usec <= 0.0 || usage_usec < previous.usage_usec {
+        return None;
+    }
+
+    let cores = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1) as f64;
+    let percent = (usage_usec - previous.usage_usec) as f64 / (wall_usec * cores) * 100.0;
+    Some(percent.min(100.0) as f32)
+}
+
+// Cumulative CPU time of the service: the cgroup counter when cgroup v2 is mounted,
+// otherwise the sum over its current processes
+fn service_cpu_usage_usec(systemd_service: &str, pids: &[u32]) -> Option<u64> {
+    let cgroup_usage = get_unit_property(systemd_service, "ControlGroup").and_then(|cgroup| {
+        let cpu_stat = std::fs::read_to_string(format!("/sys/fs/cgroup{}/cpu.stat", cgroup)).ok()?;
+        cpu_stat
+            .lines()
+            .find_map(|line| line.strip_prefix("usage_usec "))
+            .and_then(|usec| usec.trim().parse().ok())
+    });
+    if cgroup_usage.is_some() {
+        return cgroup_usage;
+    }
+
+    if pids.is_empty() {
+        return None;
+    }
+    let ticks_per_sec = clock_ticks_per_second();
+    let ticks: u64 = pids.iter().filter_map(|pid| process_cpu_ticks(*pid)).sum();
+    Some(ticks * 1_000_000 / ticks_per_sec)
+}
+
+// utime + stime from /proc/<pid>/stat; the fields after the parenthesised command name
+// start at "state", so utime and stime are the 12th and 13th of them
+fn process_cpu_ticks(pid: u32) -> Option<u64> {
+    let stat = std::fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+    let after_comm = &stat[stat.rfind(')')? + 1..];
+    let fields: Vec<&str> = after_comm.split_whitespace().collect();
+    let utime: u64 = fields.get(11)?.parse().ok()?;
+    let stime: u64 = fields.get(12)?.parse().ok()?;
+    Some(utime + stime)
+}
+
+#[cfg(unix)]
+fn clock_ticks_per_second() -> u64 {
+    // SAFETY: sysconf has no preconditions
+    let ticks = unsafe { libc::sysconf(libc::_SC_CLK_TCK) };
+    if ticks > 0 { ticks as u64 } else { 100 }
+}
+
+#[cfg(not(unix))]
+fn clock_ticks_per_second() -> u64 {
+    100
+}
+
+// Removes ANSI escape sequences (CSI, OSC and other ESC-prefixed controls) from terminal output
+fn strip_ansi_codes(input: &str) -> String {
+    enum State {
+        Normal,
+        Escape,
+        Csi,
+        // OSC and the DCS/SOS/PM/APC strings all run until BEL or ESC \
+        OscString,
+        OscStringEscape,
+        Charset,
+    }
+
+    let mut output = String::with_capacity(input.len());
+    let mut state = State::Normal;
+
+    for c in input.chars() {
+        state = match state {
+            State::Normal => match c {
+                '\u{1b}' => State::Escape,
+                '\u{9b}' => State::Csi,
+                _ => {
+                    output.push(c);
+                    State::Normal
+                }
+            },
+            State::Escape => match c {
+                '[' => State::Csi,
+                ']' | 'P' | 'X' | '^' | '_' => State::OscString,
+                '(' | ')' | '*' | '+' | '-' | '.' | '/' | '#' | '%' => State::Charset,
+                _ => State::Normal,
+            },
+            // Parameters and intermediates run until a final byte in @..~
+            State::Csi => match c {
+                '@'..='~' => State::Normal,
+                _ => State::Csi,
+            },
+            State::OscString => match c {
+                '\u{07}' => State::Normal,
+                '\u{1b}' => State::OscStringEscape,
+                _ => State::OscString,
+            },
+            State::OscStringEscape => match c {
+                '\\' => State::Normal,
+                '\u{1b}' => State::OscStringEscape,
+                _ => State::OscString,
+            },
+            State::Charset => State::Normal,
+        };
+    }
+
+    output
+}
+
+// Spawns a reader that appends everything from a child pipe to a shared buffer
+fn spawn_output_reader<R>(reader: Option<R>, buffer: Arc<Mutex<Vec<u8>>>) -> Option<tokio::task::JoinHandle<()>>
+where
+    R: tokio::io::AsyncRead + Unpin + Send + 'static,
+{
+    let mut reader = reader?;
+    Some(tokio::spawn(async move {
+        use tokio::io::AsyncReadExt;
+        let mut chunk = [0u8; 4096];
+        loop {
+            match reader.read(&mut chunk).await {
+                Ok(0) | Err(_) => break,
+                Ok(n) => buffer.lock().await.extend_from_slice(&chunk[..n]),
+            }
+        }
+    }))
+}
+
+// Checks whether the job's child has exited and records the outcome
+fn poll_terminal_job(job: &mut TerminalJob) {
+    if job.state != TerminalJobState::Running {
+        return;
+    }
+    if let Ok(Some(status)) = job.child.try_wait() {
+        job.state = if job.kill_requested { TerminalJobState::Killed } else { TerminalJobState::Exited };
+        job.exit_code = Some(job_exit_code(status));
+        job.finished_after = Some(job.started.elapsed());
+    }
+}
+
+// A job ended by a signal reports 128 + the signal number, as a shell would
+#[cfg(unix)]
+fn job_exit_code(status: std::process::ExitStatus) -> i32 {
+    use std::os::unix::process::ExitStatusExt;
+    status.code().or_else(|| status.signal().map(|signal| 128 + signal)).unwrap_or(-1)
+}
+
+#[cfg(not(unix))]
+fn job_exit_code(status: std::process::ExitStatus) -> i32 {
+    status.code().unwrap_or(-1)
+}
+
+// Background jobs whose status nobody collected are dropped this long after they finish
+const TERMINAL_JOB_TTL: std::time::Duration = std::time::Duration::from_secs(600);
+
+fn evict_stale_terminal_jobs(jobs: &mut HashMap<Uuid, TerminalJob>) {
+    jobs.retain(|_, job| {
+        poll_terminal_job(job);
+        let Some(finished_after) = job.finished_after.filter(|_| job.background) else {
+            return true;
+        };
+        job.started.elapsed().saturating_sub(finished_after) < TERMINAL_JOB_TTL
+    });
+}
+
+// Reports a job's status; a finished background job is removed once its status is handed out
+async fn collect_terminal_job(jobs: &mut HashMap<Uuid, TerminalJob>, id: Uuid) -> Option<TerminalJobStatus> {
+    let job = jobs.get(&id)?;
+    if !job.background || job.state == TerminalJobState::Running {
+        return Some(terminal_job_status(id, job).await);
+    }
+
+    // Let the readers drain what the process wrote before it ended
+    let mut job = jobs.remove(&id)?;
+    for reader in job.readers.drain(..) {
+        let _ = tokio::time::timeout(std::time::Duration::from_secs(1), reader).await;
+    }
+    Some(terminal_job_status(id, &job).await)
+}
+
+async fn terminal_job_status(job_id: Uuid, job: &TerminalJob) -> TerminalJobStatus {
+    TerminalJobStatus {
+        job_id: job_id.to_string(),
+        command: job.command.clone(),
+        state: job.state.clone(),
+        exit_code: job.exit_code,
+        output: job.combined_output().await,
+        started_at: job.started_at,
+        duration_ms: job.finished_after.unwrap_or_else(|| job.started.elapsed()).as_millis() as u64,
+    }
+}
+
+fn parse_job_id(job_id: &str) -> Result<Uuid, String> {
+    Uuid::parse_str(job_id).map_err(|e| format!("Invalid job id '{}': {}", job_id, e))
+}
+
+// Reads a field such as "MemTotal" from /proc/meminfo, converted from KB to bytes
+fn read_meminfo_bytes(field: &str) -> Option<u64> {
+    let meminfo = std::fs::read_to_string("/proc/meminfo").ok()?;
+    let prefix = format!("{}:", field);
+    meminfo
+        .lines()
+        .find(|line| line.starts_with(&prefix))
+        .and_then(|line| line.split_whitespace().nth(1))
+        .and_then(|kb| kb.parse::<u64>().ok())
+        .map(|kb| kb * 1024)
+}
+
+// Returns (busy, total) jiffies from the aggregate cpu line of /proc/stat
+fn read_cpu_jiffies() -> Option<(u64, u64)> {
+    let stat = std::fs::read_to_string("/proc/stat").ok()?;
+    let line = stat.lines().find(|line| line.starts_with("cpu "))?;
+    let values: Vec<u64> = line
+        .split_whitespace()
+        .skip(1)
+        .filter_map(|v| v.parse().ok())
+        .collect();
+    let total: u64 = values.iter().sum();
+    // idle + iowait
+    let idle = values.get(3).copied().unwrap_or(0) + values.get(4).copied().unwrap_or(0);
+    Some((total - idle, total))
+}
+
+#[tauri::command]
+pub async fn get_system_summary() -> Result<SystemSummary, CommandError> {
+    log::debug!("📊 Collecting system summary");
+
+    // CPU usage needs two samples of /proc/stat
+    let first = read_cpu_jiffies();
+    tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+    let second = read_cpu_jiffies();
+    let cpu_usage = match (first, second) {
+        (Some((busy1, total1)), Some((busy2, total2))) if total2 > total1 => {
+            (busy2.saturating_sub(busy1)) as f32 / (total2 - total1) as f32 * 100.0
+        }
+        _ => 0.0,
+    };
+
+    let memory_total = read_meminfo_bytes("MemTotal").unwrap_or(0);
+    let memory_available = read_meminfo_bytes("MemAvailable").unwrap_or(0);
+
+    let loadavg = std::fs::read_to_string("/proc/loadavg").unwrap_or_default();
+    let loads: Vec<f32> = loadavg
+        .split_whitespace()
+        .take(3)
+        .filter_map(|v| v.parse().ok())
+        .collect();
+
+    let uptime_seconds = std::fs::read_to_string("/proc/uptime")
+        .ok()
+        .and_then(|uptime| uptime.split_whitespace().next().and_then(|v| v.parse::<f64>().ok()))
+        .map(|seconds| seconds as u64)
+        .unwrap_or(0);
+
+    // One list-units call gives the active state of every loaded service
+    let mut services_running = 0;
+    let mut services_stopped = 0;
+    let mut services_failed = 0;
+    if let Ok(output) = Command::new("systemctl")
+        .args(&["list-units", "--type=service", "--all", "--no-legend", "--plain", "--no-pager"])
+        .output()
+    {
+        for line in String::from_utf8_lossy(&output.stdout).lines() {
+            // UNIT LOAD ACTIVE SUB DESCRIPTION
+            match line.split_whitespace().nth(2) {
+                Some("active") => services_running += 1,
+                Some("inactive") => services_stopped += 1,
+                Some("failed") => services_failed += 1,
+                _ => {}
+            }
+        }
+    }
+
+    Ok(SystemSummary {
+        cpu_usage,
+        memory_used: memory_total.saturating_sub(memory_available),
+        memory_total,
+        load_average_1: loads.first().copied().unwrap_or(0.0),
+        load_average_5: loads.get(1).copied().unwrap_or(0.0),
+        load_average_15: loads.get(2).copied().unwrap_or(0.0),
+        uptime_seconds,
+        services_running,
+        services_stopped,
+        services_failed,
+        timestamp: Utc::now(),
+    })
+}
+
+// Kernel and virtual filesystems that don't represent real storage
+const PSEUDO_FILESYSTEMS: [&str; 24] = [
+    "proc", "sysfs", "tmpfs", "devtmpfs", "devpts", "cgroup", "cgroup2", "securityfs",
+    "pstore", "debugfs", "tracefs", "configfs", "fusectl", "mqueue", "hugetlbfs", "bpf",
+    "autofs", "binfmt_misc", "efivarfs", "rpc_pipefs", "nsfs", "ramfs", "overlay", "squashfs",
+];
+
+// /proc/mounts escapes spaces, tabs, newlines and backslashes as octal, e.g. "\040"
+fn unescape_mount_field(field: &str) -> String {
+    let bytes = field.as_bytes();
+    let mut result = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'\\' && i + 3 < bytes.len() && bytes[i + 1..i + 4].iter().all(|b| (b'0'..=b'7').contains(b)) {
+            let code = u8::from_str_radix(&field[i + 1..i + 4], 8).unwrap_or(b'?');
+            result.push(code);
+            i += 4;
+        } else {
+            result.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8_lossy(&result).to_string()
+}
+
+// Returns (total, available to unprivileged users, free) bytes for the filesystem at path
+#[cfg(unix)]
+fn filesystem_space(path: &str) -> Option<(u64, u64, u64)> {
+    let c_path = std::ffi::CString::new(path).ok()?;
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    // SAFETY: c_path is NUL-terminated and stat is a valid, writable statvfs
+    if unsafe { libc::statvfs(c_path.as_ptr(), &mut stat) } != 0 {
+        return None;
+    }
+
+    let block_size = stat.f_frsize as u64;
+    Some((
+        stat.f_blocks as u64 * block_size,
+        stat.f_bavail as u64 * block_size,
+        stat.f_bfree as u64 * block_size,
+    ))
+}
+
+#[cfg(not(unix))]
+fn filesystem_space(_path: &str) -> Option<(u64, u64, u64)> {
+    None
+}
+
+#[tauri::command]
+pub async fn get_disk_usage(include_pseudo: Option<bool>) -> Result<Vec<DiskUsage>, CommandError> {
+    let include_pseudo = include_pseudo.unwrap_or(false);
+    log::debug!("💽 Collecting disk usage (include_pseudo: {})", include_pseudo);
+
+    let mounts = std::fs::read_to_string("/proc/mounts")
+        .map_err(|e| format!("Failed to read /proc/mounts: {}", e))?;
+
+    let mut seen_devices = std::collections::HashSet::new();
+    let mut usage = Vec::new();
+    for line in mounts.lines() {
+        // device mount_point fs_type options dump pass
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() < 3 {
+            continue;
+        }
+        let device = unescape_mount_field(fields[0]);
+        let mount_point = unescape_mount_field(fields[1]);
+        let fs_type = fields[2].to_string();
+
+        if !include_pseudo {
+            if PSEUDO_FILESYSTEMS.contains(&fs_type.as_str()) {
+                continue;
+            }
+            // Bind mounts of the same device would otherwise be listed once per mount point
+            if !seen_devices.insert(device.clone()) {
+                continue;
+            }
+        }
+
+        let Some((total, available, free)) = filesystem_space(&mount_point) else {
+            log::debug!("⚠️ Could not stat {}", mount_point);
+            continue;
+        };
+        if total == 0 && !include_pseudo {
+            continue;
+        }
+
+        let used = total.saturating_sub(free);
+        // Matches df: used as a share of the space usable by non-root users
+        let usable = used + available;
+        let percent = if usable > 0 { used as f32 / usable as f32 * 100.0 } else { 0.0 };
+
+        usage.push(DiskUsage {
+            mount_point,
+            device,
+            fs_type,
+            total,
+            used,
+            available,
+            percent,
+        });
+    }
+
+    log::debug!("✅ Found {} mounted filesystems", usage.len());
+    Ok(usage)
+}
+
+#[tauri::command]
+pub async fn execute_terminal_command(
+    jobs: State<'_, TerminalJobs>,
+    command: String,
+    working_dir: Option<String>,
+    background: Option<bool>,
+    strip_ansi: Option<bool>,
+) -> Result<TerminalCommand, CommandError> {
+    log::info!("💻 Executing terminal command: {}", command);
+    log::debug!("📁 Working directory: {:?}", working_dir);
+    
+    let timestamp = Utc::now();
+    
+    // Parse command into parts
+    let parts: Vec<&str> = command.trim().split_whitespace().collect();
+    if parts.is_empty() {
+        log::warn!("⚠️ Empty command received");
+        return Err(CommandError::InvalidInput("Empty command".to_string()));
+    }
+    
+    log::debug!("🔧 Command parts: {:?}", parts);
+    
+    let mut cmd = tokio::process::Command::new(parts[0]);
+    if parts.len() > 1 {
+        cmd.args(&parts[1..]);
+    }
+    
+    // Set working directory if provided
+    if let Some(dir) = working_dir {
+        log::debug!("📁 Setting working directory: {}", dir);
+        cmd.current_dir(dir);
+    }
+    
+    // Execute command
+    log::debug!("🚀 Spawning command with output capture");
+    let mut child = match cmd
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn() {
+        Ok(child) => child,
+        Err(e) => {
+            log::error!("❌ Failed to execute command: {}", e);
+            return Err(format!("Failed to execute command: {}", e).into());
+        }
+    };
+
+    let stdout = Arc::new(Mutex::new(Vec::new()));
+    let stderr = Arc::new(Mutex::new(Vec::new()));
+    let readers: Vec<_> = [
+        spawn_output_reader(child.stdout.take(), stdout.clone()),
+        spawn_output_reader(child.stderr.take(), stderr.clone()),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+
+    let job_id = Uuid::new_v4();
+    let mut jobs_map = jobs.lock().await;
+    evict_stale_terminal_jobs(&mut jobs_map);
+    jobs_map.insert(job_id, TerminalJob {
+        command: command.clone(),
+        child,
+        stdout,
+        stderr,
+        readers,
+        state: TerminalJobState::Running,
+        exit_code: None,
+        started_at: timestamp,
+        started: std::time::Instant::now(),
+        finished_after: None,
+        kill_requested: false,
+        strip_ansi: strip_ansi.unwrap_or(false),
+        background: background.unwrap_or(false),
+    });
+    drop(jobs_map);
+    log::debug!("🆔 Terminal job {} started", job_id);
+
+    if background.unwrap_or(false) {
+        log::info!("✅ Terminal command running in background as job {}", job_id);
+        return Ok(TerminalCommand {
+            job_id: job_id.to_string(),
+            command,
+            output: String::new(),
+            exit_code: -1,
+            timestamp,
+            duration_ms: 0,
+        });
+    }
+
+    // Wait for the job to finish; cancel_terminal_command may kill it meanwhile
+    let mut job = loop {
+        {
+            let mut jobs = jobs.lock().await;
+            let Some(job) = jobs.get_mut(&job_id) else {
+                return Err(CommandError::NotFound(format!("Terminal job {} disappeared", job_id)));
+            };
+            poll_terminal_job(job);
+            if job.state != TerminalJobState::Running {
+                break jobs.remove(&job_id).expect("job present");
+            }
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    };
+
+    for reader in job.readers.drain(..) {
+        let _ = reader.await;
+    }
+
+    let duration = job.finished_after.unwrap_or_else(|| job.started.elapsed());
+    log::debug!("⏱️ Command execution time: {:?}", duration);
+    
+    let combined_output = job.combined_output().await;
+    let output_length = combined_output.len();
+    log::debug!("📄 Command output length: {} characters", output_length);
+    
+    let result = TerminalCommand {
+        job_id: job_id.to_string(),
+        command,
+        output: combined_output,
+        exit_code: job.exit_code.unwrap_or(-1),
+        timestamp,
+        duration_ms: duration.as_millis() as u64,
+    };
+    
+    log::info!("✅ Terminal command completed in {}ms ({:?})", result.duration_ms, job.state);
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn cancel_terminal_command(jobs: State<'_, TerminalJobs>, job_id: String) -> Result<TerminalJobStatus, CommandError> {
+    let id = parse_job_id(&job_id).map_err(CommandError::InvalidInput)?;
+    let mut last_status = {
+        let mut jobs = jobs.lock().await;
+        let job = jobs.get_mut(&id).ok_or_else(|| CommandError::NotFound(format!("Terminal job {} not found", job_id)))?;
+
+        poll_terminal_job(job);
+        if job.state == TerminalJobState::Running {
+            log::info!("🛑 Killing terminal job {}: {}", job_id, job.command);
+            job.child
+                .start_kill()
+                .map_err(|e| format!("Failed to kill job {}: {}", job_id, e))?;
+            job.kill_requested = true;
+        }
+        terminal_job_status(id, job).await
+    };
+
+    // The kill is only requested above; give the process a moment to actually exit. The lock
+    // is released between polls so other jobs and status requests aren't held up meanwhile.
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(2);
+    loop {
+        let mut jobs = jobs.lock().await;
+        let Some(job) = jobs.get_mut(&id) else {
+            // The foreground command waiting on it collected the job once it exited
+            last_status.state = TerminalJobState::Killed;
+            return Ok(last_status);
+        };
+
+        poll_terminal_job(job);
+        if job.state == TerminalJobState::Running && std::time::Instant::now() >= deadline {
+            job.state = TerminalJobState::Killed;
+            job.finished_after = Some(job.started.elapsed());
+        }
+        if job.state != TerminalJobState::Running {
+            return Ok(collect_terminal_job(&mut jobs, id).await.expect("job present"));
+        }
+        last_status = terminal_job_status(id, job).await;
+        drop(jobs);
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    }
+}
+
+#[tauri::command]
+pub async fn get_terminal_job_status(jobs: State<'_, TerminalJobs>, job_id: String) -> Result<TerminalJobStatus, CommandError> {
+    let id = parse_job_id(&job_id).map_err(CommandError::InvalidInput)?;
+    let mut jobs = jobs.lock().await;
+    let job = jobs.get_mut(&id).ok_or_else(|| CommandError::NotFound(format!("Terminal job {} not found", job_id)))?;
+
+    poll_terminal_job(job);
+    Ok(collect_terminal_job(&mut jobs, id).await.expect("job present"))
+}
+
+#[tauri::command]
+pub async fn get_current_directory() -> Result<String, CommandError> {
+    Ok(std::env::current_dir()
+        .map_err(|e| format!("Failed to get current directory: {}", e))?
+        .to_string_lossy()
+        .to_string())
+}
+
+// Extracts the executable from ExecStart, e.g. "{ path=/usr/sbin/nginx ; argv[]=... }"
+fn exec_start_binary(systemd_service: &str) -> Option<String> {
+    let exec_start = get_unit_property(systemd_service, "ExecStart")?;
+    let path = exec_start
+        .split(';')
+        .find_map(|field| field.trim().trim_start_matches('{').trim().strip_prefix("path="))?
+        .trim();
+
+    let binary = std::path::Path::new(path);
+    if binary.is_absolute() && binary.is_file() {
+        Some(path.to_string())
+    } else {
+        None
+    }
+}
+
+// Runs the binary directly (no shell) with common version flags
+async fn probe_binary_version(binary: &str) -> Option<String> {
+    for flag in ["--version", "-V"] {
+        // A daemon that ignores the flag would otherwise keep running, so bound and kill it
+        let child = tokio::process::Command::new(binary)
+            .arg(flag)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .output();
+
+        let Ok(Ok(output)) = tokio::time::timeout(std::time::Duration::from_secs(3), child).await else {
+            log::debug!("⚠️ Version probe {} {} failed or timed out", binary, flag);
+            continue;
+        };
+        if !output.status.success() {
+            continue;
+        }
+
+        // Some tools (nginx, java) print their version on stderr
+        let version = [&output.stdout, &output.stderr]
+            .iter()
+            .filter_map(|stream| {
+                String::from_utf8_lossy(stream)
+                    .lines()
+                    .map(|line| line.trim().to_string())
+                    .find(|line| !line.is_empty())
+            })
+            .next();
+        if version.is_some() {
+            return version;
+        }
+    }
+
+    None
+}
+
+#[tauri::command]
+pub async fn get_service_info(service_name: String) -> Result<serde_json::Value, CommandError> {
+    validate_service_name(&service_name)?;
+
+    let systemd_service = find_service_name(&service_name)?;
+    
+    // Try to get version information from the service
+    let mut info = serde_json::json!({
+        "name": service_name,
+        "systemd_service": systemd_service
+    });
+    
+    // Probe the unit's actual binary; without a resolvable ExecStart there's nothing safe to run
+    match exec_start_binary(&systemd_service) {
+        Some(binary) => {
+            info["executable"] = serde_json::Value::String(binary.clone());
+            if let Some(version) = probe_binary_version(&binary).await {
+                info["version"] = serde_json::Value::String(version);
+            }
+        }
+        None => log::debug!("ℹ️ No ExecStart binary for {}, skipping version detection", systemd_service),
+    }
+    
+    Ok(info)
+}
+
+#[tauri::command]
+pub async fn get_service_ports(service_name: String) -> Result<Vec<String>, CommandError> {
+    validate_service_name(&service_name)?;
+
+    let mut ports = Vec::new();
+    
+    // Try to find ports using netstat and lsof
+    let commands = vec![
+        format!("netstat -tulpn | grep {}", service_name),
+        format!("lsof -i -P | grep {}", service_name),
+        format!("ss -tulpn | grep {}", service_name),
+    ];
+    
+    for cmd in commands {
+        if let Ok(output) = Command::new("sh")
+            .arg("-c")
+            .arg(&cmd)
+            .output()
+        {
+            if output.status.success() {
+                let output_str = String::from_utf8_lossy(&output.stdout);
+                for line in output_str.lines() {
+                    // Extract port numbers from the output using simple string parsing
+                    if let Some(colon_pos) = line.find(':') {
+                        let after_colon = &line[colon_pos + 1..];
+                        if let Some(space_pos) = after_colon.find(' ') {
+                            let port_str = &after_colon[..space_pos];
+                            if let Ok(_) = port_str.parse::<u16>() {
+                                let port = port_str.to_string();
+                                if !ports.contains(&port) {
+                                    ports.push(port);
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+    
+    // Limit to first 10 ports to avoid overwhelming the UI
+    ports.truncate(10);
+    Ok(ports)
+}
+
+#[tauri::command]
+pub async fn find_service_by_port(port: u16) -> Result<Option<PortOwner>, CommandError> {
+    let output = Command::new("ss")
+        .args(&["-tulpnH"])
+        .output()
+        .map_err(|e| format!("Failed to run ss: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("Failed to list sockets: {}", String::from_utf8_lossy(&output.stderr).trim()).into());
+    }
+
+    // Prefer a TCP listener when both protocols are bound, as with DNS on 53
+    let mut owners = parse_ss_sockets(&String::from_utf8_lossy(&output.stdout), port);
+    owners.sort_by_key(|owner| owner.protocol != "tcp");
+    let Some(mut owner) = owners.into_iter().next() else {
+        log::debug!("🔌 Nothing is bound to port {}", port);
+        return Ok(None);
+    };
+
+    // ss only reports the process when it's ours or we're root
+    if owner.pid.is_none() {
+        log::debug!("ℹ️ Port {} is bound but its process isn't visible without root", port);
+    }
+    owner.service_name = owner.pid.and_then(unit_for_pid);
+    Ok(Some(owner))
+}
+
+// Parses `ss -tulpnH` lines into the sockets bound to `port`:
+// udp UNCONN 0 0 127.0.0.53%lo:53 0.0.0.0:* users:(("systemd-resolve",pid=612,fd=13))
+fn parse_ss_sockets(output: &str, port: u16) -> Vec<PortOwner> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 5 {
+                return None;
+            }
+
+            let protocol = fields[0].to_string();
+            // The port follows the last colon, which also covers [::]:22 and *:22
+            let (address, local_port) = fields[4].rsplit_once(':')?;
+            if local_port.parse::<u16>().ok()? != port {
+                return None;
+            }
+
+            let users = fields.iter().find(|field| field.starts_with("users:"));
+            let process = users
+                .and_then(|users| users.split('"').nth(1))
+                .map(|name| name.to_string());
+            let pid = users
+                .and_then(|users| users.split("pid=").nth(1))
+                .and_then(|rest| rest.split(|c: char| !c.is_ascii_digit()).next())
+                .and_then(|pid| pid.parse().ok());
+
+            Some(PortOwner {
+                port,
+                protocol,
+                local_address: address.to_string(),
+                pid,
+                process,
+                service_name: None,
+            })
+        })
+        .collect()
+}
+
+// Resolves a PID to its systemd unit from /proc/<pid>/cgroup, falling back to `systemctl status <pid>`
+fn unit_for_pid(pid: u32) -> Option<String> {
+    let from_cgroup = std::fs::read_to_string(format!("/proc/{}/cgroup", pid))
+        .ok()
+        .and_then(|cgroups| {
+            // cgroup v2 has a single "0::<path>" line; on v1 the name=systemd hierarchy holds the unit
+            cgroups
+                .lines()
+                .find(|line| line.starts_with("0::") || line.contains(":name=systemd:"))
+                .and_then(|line| line.splitn(3, ':').nth(2))
+                .and_then(unit_from_cgroup_path)
+        });
+    if from_cgroup.is_some() {
+        return from_cgroup;
+    }
+
+    // The first line is "● ssh.service - OpenBSD Secure Shell server"
+    let output = Command::new("systemctl")
+        .args(&["status", "--no-pager", "--lines=0", &pid.to_string()])
+        .output()
+        .ok()?;
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .next()
+        .and_then(|line| line.split_whitespace().find(|word| word.contains('.')))
+        .map(|unit| unit.trim_end_matches(".service").to_string())
+}
+
+// "/system.slice/nginx.service" -> "nginx"; the innermost service wins over its slices,
+// and processes in a scope (e.g. a login session) report the scope itself
+fn unit_from_cgroup_path(path: &str) -> Option<String> {
+    let components: Vec<&str> = path.trim().split('/').filter(|part| !part.is_empty()).collect();
+    if let Some(service) = components.iter().rev().find(|part| part.ends_with(".service")) {
+        return Some(service.trim_end_matches(".service").to_string());
+    }
+    components
+        .iter()
+        .rev()
+        .find(|part| part.ends_with(".scope"))
+        .map(|scope| scope.to_string())
+}
+
+#[tauri::command]
+pub async fn check_port_open(host: String, port: u16, timeout_ms: Option<u64>) -> Result<PortCheck, CommandError> {
+    let host = host.trim().to_string();
+    if host.is_empty() {
+        return Err(CommandError::InvalidInput("Host cannot be empty".to_string()));
+    }
+    let timeout = std::time::Duration::from_millis(timeout_ms.unwrap_or(2000).max(1));
+    log::debug!("🔌 Checking {}:{} (timeout {:?})", host, port, timeout);
+
+    let result = |open: bool, latency_ms: Option<u64>, error: Option<String>| PortCheck {
+        host: host.clone(),
+        port,
+        open,
+        latency_ms,
+        error,
+    };
+
+    // Name resolution shares the timeout so an unresponsive DNS server can't hang the check
+    let started = std::time::Instant::now();
+    let addresses: Vec<std::net::SocketAddr> =
+        match tokio::time::timeout(timeout, tokio::net::lookup_host((host.as_str(), port))).await {
+            Ok(Ok(addresses)) => addresses.collect(),
+            Ok(Err(e)) => return Ok(result(false, None, Some(format!("Failed to resolve {}: {}", host, e)))),
+            Err(_) => return Ok(result(false, None, Some(format!("Timed out resolving {}", host)))),
+        };
+
+    let mut last_error = format!("No addresses found for {}", host);
+    for address in addresses {
+        let remaining = timeout.saturating_sub(started.elapsed());
+        if remaining.is_zero() {
+            last_error = format!("Timed out after {} ms", timeout.as_millis());
+            break;
+        }
+
+        let attempt = std::time::Instant::now();
+        match tokio::time::timeout(remaining, tokio::net::TcpStream::connect(address)).await {
+            Ok(Ok(_)) => {
+                let latency = attempt.elapsed().as_millis() as u64;
+                log::debug!("✅ {} is accepting connections ({} ms)", address, latency);
+                return Ok(result(true, Some(latency), None));
+            }
+            Ok(Err(e)) => last_error = format!("{}: {}", address, e),
+            // A filtered port drops the SYN, so only the timeout ends the attempt
+            Err(_) => last_error = format!("{}: timed out after {} ms", address, timeout.as_millis()),
+        }
+    }
+
+    log::debug!("❌ {}:{} is not reachable: {}", host, port, last_error);
+    Ok(result(false, None, Some(last_error)))
+}
+
+fn daemon_reload(password: Option<String>) -> Result<ServiceOperation, String> {
+    require_systemctl()?;
+    log::info!("🔄 Reloading systemd manager configuration");
+
+    let output = execute_sudo_command(&["systemctl", "daemon-reload"], password, true)?;
+    if !output.status.success() {
+        let error = String::from_utf8_lossy(&output.stderr);
+        log::error!("❌ daemon-reload failed: {}", error);
+        return Ok(ServiceOperation {
+            success: false,
+            message: format!("daemon-reload failed: {}", error),
+            service: None,
+        });
+    }
+
+    // The user manager keeps its own unit cache; reload it too when one is running
+    if let Err(e) = execute_sudo_command(&["systemctl", "daemon-reload"], None, false) {
+        log::debug!("ℹ️ User-level daemon-reload skipped: {}", e);
+    }
+
+    log::info!("✅ systemd manager configuration reloaded");
+    Ok(ServiceOperation {
+        success: true,
+        message: "systemd configuration reloaded".to_string(),
+        service: None,
+    })
+}
+
+#[tauri::command]
+pub async fn reload_daemon(password: Option<String>) -> Result<ServiceOperation, CommandError> {
+    Ok(daemon_reload(password)?)
+}
+
+#[tauri::command]
+pub async fn get_targets() -> Result<Vec<SystemTarget>, CommandError> {
+    require_systemctl()?;
+
+    let output = Command::new("systemctl")
+        .args(&["list-units", "--type=target", "--all", "--no-legend", "--plain", "--no-pager"])
+        .output()
+        .map_err(|e| format!("Failed to list targets: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("Failed to list targets: {}", String::from_utf8_lossy(&output.stderr)).into());
+    }
+
+    let default_target = read_default_target().unwrap_or_default();
+    let targets = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            // UNIT LOAD ACTIVE SUB DESCRIPTION
+            let mut fields = line.split_whitespace();
+            let name = fields.next()?;
+            let load_state = fields.next()?;
+            let active_state = fields.next()?;
+            let sub_state = fields.next()?;
+            Some(SystemTarget {
+                name: name.to_string(),
+                description: fields.collect::<Vec<_>>().join(" "),
+                load_state: load_state.to_string(),
+                active_state: active_state.to_string(),
+                sub_state: sub_state.to_string(),
+                is_default: name == default_target,
+            })
+        })
+        .collect();
+
+    Ok(targets)
+}
+
+#[tauri::command]
+pub async fn get_default_target() -> Result<String, CommandError> {
+    Ok(read_default_target()?)
+}
+
+// Changing the boot target affects every future boot, so the caller has to confirm it
+#[tauri::command]
+pub async fn set_default_target(target: String, password: Option<String>, confirm: bool) -> Result<ServiceOperation, CommandError> {
+    let target = target_unit_name(&target)?;
+    if !confirm {
+        return Err(CommandError::InvalidInput(format!("Setting the default target to {} requires confirmation", target)));
+    }
+
+    log::info!("🎯 Setting default target to {}", target);
+    let output = execute_sudo_command(&["systemctl", "set-default", &target], password, true)?;
+    Ok(target_operation(&output, format!("Default target set to {}", target)))
+}
+
+// Isolating stops every unit the target doesn't need, so it needs the same confirmation
+#[tauri::command]
+pub async fn isolate_target(target: String, password: Option<String>, confirm: bool) -> Result<ServiceOperation, CommandError> {
+    let target = target_unit_name(&target)?;
+    if !confirm {
+        return Err(CommandError::InvalidInput(format!("Isolating {} stops unrelated units and requires confirmation", target)));
+    }
+
+    log::warn!("🎯 Isolating target {}", target);
+    let output = execute_sudo_command(&["systemctl", "isolate", &target], password, true)?;
+    Ok(target_operation(&output, format!("Switched to {}", target)))
+}
+
+fn read_default_target() -> Result<String, String> {
+    require_systemctl()?;
+
+    let output = Command::new("systemctl")
+        .arg("get-default")
+        .output()
+        .map_err(|e| format!("Failed to get default target: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("Failed to get default target: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+// Accepts "multi-user" or "multi-user.target"
+fn target_unit_name(target: &str) -> Result<String, CommandError> {
+    require_systemctl()?;
+    validate_service_name(target)?;
+    if target.ends_with(".target") {
+        Ok(target.to_string())
+    } else if target.contains('.') {
+        Err(CommandError::InvalidInput(format!("'{}' is not a target unit", target)))
+    } else {
+        Ok(format!("{}.target", target))
+    }
+}
+
+fn target_operation(output: &std::process::Output, message: String) -> ServiceOperation {
+    if output.status.success() {
+        ServiceOperation {
+            success: true,
+            message,
+            service: None,
+        }
+    } else {
+        ServiceOperation {
+            success: false,
+            message: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            service: None,
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn get_service_properties(service_name: String) -> Result<ServiceProperties, CommandError> {
+    validate_service_name(&service_name)?;
+
+    let systemd_service = find_service_name(&service_name)?;
+
+    // Documentation= is a space separated list of URIs, which newer systemd releases quote
+    let documentation: Vec<String> = get_unit_property(&systemd_service, "Documentation")
+        .map(|value| value.split_whitespace().map(|url| url.trim_matches('"').to_string()).collect())
+        .unwrap_or_default();
+    let documentation_links = documentation.iter().map(|url| DocumentationLink::classify(url)).collect();
+
+    Ok(ServiceProperties {
+        service_name,
+        load_state: get_unit_property(&systemd_service, "LoadState"),
+        active_state: get_unit_property(&systemd_service, "ActiveState"),
+        sub_state: get_unit_property(&systemd_service, "SubState"),
+        unit_file_state: get_unit_property(&systemd_service, "UnitFileState"),
+        fragment_path: get_unit_property(&systemd_service, "FragmentPath"),
+        need_daemon_reload: get_unit_property(&systemd_service, "NeedDaemonReload").as_deref() == Some("yes"),
+        // Result reflects how the last run ended and stays "oom-kill" until the next start
+        recently_oom_killed: get_unit_property(&systemd_service, "Result").as_deref() == Some("oom-kill"),
+        documentation,
+        documentation_links,
+    })
+}
+
+const LIMIT_PROPERTIES: [&str; 5] = ["MemoryMax", "MemoryHigh", "CPUQuotaPerSecUSec", "TasksMax", "LimitNOFILE"];
+
+#[tauri::command]
+pub async fn get_service_limits(service_name: String) -> Result<ServiceLimits, CommandError> {
+    validate_service_name(&service_name)?;
+
+    if DockerBackend::container_name(&service_name).is_some() {
+        return Err(CommandError::InvalidInput("Resource limits are only available for systemd services".to_string()));
+    }
+
+    let systemd_service = find_service_name(&service_name)?;
+    Ok(read_service_limits(&systemd_service, service_name)?)
+}
+
+fn read_service_limits(systemd_service: &str, service_name: String) -> Result<ServiceLimits, String> {
+    let mut args = vec!["show", systemd_service];
+    for property in LIMIT_PROPERTIES {
+        args.extend(["-p", property]);
+    }
+    let output = Command::new("systemctl")
+        .args(&args)
+        .output()
+        .map_err(|e| format!("Failed to read limits for {}: {}", service_name, e))?;
+
+    if !output.status.success() {
+        return Err(format!("Failed to read limits for {}: {}", service_name,
+            String::from_utf8_lossy(&output.stderr).trim()));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let properties: HashMap<&str, &str> = stdout
+        .lines()
+        .filter_map(|line| line.split_once('='))
+        .collect();
+    let limit = |name: &str| properties.get(name).and_then(|value| parse_limit_value(value));
+
+    // The quota is CPU time allowed per wall-clock second, so 1s is one full core
+    let cpu_quota_percent = properties
+        .get("CPUQuotaPerSecUSec")
+        .filter(|value| !is_unlimited(value))
+        .and_then(|value| parse_systemd_duration_ms(value))
+        .map(|quota_ms| quota_ms as f32 / 10.0);
+    let cores = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1) as f32;
+
+    Ok(ServiceLimits {
+        service_name,
+        memory_max: limit("MemoryMax"),
+        memory_high: limit("MemoryHigh"),
+        cpu_quota_percent,
+        cpu_quota_share: cpu_quota_percent.map(|quota| quota / cores),
+        tasks_max: limit("TasksMax"),
+        open_files_max: limit("LimitNOFILE"),
+    })
+}
+
+fn is_unlimited(value: &str) -> bool {
+    matches!(value.trim(), "" | "infinity" | "[not set]" | "n/a")
+}
+
+// systemctl show prints memory limits in bytes and counts as plain numbers; u64::MAX is
+// how older releases spell infinity
+fn parse_limit_value(value: &str) -> Option<u64> {
+    if is_unlimited(value) {
+        return None;
+    }
+    value.trim().parse::<u64>().ok().filter(|limit| *limit != u64::MAX)
+}
+
+const LIMITS_DROPIN_NAME: &str = "limits.conf";
+
+// Directives set_service_limit may write. The flag marks limits that are applied when the
+// main process is spawned, so a running service keeps the old value until it restarts;
+// the cgroup ones take effect on daemon-reload.
+const SETTABLE_LIMITS: [(&str, bool); 5] = [
+    ("MemoryMax", false),
+    ("MemoryHigh", false),
+    ("CPUQuota", false),
+    ("TasksMax", false),
+    ("LimitNOFILE", true),
+];
+
+// Checks a limit value against the syntax systemd accepts for that directive
+fn validate_limit_value(limit_name: &str, value: &str) -> Result<(), CommandError> {
+    let is_number = |text: &str| !text.is_empty() && text.chars().all(|c| c.is_ascii_digit());
+    let is_percentage = |text: &str| text
+        .strip_suffix('%')
+        .map(|number| number.parse::<f64>().map(|percent| percent > 0.0).unwrap_or(false))
+        .unwrap_or(false);
+
+    let valid = match limit_name {
+        // Bytes with an optional K/M/G/T suffix, a share of physical memory, or infinity
+        "MemoryMax" | "MemoryHigh" => {
+            value == "infinity"
+                || is_percentage(value)
+                || is_number(value.strip_suffix(['K', 'M', 'G', 'T']).unwrap_or(value))
+        }
+        // Percent of one CPU; values above 100% allow more than one core
+        "CPUQuota" => is_percentage(value),
+        "TasksMax" => value == "infinity" || is_number(value) || is_percentage(value),
+        // A single value or soft:hard
+        "LimitNOFILE" => value
+            .split(':')
+            .all(|part| part == "infinity" || is_number(part)) && value.split(':').count() <= 2,
+        _ => false,
+    };
+
+    if valid {
+        Ok(())
+    } else {
+        Err(CommandError::InvalidInput(format!("Invalid value '{}' for {}", value, limit_name)))
+    }
+}
+
+// Reads the directives from the limits.conf drop-in we manage, preserving their order
+fn read_limits_dropin(systemd_service: &str) -> Vec<(String, String)> {
+    let path = format!("{}/{}.d/{}", PERSISTENT_UNIT_DIR, systemd_service, LIMITS_DROPIN_NAME);
+    let Ok(contents) = std::fs::read_to_string(&path) else {
+        return Vec::new();
+    };
+
+    contents
+        .lines()
+        .filter_map(|line| line.trim().split_once('='))
+        .filter(|(key, _)| SETTABLE_LIMITS.iter().any(|(name, _)| name == key))
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect()
+}
+
+fn render_limits_dropin(directives: &[(String, String)]) -> String {
+    let mut contents = String::from("[Service]\n");
+    for (key, value) in directives {
+        contents.push_str(&format!("{}={}\n", key, value));
+    }
+    contents
+}
+
+// Writes one resource limit into the limits.conf drop-in and reloads systemd. An empty value
+// or "reset" removes the directive so the unit falls back to its own setting.
+#[tauri::command]
+pub async fn set_service_limit(
+    service_name: String,
+    limit_name: String,
+    value: String,
+    password: Option<String>,
+) -> Result<ServiceLimitUpdate, CommandError> {
+    validate_service_name(&service_name)?;
+
+    if DockerBackend::container_name(&service_name).is_some() {
+        return Err(CommandError::InvalidInput("Resource limits are only available for systemd services".to_string()));
+    }
+
+    let Some(&(_, applies_on_start)) = SETTABLE_LIMITS.iter().find(|(name, _)| *name == limit_name) else {
+        let allowed: Vec<&str> = SETTABLE_LIMITS.iter().map(|(name, _)| *name).collect();
+        return Err(CommandError::InvalidInput(format!(
+            "Unsupported limit '{}'; expected one of {}", limit_name, allowed.join(", "))));
+    };
+
+    let value = value.trim().to_string();
+    let reset = value.is_empty() || value == "reset";
+    if !reset {
+        validate_limit_value(&limit_name, &value)?;
+    }
+
+    let systemd_service = find_service_name(&service_name)?;
+    log::info!("📏 Setting {}={} for service: {}", limit_name, value, service_name);
+
+    let mut directives = read_limits_dropin(&systemd_service);
+    if reset {
+        directives.retain(|(key, _)| *key != limit_name);
+    } else {
+        match directives.iter_mut().find(|(key, _)| *key == limit_name) {
+            Some(existing) => existing.1 = value.clone(),
+            None => directives.push((limit_name.clone(), value.clone())),
+        }
+    }
+
+    // install_unit_dropin runs daemon-reload once the file is in place
+    let path = install_unit_dropin(&systemd_service, LIMITS_DROPIN_NAME, &render_limits_dropin(&directives), password)?;
+    log::info!("✅ {} written to {}", limit_name, path);
+
+    let running = matches!(check_service_status(&systemd_service), Ok(ServiceStatus::Running));
+    let restart_required = applies_on_start && running;
+    let action = if reset { format!("Reset {}", limit_name) } else { format!("Set {}={}", limit_name, value) };
+    let message = if restart_required {
+        format!("{} for {}; restart the service to apply it", action, service_name)
+    } else {
+        format!("{} for {}", action, service_name)
+    };
+
+    Ok(ServiceLimitUpdate {
+        success: true,
+        message,
+        restart_required,
+        limits: read_service_limits(&systemd_service, service_name).ok(),
+    })
+}
+
+// Parses systemd time spans such as "1min 2.345s", "850ms" or "1h 3min" into milliseconds
+fn parse_systemd_duration_ms(text: &str) -> Option<u64> {
+    let mut total_ms = 0.0;
+    let mut parsed_any = false;
+
+    for token in text.split_whitespace() {
+        let (number, multiplier) = if let Some(n) = token.strip_suffix("ms") {
+            (n, 1.0)
+        } else if let Some(n) = token.strip_suffix("us").or_else(|| token.strip_suffix("µs")) {
+            (n, 0.001)
+        } else if let Some(n) = token.strip_suffix("min") {
+            (n, 60_000.0)
+        } else if let Some(n) = token.strip_suffix('h') {
+            (n, 3_600_000.0)
+        } else if let Some(n) = token.strip_suffix('d') {
+            (n, 86_400_000.0)
+        } else if let Some(n) = token.strip_suffix('s') {
+            (n, 1_000.0)
+        } else {
+            return None;
+        };
+
+        let value = number.parse::<f64>().ok()?;
+        total_ms += value * multiplier;
+        parsed_any = true;
+    }
+
+    if parsed_any {
+        Some(total_ms.round() as u64)
+    } else {
+        None
+    }
+}
+
+fn parse_blame_output(output: &str) -> Vec<BootTimingEntry> {
+    let mut entries: Vec<BootTimingEntry> = output
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            let (duration, unit) = line.rsplit_once(char::is_whitespace)?;
+            Some(BootTimingEntry {
+                service_name: unit.to_string(),
+                time_ms: parse_systemd_duration_ms(duration)?,
+                started_at_ms: None,
+            })
+        })
+        .collect();
+
+    entries.sort_by(|a, b| b.time_ms.cmp(&a.time_ms));
+    entries
+}
+
+fn parse_critical_chain_output(output: &str) -> Vec<BootTimingEntry> {
+    let mut entries: Vec<BootTimingEntry> = output
+        .lines()
+        .filter_map(|line| {
+            // Strip the tree drawing characters in front of each unit
+            let line = line.trim_start_matches(|c: char| !c.is_alphanumeric());
+            let (unit, timing) = line.split_once(" @")?;
+            let (started_at, activation) = match timing.split_once(" +") {
+                Some((at, took)) => (at, Some(took)),
+                None => (timing, None),
+            };
+
+            Some(BootTimingEntry {
+                service_name: unit.trim().to_string(),
+                time_ms: activation.and_then(parse_systemd_duration_ms).unwrap_or(0),
+                started_at_ms: parse_systemd_duration_ms(started_at),
+            })
+        })
+        .collect();
+
+    entries.sort_by(|a, b| b.time_ms.cmp(&a.time_ms));
+    entries
+}
+
+fn parse_boot_summary(output: &str) -> BootSummary {
+    let mut summary = BootSummary {
+        firmware_ms: None,
+        loader_ms: None,
+        kernel_ms: None,
+        initrd_ms: None,
+        userspace_ms: None,
+        total_ms: None,
+    };
+
+    // "Startup finished in 3.2s (firmware) + 2.1s (loader) + 1.5s (kernel) + 10.2s (userspace) = 17.0s"
+    let Some(line) = output.lines().find(|l| l.starts_with("Startup finished in ")) else {
+        return summary;
+    };
+    let line = line.trim_start_matches("Startup finished in ");
+    let (phases, total) = match line.rsplit_once(" = ") {
+        Some((phases, total)) => (phases, Some(total)),
+        None => (line, None),
+    };
+
+    summary.total_ms = total.and_then(|t| parse_systemd_duration_ms(t.trim()));
+
+    for phase in phases.split(" + ") {
+        let Some((duration, label)) = phase.trim().split_once(" (") else {
+            continue;
+        };
+        let value = parse_systemd_duration_ms(duration);
+        match label.trim_end_matches(')') {
+            "firmware" => summary.firmware_ms = value,
+            "loader" => summary.loader_ms = value,
+            "kernel" => summary.kernel_ms = value,
+            "initrd" => summary.initrd_ms = value,
+            "userspace" => summary.userspace_ms = value,
+            _ => {}
+        }
+    }
+
+    summary
+}
+
+#[tauri::command]
+pub async fn get_boot_timing() -> Result<BootTiming, CommandError> {
+    log::info!("⏱️ Collecting boot timing from systemd-analyze");
+
+    let blame_output = Command::new("systemd-analyze")
+        .arg("blame")
+        .output()
+        .map_err(|e| format!("Failed to run systemd-analyze blame: {}", e))?;
+
+    if !blame_output.status.success() {
+        return Err(format!("systemd-analyze blame failed: {}",
+            String::from_utf8_lossy(&blame_output.stderr)).into());
+    }
+
+    let chain_output = Command::new("systemd-analyze")
+        .arg("critical-chain")
+        .output()
+        .map_err(|e| format!("Failed to run systemd-analyze critical-chain: {}", e))?;
+
+    let blame = parse_blame_output(&String::from_utf8_lossy(&blame_output.stdout));
+    let critical_chain = if chain_output.status.success() {
+        parse_critical_chain_output(&String::from_utf8_lossy(&chain_output.stdout))
+    } else {
+        log::warn!("⚠️ systemd-analyze critical-chain failed: {}", String::from_utf8_lossy(&chain_output.stderr));
+        Vec::new()
+    };
+
+    log::debug!("✅ Parsed {} blame entries and {} critical-chain entries", blame.len(), critical_chain.len());
+    Ok(BootTiming { blame, critical_chain })
+}
+
+#[tauri::command]
+pub async fn get_boot_summary() -> Result<BootSummary, CommandError> {
+    let output = Command::new("systemd-analyze")
+        .arg("time")
+        .output()
+        .map_err(|e| format!("Failed to run systemd-analyze time: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("systemd-analyze time failed: {}",
+            String::from_utf8_lossy(&output.stderr)).into());
+    }
+
+    Ok(parse_boot_summary(&String::from_utf8_lossy(&output.stdout)))
+}
+
+// Splits a fixed-width systemctl table row using the column offsets from its header line
+fn split_table_row<'a>(row: &'a str, offsets: &[usize]) -> Vec<&'a str> {
+    offsets
+        .iter()
+        .enumerate()
+        .map(|(i, &start)| {
+            let end = offsets.get(i + 1).copied().unwrap_or(row.len()).min(row.len());
+            row.get(start.min(end)..end).unwrap_or("").trim()
+        })
+        .collect()
+}
+
+fn parse_timers_output(output: &str) -> Vec<TimerUnit> {
+    let mut lines = output.lines();
+    let Some(header) = lines.next() else {
+        return Vec::new();
+    };
+
+    let columns = ["NEXT", "LEFT", "LAST", "PASSED", "UNIT", "ACTIVATES"];
+    let offsets: Vec<usize> = columns.iter().filter_map(|c| header.find(c)).collect();
+    if offsets.len() != columns.len() {
+        log::warn!("⚠️ Unexpected list-timers header: {}", header);
+        return Vec::new();
+    }
+
+    let optional = |value: &str| -> Option<String> {
+        if value.is_empty() || value == "n/a" || value == "-" {
+            None
+        } else {
+            Some(value.to_string())
+        }
+    };
+
+    lines
+        // Stop at the blank line that precedes the "N timers listed." footer
+        .take_while(|line| !line.trim().is_empty())
+        .filter_map(|line| {
+            let fields = split_table_row(line, &offsets);
+            let unit = fields[4];
+            if !unit.ends_with(".timer") {
+                return None;
+            }
+            Some(TimerUnit {
+                unit: unit.to_string(),
+                activates: optional(fields[5]),
+                next: optional(fields[0]),
+                left: optional(fields[1]),
+                last: optional(fields[2]),
+                passed: optional(fields[3]),
+            })
+        })
+        .collect()
+}
+
+#[tauri::command]
+pub async fn get_timers() -> Result<Vec<TimerUnit>, CommandError> {
+    log::info!("⏰ Listing systemd timers");
+
+    let output = Command::new("systemctl")
+        .args(&["list-timers", "--all", "--no-pager"])
+        .output()
+        .map_err(|e| format!("Failed to list timers: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("Failed to list timers: {}",
+            String::from_utf8_lossy(&output.stderr)).into());
+    }
+
+    let timers = parse_timers_output(&String::from_utf8_lossy(&output.stdout));
+    log::debug!("✅ Found {} timers", timers.len());
+    Ok(timers)
+}
+
+fn parse_sockets_output(output: &str) -> Vec<SocketUnit> {
+    let mut lines = output.lines();
+    let Some(header) = lines.next() else {
+        return Vec::new();
+    };
+
+    let columns = ["LISTEN", "UNIT", "ACTIVATES"];
+    let offsets: Vec<usize> = columns.iter().filter_map(|c| header.find(c)).collect();
+    if offsets.len() != columns.len() {
+        log::warn!("⚠️ Unexpected list-sockets header: {}", header);
+        return Vec::new();
+    }
+
+    lines
+        .take_while(|line| !line.trim().is_empty())
+        .filter_map(|line| {
+            let fields = split_table_row(line, &offsets);
+            if !fields[1].ends_with(".socket") {
+                return None;
+            }
+            Some(SocketUnit {
+                unit: fields[1].to_string(),
+                listen: fields[0].to_string(),
+                activates: if fields[2].is_empty() { None } else { Some(fields[2].to_string()) },
+            })
+        })
+        .collect()
+}
+
+#[tauri::command]
+pub async fn get_sockets() -> Result<Vec<SocketUnit>, CommandError> {
+    log::info!("🔌 Listing systemd sockets");
+
+    let output = Command::new("systemctl")
+        .args(&["list-sockets", "--all", "--no-pager"])
+        .output()
+        .map_err(|e| format!("Failed to list sockets: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("Failed to list sockets: {}",
+            String::from_utf8_lossy(&output.stderr)).into());
+    }
+
+    let sockets = parse_sockets_output(&String::from_utf8_lossy(&output.stdout));
+    log::debug!("✅ Found {} sockets", sockets.len());
+    Ok(sockets)
+}
+
+// Reads a "key:12345kB" figure out of a kernel OOM report
+fn extract_kb_field(line: &str, key: &str) -> Option<u64> {
+    let start = line.find(key)? + key.len();
+    let digits: String = line[start..].chars().take_while(|c| c.is_ascii_digit()).collect();
+    digits.parse().ok()
+}
+
+fn parse_oom_line(service_name: &str, line: &str, source: &str) -> OomEvent {
+    // short-iso lines start with the timestamp followed by host and identifier
+    let (timestamp, message) = line.split_once(' ').unwrap_or(("", line));
+    let message = message.split_once(": ").map(|(_, m)| m).unwrap_or(message);
+
+    let mut pid = None;
+    let mut process_name = None;
+    if let Some(start) = message.find("Killed process ") {
+        let rest = &message[start + "Killed process ".len()..];
+        pid = rest.split_whitespace().next().and_then(|p| p.parse::<u32>().ok());
+        process_name = rest
+            .split_once('(')
+            .and_then(|(_, after)| after.split_once(')'))
+            .map(|(name, _)| name.to_string());
+    }
+
+    OomEvent {
+        service_name: service_name.to_string(),
+        timestamp: timestamp.to_string(),
+        source: source.to_string(),
+        pid,
+        process_name,
+        total_vm_kb: extract_kb_field(message, "total-vm:"),
+        anon_rss_kb: extract_kb_field(message, "anon-rss:"),
+        file_rss_kb: extract_kb_field(message, "file-rss:"),
+        message: message.to_string(),
+    }
+}
+
+fn is_oom_line(line: &str) -> bool {
+    let lower = line.to_lowercase();
+    lower.contains("out of memory") || lower.contains("oom-kill") || lower.contains("oom killer") || lower.contains("killed process")
+}
+
+#[tauri::command]
+pub async fn get_oom_events(service_name: String, since: Option<String>) -> Result<Vec<OomEvent>, CommandError> {
+    validate_service_name(&service_name)?;
+
+    log::info!("💥 Scanning journal for OOM kills of service: {}", service_name);
+    let systemd_service = find_service_name(&service_name)?;
+    require_journalctl()?;
+    let since = since.unwrap_or_else(|| "7 days ago".to_string());
+
+    // Process names the kernel would print in "Killed process <pid> (<name>)"
+    let base_name = systemd_service.trim_end_matches(".service").to_string();
+    let mut process_names = vec![base_name];
+    if let Some(main_pid) = get_unit_property(&systemd_service, "MainPID").filter(|p| p != "0") {
+        if let Ok(comm) = std::fs::read_to_string(format!("/proc/{}/comm", main_pid)) {
+            process_names.push(comm.trim().to_string());
+        }
+    }
+
+    let mut events = Vec::new();
+
+    let kernel_output = Command::new("journalctl")
+        .args(&["-k", "--no-pager", "-o", "short-iso", "--since", &since])
+        .output()
+        .map_err(|e| format!("Failed to read kernel journal: {}", e))?;
+
+    for line in String::from_utf8_lossy(&kernel_output.stdout).lines() {
+        if !is_oom_line(line) {
+            continue;
+        }
+        // The oom-kill summary names the cgroup; the "Killed process" line names the process
+        let mentions_unit = line.contains(&systemd_service)
+            || process_names.iter().any(|name| line.contains(&format!("({})", name)));
+        if mentions_unit {
+            events.push(parse_oom_line(&service_name, line, "kernel"));
+        }
+    }
+
+    let unit_output = Command::new("journalctl")
+        .args(&["-u", &systemd_service, "--no-pager", "-o", "short-iso", "--since", &since])
+        .output()
+        .map_err(|e| format!("Failed to read unit journal: {}", e))?;
+
+    for line in String::from_utf8_lossy(&unit_output.stdout).lines() {
+        if is_oom_line(line) {
+            events.push(parse_oom_line(&service_name, line, "unit"));
+        }
+    }
+
+    events.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+    log::info!("✅ Found {} OOM-related entries for {}", events.len(), service_name);
+    Ok(events)
+}
+
+// Splits a systemd-style word list, honouring single/double quotes and backslash escapes
+fn split_quoted_words(input: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut in_word = false;
+    let mut quote: Option<char> = None;
+    let mut chars = input.chars();
+
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (_, '\\') => {
+                if let Some(next) = chars.next() {
+                    current.push(next);
+                }
+                in_word = true;
+            }
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), c) => current.push(c),
+            (None, '"') | (None, '\'') => {
+                quote = Some(c);
+                in_word = true;
+            }
+            (None, c) if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut current));
+                    in_word = false;
+                }
+            }
+            (None, c) => {
+                current.push(c);
+                in_word = true;
+            }
+        }
+    }
+
+    if in_word {
+        words.push(current);
+    }
+    words
+}
+
+fn is_secret_env_key(key: &str) -> bool {
+    let upper = key.to_uppercase();
+    ["PASSWORD", "PASSWD", "TOKEN", "SECRET", "KEY"]
+        .iter()
+        .any(|pattern| upper.contains(pattern))
+}
+
+fn parse_environment_file(contents: &str, environment: &mut HashMap<String, String>) {
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        if let Some((key, value)) = line.split_once('=') {
+            let value = split_quoted_words(value).join(" ");
+            environment.insert(key.trim().to_string(), value);
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn get_service_environment(
+    service_name: String,
+    reveal_secrets: Option<bool>,
+    include_process: Option<bool>,
+) -> Result<HashMap<String, String>, CommandError> {
+    validate_service_name(&service_name)?;
+
+    log::info!("🌿 Reading environment for service: {}", service_name);
+    let systemd_service = find_service_name(&service_name)?;
+    let mut environment = HashMap::new();
+
+    if let Some(assignments) = get_unit_property(&systemd_service, "Environment") {
+        for assignment in split_quoted_words(&assignments) {
+            if let Some((key, value)) = assignment.split_once('=') {
+                environment.insert(key.to_string(), value.to_string());
+            }
+        }
+    }
+
+    // EnvironmentFile= entries override Environment=, matching systemd's precedence
+    if let Some(files) = get_unit_property(&systemd_service, "EnvironmentFiles") {
+        for line in files.lines() {
+            let Some(path) = line.split_whitespace().next() else {
+                continue;
+            };
+            match std::fs::read_to_string(path) {
+                Ok(contents) => parse_environment_file(&contents, &mut environment),
+                Err(e) => log::debug!("ℹ️ Skipping environment file {}: {}", path, e),
+            }
+        }
+    }
+
+    // The live process environment is authoritative when we are allowed to read it
+    if include_process.unwrap_or(true) {
+        if let Some(pid) = get_unit_property(&systemd_service, "MainPID").filter(|p| p != "0") {
+            match std::fs::read(format!("/proc/{}/environ", pid)) {
+                Ok(raw) => {
+                    for entry in raw.split(|&b| b == 0).filter(|e| !e.is_empty()) {
+                        let entry = String::from_utf8_lossy(entry);
+                        if let Some((key, value)) = entry.split_once('=') {
+                            environment.insert(key.to_string(), value.to_string());
+                        }
+                    }
+                }
+                Err(e) => log::debug!("ℹ️ Cannot read environment of PID {}: {}", pid, e),
+            }
+        }
+    }
+
+    if !reveal_secrets.unwrap_or(false) {
+        for (key, value) in environment.iter_mut() {
+            if is_secret_env_key(key) {
+                *value = "********".to_string();
+            }
+        }
+    }
+
+    log::debug!("✅ Found {} environment variables for {}", environment.len(), service_name);
+    Ok(environment)
+}
+
+#[tauri::command]
+pub async fn get_unit_file(service_name: String) -> Result<UnitFile, CommandError> {
+    validate_service_name(&service_name)?;
+
+    log::info!("📄 Reading unit file for service: {}", service_name);
+    let systemd_service = find_service_name(&service_name)?;
+
+    let path = get_unit_property(&systemd_service, "FragmentPath")
+        .ok_or_else(|| format!("No unit file found for {}", service_name))?;
+
+    let contents = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read unit file {}: {}", path, e))?;
+
+    Ok(UnitFile {
+        service_name,
+        path,
+        contents,
+    })
+}
+
+// Colon separated directories replacing the default readable roots. It is read from the
+// environment of whoever launches the app, so the webview whose reads it limits can't widen it.
+pub const READABLE_ROOTS_ENV: &str = "DSM_READABLE_ROOTS";
+const DEFAULT_READABLE_ROOTS: [&str; 2] = ["/etc", "/opt"];
+const DEFAULT_TEXT_FILE_MAX_BYTES: u64 = 1024 * 1024;
+const TEXT_FILE_MAX_BYTES_LIMIT: u64 = 16 * 1024 * 1024;
+
+static READABLE_ROOTS: OnceLock<Vec<PathBuf>> = OnceLock::new();
+
+// Resolved the same way read_text_file resolves the file, so "/.", "//." or "/usr/.." can't
+// sneak the filesystem root in; relative and missing directories are skipped too
+fn canonical_readable_root(root: &str) -> Option<PathBuf> {
+    let root = root.trim();
+    if !root.starts_with('/') {
+        log::warn!("⚠️ Ignoring readable root {:?}: not an absolute path", root);
+        return None;
+    }
+    let canonical = std::fs::canonicalize(root).ok()?;
+    if canonical.parent().is_none() {
+        log::warn!("⚠️ Ignoring readable root {:?}: it resolves to /", root);
+        return None;
+    }
+    Some(canonical)
+}
+
+fn readable_roots() -> &'static [PathBuf] {
+    READABLE_ROOTS.get_or_init(|| {
+        let configured = std::env::var(READABLE_ROOTS_ENV).ok().filter(|value| !value.trim().is_empty());
+        let roots: Vec<String> = match configured {
+            Some(value) => value.split(':').map(|root| root.to_string()).collect(),
+            None => DEFAULT_READABLE_ROOTS.iter().map(|root| root.to_string()).collect(),
+        };
+
+        let mut canonical: Vec<PathBuf> = Vec::new();
+        for root in roots.iter().filter_map(|root| canonical_readable_root(root)) {
+            if !canonical.contains(&root) {
+                canonical.push(root);
+            }
+        }
+        log::info!("📂 Readable roots: {:?}", canonical);
+        canonical
+    })
+}
+
+// The configured roots plus the data directory, which is always readable
+async fn readable_roots_with_data_dir(db: &Arc<Mutex<Database>>) -> Vec<PathBuf> {
+    let mut roots = readable_roots().to_vec();
+    let data_dir = db.lock().await.directory().to_string_lossy().to_string();
+    if let Some(data_dir) = canonical_readable_root(&data_dir) {
+        if !roots.contains(&data_dir) {
+            roots.push(data_dir);
+        }
+    }
+    roots
+}
+
+#[tauri::command]
+pub async fn get_readable_roots(db: State<'_, Arc<Mutex<Database>>>) -> Result<Vec<String>, CommandError> {
+    Ok(readable_roots_with_data_dir(&db)
+        .await
+        .iter()
+        .map(|root| root.to_string_lossy().to_string())
+        .collect())
+}
+
+// Reads a text file under one of the readable roots, e.g. a path from get_service_config_files.
+// Symlinks and ".." are resolved before the check so they can't point outside the roots.
+#[tauri::command]
+pub async fn read_text_file(
+    db: State<'_, Arc<Mutex<Database>>>,
+    path: String,
+    max_bytes: Option<u64>,
+) -> Result<TextFileContents, CommandError> {
+    use std::io::Read;
+
+    let max_bytes = max_bytes.unwrap_or(DEFAULT_TEXT_FILE_MAX_BYTES);
+    if max_bytes == 0 || max_bytes > TEXT_FILE_MAX_BYTES_LIMIT {
+        return Err(CommandError::InvalidInput(format!(
+            "max_bytes must be between 1 and {}", TEXT_FILE_MAX_BYTES_LIMIT
+        )));
+    }
+
+    let resolved = std::fs::canonicalize(&path).map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => CommandError::NotFound(format!("File not found: {}", path)),
+        _ => CommandError::from(format!("Failed to resolve {}: {}", path, e)),
+    })?;
+
+    let roots = readable_roots_with_data_dir(&db).await;
+    if !roots.iter().any(|root| resolved.starts_with(root)) {
+        log::warn!("🚫 Refusing to read {} outside the readable roots", resolved.display());
+        return Err(CommandError::PermissionDenied(format!(
+            "{} is outside the readable directories", path
+        )));
+    }
+
+    let metadata = std::fs::metadata(&resolved)
+        .map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    if !metadata.is_file() {
+        return Err(CommandError::InvalidInput(format!("{} is not a regular file", path)));
+    }
+
+    log::info!("📄 Reading text file: {}", resolved.display());
+    let mut bytes = Vec::new();
+    std::fs::File::open(&resolved)
+        .and_then(|file| file.take(max_bytes).read_to_end(&mut bytes))
+        .map_err(|e| format!("Failed to read {}: {}", path, e))?;
+
+    // Same heuristic as grep and git: a NUL byte means it isn't text
+    if bytes.contains(&0) {
+        return Err(CommandError::InvalidInput(format!("{} looks like a binary file", path)));
+    }
+
+    Ok(TextFileContents {
+        path: resolved.display().to_string(),
+        contents: String::from_utf8_lossy(&bytes).into_owned(),
+        size: metadata.len(),
+        modified: metadata.modified().ok().map(DateTime::<Utc>::from),
+        truncated: metadata.len() > max_bytes,
+    })
+}
+
+// Flags daemons commonly take their config file with, as "-c path" or "--config=path"
+const CONFIG_FLAGS: [&str; 7] = ["-c", "--config", "--conf", "--config-file", "--configfile", "-config", "--cfg"];
+const CONFIG_EXTENSIONS: [&str; 9] = ["conf", "cfg", "cnf", "ini", "yaml", "yml", "toml", "json", "xml"];
+
+// Every argv of the unit's ExecStart commands, e.g. "{ path=... ; argv[]=/usr/sbin/nginx -c /etc/nginx/nginx.conf ; ... }"
+fn exec_start_argvs(systemd_service: &str) -> Vec<Vec<String>> {
+    let Some(exec_start) = get_unit_property(systemd_service, "ExecStart") else {
+        return Vec::new();
+    };
+
+    exec_start
+        .lines()
+        .filter_map(|command| {
+            command
+                .split(" ; ")
+                .find_map(|field| field.trim().trim_start_matches('{').trim().strip_prefix("argv[]="))
+                .map(|argv| argv.split_whitespace().map(|arg| arg.to_string()).collect())
+        })
+        .collect()
+}
+
+// Best-effort guess at where a service reads its configuration from. Nothing found is an
+// empty list, not an error.
+#[tauri::command]
+pub async fn get_service_config_files(service_name: String) -> Result<Vec<ConfigFileCandidate>, CommandError> {
+    validate_service_name(&service_name)?;
+
+    if DockerBackend::container_name(&service_name).is_some() {
+        return Ok(Vec::new());
+    }
+
+    log::info!("🗂️ Looking for config files of service: {}", service_name);
+    let systemd_service = find_service_name(&service_name)?;
+    let working_directory = get_unit_property(&systemd_service, "WorkingDirectory")
+        .map(|dir| dir.trim_start_matches('!').trim_start_matches('-').to_string())
+        .filter(|dir| dir.starts_with('/'));
+
+    let mut candidates: Vec<(String, ConfigFileSource)> = Vec::new();
+
+    for argv in exec_start_argvs(&systemd_service) {
+        // argv[0] is the binary itself
+        let mut args = argv.iter().skip(1).peekable();
+        while let Some(arg) = args.next() {
+            if let Some((flag, value)) = arg.split_once('=') {
+                if CONFIG_FLAGS.contains(&flag) {
+                    candidates.push((value.to_string(), ConfigFileSource::ExecStartFlag));
+                    continue;
+                }
+            }
+            if CONFIG_FLAGS.contains(&arg.as_str()) {
+                if let Some(value) = args.next_if(|value| !value.starts_with('-')) {
+                    candidates.push((value.clone(), ConfigFileSource::ExecStartFlag));
+                }
+                continue;
+            }
+
+            let looks_like_config = !arg.starts_with('-')
+                && arg.contains('/')
+                && std::path::Path::new(arg)
+                    .extension()
+                    .and_then(|extension| extension.to_str())
+                    .is_some_and(|extension| CONFIG_EXTENSIONS.contains(&extension.to_lowercase().as_str()));
+            if looks_like_config {
+                candidates.push((arg.clone(), ConfigFileSource::ExecStartArgument));
+            }
+        }
+    }
+
+    // One "path (ignore_errors=no)" line per EnvironmentFile=
+    if let Some(files) = get_unit_property(&systemd_service, "EnvironmentFiles") {
+        for line in files.lines() {
+            if let Some(path) = line.split_whitespace().next() {
+                candidates.push((path.to_string(), ConfigFileSource::EnvironmentFile));
+            }
+        }
+    }
+
+    // Conventional locations only count when they exist; guessing missing paths is just noise
+    let stem = systemd_service
+        .trim_end_matches(".service")
+        .split('@')
+        .next()
+        .unwrap_or_default()
+        .to_string();
+    if !stem.is_empty() {
+        let conventions = [
+            format!("/etc/{0}/{0}.conf", stem),
+            format!("/etc/{}.conf", stem),
+            format!("/etc/default/{}", stem),
+            format!("/etc/sysconfig/{}", stem),
+        ];
+        for path in conventions {
+            if std::path::Path::new(&path).is_file() {
+                candidates.push((path, ConfigFileSource::Convention));
+            }
+        }
+    }
+
+    let mut files: Vec<ConfigFileCandidate> = Vec::new();
+    for (path, source) in candidates {
+        // Relative paths are resolved the way the service would see them
+        let path = match &working_directory {
+            Some(dir) if !path.starts_with('/') => format!("{}/{}", dir.trim_end_matches('/'), path),
+            _ => path,
+        };
+        if files.iter().any(|file| file.path == path) {
+            continue;
+        }
+        files.push(ConfigFileCandidate {
+            exists: std::path::Path::new(&path).is_file(),
+            path,
+            source,
+        });
+    }
+
+    log::debug!("✅ Found {} config file candidates for {}", files.len(), service_name);
+    Ok(files)
+}
+
+#[tauri::command]
+pub async fn get_unit_dropins(service_name: String) -> Result<Vec<UnitFile>, CommandError> {
+    validate_service_name(&service_name)?;
+
+    log::info!("📄 Listing drop-in files for service: {}", service_name);
+    let systemd_service = find_service_name(&service_name)?;
+
+    let mut dropins = Vec::new();
+    if let Some(paths) = get_unit_property(&systemd_service, "DropInPaths") {
+        for path in paths.split_whitespace() {
+            match std::fs::read_to_string(path) {
+                Ok(contents) => dropins.push(UnitFile {
+                    service_name: service_name.clone(),
+                    path: path.to_string(),
+                    contents,
+                }),
+                Err(e) => log::warn!("⚠️ Failed to read drop-in {}: {}", path, e),
+            }
+        }
+    }
+
+    log::debug!("✅ Found {} drop-in files for {}", dropins.len(), service_name);
+    Ok(dropins)
+}
+
+// Persistent drop-ins live under /etc; runtime ones under /run vanish on reboot
+const PERSISTENT_UNIT_DIR: &str = "/etc/systemd/system";
+const RUNTIME_UNIT_DIR: &str = "/run/systemd/system";
+
+// Installs a drop-in file for the unit as root and reloads systemd so it takes effect.
+// Returns the installed path, or a failure message suitable for a ServiceOperation.
+fn install_unit_dropin(systemd_service: &str, file_name: &str, contents: &str, password: Option<String>) -> Result<String, String> {
+    install_unit_dropin_in(PERSISTENT_UNIT_DIR, systemd_service, file_name, contents, password)
+}
+
+fn install_unit_dropin_in(base_dir: &str, systemd_service: &str, file_name: &str, contents: &str, password: Option<String>) -> Result<String, String> {
+    let dropin_dir = format!("{}/{}.d", base_dir, systemd_service);
+    let dropin_path = format!("{}/{}", dropin_dir, file_name);
+
+    let mkdir_output = execute_sudo_command(&["mkdir", "-p", &dropin_dir], password.clone(), true)?;
+    if !mkdir_output.status.success() {
+        let error = String::from_utf8_lossy(&mkdir_output.stderr);
+        return Err(format!("Failed to create {}: {}", dropin_dir, error));
+    }
+
+    install_root_file(&dropin_path, contents, password.clone())?;
+
+    let reload = daemon_reload(password)?;
+    if !reload.success {
+        return Err(format!("Wrote {} but {}", dropin_path, reload.message));
+    }
+
+    Ok(dropin_path)
+}
+
+// Generated timer/service pairs are named dsm-schedule-<unit>-<action> so get_schedules can find them
+const SCHEDULE_UNIT_PREFIX: &str = "dsm-schedule-";
+const SCHEDULE_ACTIONS: [&str; 3] = ["start", "stop", "restart"];
+
+// '@' would turn the generated name into a template instance
+fn schedule_unit_stem(systemd_service: &str, action: &str) -> String {
+    format!("{}{}-{}", SCHEDULE_UNIT_PREFIX, systemd_service.trim_end_matches(".service").replace('@', "_"), action)
+}
+
+// Returns systemd's normalized form of the spec, or its parse error
+fn normalize_calendar_spec(calendar_spec: &str) -> Result<String, CommandError> {
+    if calendar_spec.trim().is_empty() || calendar_spec.contains(['\n', '\r']) {
+        return Err(CommandError::InvalidInput("Calendar spec must be a single non-empty line".to_string()));
+    }
+
+    let output = Command::new("systemd-analyze")
+        .args(&["calendar", calendar_spec])
+        .output()
+        .map_err(|e| CommandError::Internal(format!("Failed to run systemd-analyze: {}", e)))?;
+
+    if !output.status.success() {
+        return Err(CommandError::InvalidInput(format!("Invalid calendar spec '{}': {}", calendar_spec,
+            String::from_utf8_lossy(&output.stderr).trim())));
+    }
+
+    let normalized = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("Normalized form:").map(|form| form.trim().to_string()))
+        .unwrap_or_else(|| calendar_spec.trim().to_string());
+    Ok(normalized)
+}
+
+#[tauri::command]
+pub async fn schedule_service(
+    service_name: String,
+    action: String,
+    calendar_spec: String,
+    password: Option<String>,
+) -> Result<ServiceSchedule, CommandError> {
+    validate_service_name(&service_name)?;
+    if !SCHEDULE_ACTIONS.contains(&action.as_str()) {
+        return Err(CommandError::InvalidInput(format!("Unsupported scheduled action '{}', expected start, stop or restart", action)));
+    }
+
+    let systemd_service = find_service_name(&service_name)?;
+    let calendar_spec = normalize_calendar_spec(&calendar_spec)?;
+    let systemctl = systemctl_path()
+        .ok_or_else(|| backend_unavailable("systemctl"))?
+        .display()
+        .to_string();
+
+    log::info!("⏰ Scheduling {} of {} at {}", action, systemd_service, calendar_spec);
+
+    let stem = schedule_unit_stem(&systemd_service, &action);
+    let timer_unit = format!("{}.timer", stem);
+    let service_contents = format!(
+        "[Unit]\nDescription=Scheduled {action} of {unit} (Dev Services Manager)\n\n\
+         [Service]\nType=oneshot\nExecStart={systemctl} {action} {unit}\n",
+        action = action,
+        unit = systemd_service,
+        systemctl = systemctl,
+    );
+    let timer_contents = format!(
+        "[Unit]\nDescription=Scheduled {action} of {unit} (Dev Services Manager)\n\n\
+         [Timer]\nOnCalendar={spec}\nUnit={stem}.service\n\n\
+         [Install]\nWantedBy=timers.target\n",
+        action = action,
+        unit = systemd_service,
+        spec = calendar_spec,
+        stem = stem,
+    );
+
+    install_root_file(&format!("{}/{}.service", PERSISTENT_UNIT_DIR, stem), &service_contents, password.clone())?;
+    install_root_file(&format!("{}/{}", PERSISTENT_UNIT_DIR, timer_unit), &timer_contents, password.clone())?;
+
+    let reload = daemon_reload(password.clone())?;
+    if !reload.success {
+        return Err(format!("Wrote {} but {}", timer_unit, reload.message).into());
+    }
+
+    let enable_output = execute_sudo_command(&["systemctl", "enable", "--now", &timer_unit], password, true)?;
+    if !enable_output.status.success() {
+        return Err(format!("Failed to enable {}: {}", timer_unit,
+            String::from_utf8_lossy(&enable_output.stderr).trim()).into());
+    }
+
+    log::info!("✅ Scheduled {} of {} via {}", action, systemd_service, timer_unit);
+    let (next, left) = timer_next_elapse(&timer_unit);
+    Ok(ServiceSchedule {
+        service_name,
+        action,
+        calendar_spec,
+        timer_unit,
+        next,
+        left,
+    })
+}
+
+#[tauri::command]
+pub async fn unschedule_service(
+    service_name: String,
+    action: String,
+    password: Option<String>,
+) -> Result<ServiceOperation, CommandError> {
+    validate_service_name(&service_name)?;
+    if !SCHEDULE_ACTIONS.contains(&action.as_str()) {
+        return Err(CommandError::InvalidInput(format!("Unsupported scheduled action '{}', expected start, stop or restart", action)));
+    }
+
+    // The unit may have been uninstalled since it was scheduled, so don't require it to exist
+    let systemd_service = remote_unit_name(&service_name);
+    let stem = schedule_unit_stem(&systemd_service, &action);
+    let timer_path = format!("{}/{}.timer", PERSISTENT_UNIT_DIR, stem);
+    if !std::path::Path::new(&timer_path).exists() {
+        return Err(CommandError::NotFound(format!("No scheduled {} for {}", action, service_name)));
+    }
+
+    log::info!("🗑️ Removing scheduled {} of {}", action, systemd_service);
+    let timer_unit = format!("{}.timer", stem);
+    if let Ok(output) = execute_sudo_command(&["systemctl", "disable", "--now", &timer_unit], password.clone(), true) {
+        if !output.status.success() {
+            log::warn!("⚠️ Failed to disable {}: {}", timer_unit, String::from_utf8_lossy(&output.stderr).trim());
+        }
+    }
+
+    let service_path = format!("{}/{}.service", PERSISTENT_UNIT_DIR, stem);
+    let remove_output = execute_sudo_command(&["rm", "-f", &timer_path, &service_path], password.clone(), true)?;
+    if !remove_output.status.success() {
+        return Ok(ServiceOperation {
+            success: false,
+            message: format!("Failed to remove {}: {}", timer_unit, String::from_utf8_lossy(&remove_output.stderr).trim()),
+            service: None,
+        });
+    }
+
+    let reload = daemon_reload(password)?;
+    Ok(ServiceOperation {
+        success: reload.success,
+        message: if reload.success {
+            format!("Removed scheduled {} of {}", action, service_name)
+        } else {
+            format!("Removed {} but {}", timer_unit, reload.message)
+        },
+        service: None,
+    })
+}
+
+#[tauri::command]
+pub async fn get_schedules() -> Result<Vec<ServiceSchedule>, CommandError> {
+    let entries = match std::fs::read_dir(PERSISTENT_UNIT_DIR) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("Failed to read {}: {}", PERSISTENT_UNIT_DIR, e).into()),
+    };
+
+    let mut schedules = Vec::new();
+    for entry in entries.flatten() {
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        let Some(stem) = file_name
+            .strip_prefix(SCHEDULE_UNIT_PREFIX)
+            .and_then(|_| file_name.strip_suffix(".timer"))
+        else {
+            continue;
+        };
+
+        let timer = std::fs::read_to_string(entry.path()).unwrap_or_default();
+        let service = std::fs::read_to_string(format!("{}/{}.service", PERSISTENT_UNIT_DIR, stem)).unwrap_or_default();
+
+        // ExecStart=<systemctl> <action> <unit>
+        let exec_start = service
+            .lines()
+            .find_map(|line| line.trim().strip_prefix("ExecStart="))
+            .map(|command| command.split_whitespace().collect::<Vec<_>>())
+            .unwrap_or_default();
+        let (Some(action), Some(unit)) = (exec_start.get(1), exec_start.get(2)) else {
+            log::warn!("⚠️ Skipping malformed schedule {}", file_name);
+            continue;
+        };
+        let calendar_spec = timer
+            .lines()
+            .find_map(|line| line.trim().strip_prefix("OnCalendar="))
+            .unwrap_or_default()
+            .to_string();
+
+        let (next, left) = timer_next_elapse(&file_name);
+        schedules.push(ServiceSchedule {
+            service_name: unit.trim_end_matches(".service").to_string(),
+            action: action.to_string(),
+            calendar_spec,
+            timer_unit: file_name.clone(),
+            next,
+            left,
+        });
+    }
+
+    schedules.sort_by(|a, b| a.service_name.cmp(&b.service_name).then_with(|| a.action.cmp(&b.action)));
+    Ok(schedules)
+}
+
+// NEXT and LEFT columns from list-timers for a single timer
+fn timer_next_elapse(timer_unit: &str) -> (Option<String>, Option<String>) {
+    Command::new("systemctl")
+        .args(&["list-timers", "--all", "--no-pager", timer_unit])
+        .output()
+        .ok()
+        .and_then(|output| {
+            parse_timers_output(&String::from_utf8_lossy(&output.stdout))
+                .into_iter()
+                .find(|timer| timer.unit == timer_unit)
+        })
+        .map(|timer| (timer.next, timer.left))
+        .unwrap_or((None, None))
+}
+
+// Stages the contents in a temp file, then installs it into place as root with mode 0644
+fn install_root_file(target_path: &str, contents: &str, password: Option<String>) -> Result<(), String> {
+    let file_name = std::path::Path::new(target_path)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| "unit".to_string());
+    let staged_path = std::env::temp_dir().join(format!("dsm-{}-{}", Uuid::new_v4(), file_name));
+    std::fs::write(&staged_path, contents)
+        .map_err(|e| format!("Failed to stage {}: {}", file_name, e))?;
+    let staged = staged_path.to_string_lossy().to_string();
+
+    let install_output = execute_sudo_command(&["install", "-m", "0644", &staged, target_path], password, true);
+    let _ = std::fs::remove_file(&staged_path);
+    let install_output = install_output?;
+
+    if !install_output.status.success() {
+        let error = String::from_utf8_lossy(&install_output.stderr);
+        return Err(format!("Failed to write {}: {}", target_path, error));
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn write_unit_dropin(service_name: String, contents: String, password: Option<String>) -> Result<ServiceOperation, CommandError> {
+    validate_service_name(&service_name)?;
+
+    log::info!("✏️ Writing drop-in override for service: {}", service_name);
+    let systemd_service = find_service_name(&service_name)?;
+
+    validate_unit_contents(&contents)?;
+
+    let dropin_path = match install_unit_dropin(&systemd_service, "override.conf", &contents, password) {
+        Ok(path) => path,
+        Err(message) => {
+            log::error!("❌ Failed to write drop-in for {}: {}", service_name, message);
+            return Ok(ServiceOperation {
+                success: false,
+                message,
+                service: None,
+            });
+        }
+    };
+
+    log::info!("✅ Drop-in override written to {}", dropin_path);
+    let service = get_service_status_internal(&service_name).await.ok();
+
+    Ok(ServiceOperation {
+        success: true,
+        message: format!("Override for {} written to {}", service_name, dropin_path),
+        service,
+    })
+}
+
+// [Service] directives start_service_transient accepts
+const TRANSIENT_OVERRIDE_DIRECTIVES: &[&str] = &[
+    "Environment", "EnvironmentFile", "ExecStart", "ExecStartPre", "ExecStartPost",
+    "WorkingDirectory", "User", "Group", "Nice", "UMask", "Restart", "RestartSec",
+    "TimeoutStartSec", "TimeoutStopSec", "StandardOutput", "StandardError",
+    "MemoryMax", "CPUQuota", "LimitNOFILE",
+];
+const TRANSIENT_DROPIN_NAME: &str = "zz-transient-override.conf";
+
+fn render_transient_dropin(overrides: &HashMap<String, String>) -> Result<String, String> {
+    let mut keys: Vec<&String> = overrides.keys().collect();
+    keys.sort();
+
+    let mut contents = String::from("[Service]\n");
+    for key in keys {
+        let value = &overrides[key];
+        if !TRANSIENT_OVERRIDE_DIRECTIVES.contains(&key.as_str()) {
+            return Err(format!("Unsupported override directive: {}", key));
+        }
+        if value.contains('\n') || value.contains('\r') {
+            return Err(format!("Override value for {} must be a single line", key));
+        }
+        // ExecStart= accumulates, so it has to be reset before it can be replaced
+        if key.starts_with("Exec") {
+            contents.push_str(&format!("{}=\n", key));
+        }
+        contents.push_str(&format!("{}={}\n", key, value));
+    }
+    Ok(contents)
+}
+
+// Transient drop-ins whose cleanup hasn't run yet; removed on exit so they can't outlive the app
+static PENDING_TRANSIENT_DROPINS: OnceLock<std::sync::Mutex<Vec<String>>> = OnceLock::new();
+
+fn pending_transient_dropins() -> &'static std::sync::Mutex<Vec<String>> {
+    PENDING_TRANSIENT_DROPINS.get_or_init(|| std::sync::Mutex::new(Vec::new()))
+}
+
+// Removes the transient drop-ins whose units are still running when the app exits. The running
+// units keep their overrides, but their next start uses the real definition. Returns how many
+// were removed.
+pub fn remove_pending_transient_dropins_blocking(password: Option<String>) -> usize {
+    let paths = std::mem::take(&mut *pending_transient_dropins().lock().unwrap_or_else(|e| e.into_inner()));
+    let mut removed = 0;
+    for path in paths {
+        match execute_sudo_command(&["rm", "-f", &path], password.clone(), true) {
+            Ok(output) if output.status.success() => removed += 1,
+            Ok(output) => log::error!("❌ Failed to remove {}: {}", path, String::from_utf8_lossy(&output.stderr)),
+            Err(e) => log::error!("❌ Failed to remove {}: {}", path, e),
+        }
+    }
+    if removed > 0 {
+        if let Err(e) = daemon_reload(password) {
+            log::error!("❌ daemon-reload after transient cleanup failed: {}", e);
+        }
+    }
+    removed
+}
+
+// Removes the transient drop-in once the unit stops so the next start uses its real definition
+fn schedule_transient_cleanup(systemd_service: String, dropin_path: String, password: Option<String>) {
+    pending_transient_dropins().lock().unwrap_or_else(|e| e.into_inner()).push(dropin_path.clone());
+    tokio::spawn(async move {
+        // Give the unit a moment to get going before watching for it to stop
+        tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+        loop {
+            match check_service_status(&systemd_service) {
+                Ok(ServiceStatus::Running) | Ok(ServiceStatus::Activating) | Ok(ServiceStatus::Reloading) => {
+                    tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+                }
+                _ => break,
+            }
+        }
+
+        // The exit handler may have removed it already
+        {
+            let mut pending = pending_transient_dropins().lock().unwrap_or_else(|e| e.into_inner());
+            let Some(index) = pending.iter().position(|path| *path == dropin_path) else {
+                return;
+            };
+            pending.remove(index);
+        }
+
+        log::info!("🧹 {} stopped, removing transient overrides {}", systemd_service, dropin_path);
+        match execute_sudo_command(&["rm", "-f", &dropin_path], password.clone(), true) {
+            Ok(output) if output.status.success() => {
+                if let Err(e) = daemon_reload(password) {
+                    log::error!("❌ daemon-reload after transient cleanup failed: {}", e);
+                }
+            }
+            Ok(output) => log::error!("❌ Failed to remove {}: {}", dropin_path, String::from_utf8_lossy(&output.stderr)),
+            Err(e) => log::error!("❌ Failed to remove {}: {}", dropin_path, e),
+        }
+    });
+}
+
+// Starts a service with one-off [Service] overrides. The overrides are written to a
+// runtime drop-in under /run, removed again when the service next stops or the app exits,
+// and never persist across reboots.
+#[tauri::command]
+pub async fn start_service_transient(
+    service_name: String,
+    overrides: HashMap<String, String>,
+    password: Option<String>,
+) -> Result<ServiceOperation, CommandError> {
+    validate_service_name(&service_name)?;
+
+    log::info!("🚀 Starting {} with {} transient overrides", service_name, overrides.len());
+    let systemd_service = find_service_name(&service_name)?;
+
+    if overrides.is_empty() {
+        return Err(CommandError::InvalidInput("No overrides given; use start_service instead".to_string()));
+    }
+    if let Ok(ServiceStatus::Running) = check_service_status(&systemd_service) {
+        return Err(CommandError::InvalidInput(format!("{} is already running; stop it before applying transient overrides", service_name)));
+    }
+
+    let contents = render_transient_dropin(&overrides)?;
+    let dropin_path = match install_unit_dropin_in(RUNTIME_UNIT_DIR, &systemd_service, TRANSIENT_DROPIN_NAME, &contents, password.clone()) {
+        Ok(path) => path,
+        Err(message) => {
+            return Ok(ServiceOperation {
+                success: false,
+                message,
+                service: None,
+            });
+        }
+    };
+
+    let output = execute_sudo_command(&["systemctl", "start", &systemd_service], password.clone(), true)?;
+    if !output.status.success() {
+        let error = String::from_utf8_lossy(&output.stderr).to_string();
+        // Nothing is running with the overrides, so clean up right away
+        let _ = execute_sudo_command(&["rm", "-f", &dropin_path], password.clone(), true);
+        let _ = daemon_reload(password);
+        return Ok(ServiceOperation {
+            success: false,
+            message: format!("Failed to start {}: {}", service_name, error),
+            service: None,
+        });
+    }
+
+    schedule_transient_cleanup(systemd_service, dropin_path, password);
+    record_session_start(&service_name, false);
+
+    Ok(ServiceOperation {
+        success: true,
+        message: format!("{} started with transient overrides (not persisted)", service_name),
+        service: get_service_status_internal(&service_name).await.ok(),
+    })
+}
+
+fn is_valid_env_key(key: &str) -> bool {
+    let mut chars = key.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+// Quotes an assignment for Environment=, escaping quotes, backslashes and % specifiers
+fn quote_env_assignment(key: &str, value: &str) -> String {
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('%', "%%");
+    format!("\"{}={}\"", key, escaped)
+}
+
+// Reads the assignments from the env.conf drop-in we manage, preserving their order
+fn read_env_dropin(systemd_service: &str) -> Vec<(String, String)> {
+    let path = format!("/etc/systemd/system/{}.d/env.conf", systemd_service);
+    let Ok(contents) = std::fs::read_to_string(&path) else {
+        return Vec::new();
+    };
+
+    let mut assignments: Vec<(String, String)> = Vec::new();
+    for line in contents.lines() {
+        let Some(words) = line.trim().strip_prefix("Environment=") else {
+            continue;
+        };
+        for word in split_quoted_words(words) {
+            if let Some((key, value)) = word.split_once('=') {
+                let value = value.replace("%%", "%");
+                match assignments.iter_mut().find(|(k, _)| k == key) {
+                    Some(existing) => existing.1 = value,
+                    None => assignments.push((key.to_string(), value)),
+                }
+            }
+        }
+    }
+    assignments
+}
+
+fn render_env_dropin(assignments: &[(String, String)]) -> String {
+    let mut contents = String::from("[Service]\n");
+    for (key, value) in assignments {
+        contents.push_str(&format!("Environment={}\n", quote_env_assignment(key, value)));
+    }
+    contents
+}
+
+#[tauri::command]
+pub async fn set_service_environment(
+    service_name: String,
+    key: String,
+    value: String,
+    password: Option<String>,
+) -> Result<ServiceOperation, CommandError> {
+    validate_service_name(&service_name)?;
+
+    log::info!("🌿 Setting environment variable {} for service: {}", key, service_name);
+    let systemd_service = find_service_name(&service_name)?;
+
+    if !is_valid_env_key(&key) {
+        return Err(CommandError::InvalidInput(format!("Invalid environment variable name: {}", key)));
+    }
+    // A line break would end the Environment= line and let the rest become a directive of its own
+    if value.contains(['\n', '\r', '\0']) {
+        return Err(CommandError::InvalidInput(format!("Value for {} must be a single line without NUL bytes", key)));
+    }
+
+    let mut assignments = read_env_dropin(&systemd_service);
+    match assignments.iter_mut().find(|(k, _)| *k == key) {
+        Some(existing) => existing.1 = value,
+        None => assignments.push((key.clone(), value)),
+    }
+
+    match install_unit_dropin(&systemd_service, "env.conf", &render_env_dropin(&assignments), password) {
+        Ok(path) => {
+            log::info!("✅ Environment variable {} written to {}", key, path);
+            Ok(ServiceOperation {
+                success: true,
+                message: format!("Set {} for {}; restart the service to apply it", key, service_name),
+                service: get_service_status_internal(&service_name).await.ok(),
+            })
+        }
+        Err(message) => Ok(ServiceOperation {
+            success: false,
+            message,
+            service: None,
+        }),
+    }
+}
+
+#[tauri::command]
+pub async fn unset_service_environment(
+    service_name: String,
+    key: String,
+    password: Option<String>,
+) -> Result<ServiceOperation, CommandError> {
+    validate_service_name(&service_name)?;
+
+    log::info!("🌿 Removing environment variable {} for service: {}", key, service_name);
+    let systemd_service = find_service_name(&service_name)?;
+
+    let mut assignments = read_env_dropin(&systemd_service);
+    let before = assignments.len();
+    assignments.retain(|(k, _)| *k != key);
+    if assignments.len() == before {
+        return Ok(ServiceOperation {
+            success: true,
+            message: format!("{} is not set in the environment drop-in for {}", key, service_name),
+            service: None,
+        });
+    }
+
+    match install_unit_dropin(&systemd_service, "env.conf", &render_env_dropin(&assignments), password) {
+        Ok(path) => {
+            log::info!("✅ Environment variable {} removed from {}", key, path);
+            Ok(ServiceOperation {
+                success: true,
+                message: format!("Unset {} for {}; restart the service to apply it", key, service_name),
+                service: get_service_status_internal(&service_name).await.ok(),
+            })
+        }
+        Err(message) => Ok(ServiceOperation {
+            success: false,
+            message,
+            service: None,
+        }),
+    }
+}
+
+static PACKAGE_MANAGER: OnceLock<Option<PackageManager>> = OnceLock::new();
+
+// Detected once; apt/dpkg first since that's what remove_service was written for, then the
+// rpm based front ends (dnf before the older yum) and pacman
+fn package_manager() -> Option<PackageManager> {
+    *PACKAGE_MANAGER.get_or_init(|| {
+        let has_rpm = find_on_path("rpm").is_some();
+        let manager = if find_on_path("dpkg").is_some() && find_on_path("apt").is_some() {
+            Some(PackageManager::Apt)
+        } else if has_rpm && find_on_path("dnf").is_some() {
+            Some(PackageManager::Dnf)
+        } else if has_rpm && find_on_path("yum").is_some() {
+            Some(PackageManager::Yum)
+        } else if has_rpm && find_on_path("zypper").is_some() {
+            Some(PackageManager::Zypper)
+        } else if find_on_path("pacman").is_some() {
+            Some(PackageManager::Pacman)
+        } else {
+            None
+        };
+
+        match manager {
+            Some(manager) => log::info!("📦 Package manager: {:?}", manager),
+            None => log::warn!("⚠️ No supported package manager found"),
+        }
+        manager
+    })
+}
+
+fn require_package_manager() -> Result<PackageManager, CommandError> {
+    package_manager().ok_or_else(|| {
+        CommandError::BackendUnavailable("No supported package manager (apt, dnf, yum, zypper or pacman) was found".to_string())
+    })
+}
+
+fn command_stdout(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if stdout.is_empty() { None } else { Some(stdout) }
+}
+
+// Every package that claims a file; usrmerge means dpkg may only know /sbin/foo for /usr/sbin/foo
+fn file_owners(manager: PackageManager, file: &str) -> Vec<String> {
+    let mut paths = vec![file.to_string()];
+    if let Some(unmerged) = file.strip_prefix("/usr") {
+        paths.push(unmerged.to_string());
+    }
+    if let Ok(resolved) = std::fs::canonicalize(file) {
+        paths.push(resolved.to_string_lossy().to_string());
+    }
+
+    let owners = paths.iter().find_map(|path| match manager {
+        // "nginx-core: /usr/sbin/nginx", or "pkg-a, pkg-b: path" when shared
+        PackageManager::Apt => command_stdout("dpkg", &["-S", path]).and_then(|owner| {
+            let packages = owner.lines().next()?.split_once(": ")?.0.to_string();
+            Some(
+                packages
+                    .split(", ")
+                    .filter_map(|package| package.split(':').next())
+                    .map(|package| package.trim().to_string())
+                    .collect::<Vec<_>>(),
+            )
+        }),
+        PackageManager::Dnf | PackageManager::Yum | PackageManager::Zypper => command_stdout("rpm", &["-qf", "--queryformat", "%{NAME}\\n", path])
+            .map(|owners| owners.lines().map(|line| line.trim().to_string()).collect()),
+        PackageManager::Pacman => command_stdout("pacman", &["-Qoq", path])
+            .map(|owners| owners.lines().map(|line| line.trim().to_string()).collect()),
+    });
+
+    let mut unique: Vec<String> = Vec::new();
+    for owner in owners.unwrap_or_default() {
+        if !owner.is_empty() && !unique.contains(&owner) {
+            unique.push(owner);
+        }
+    }
+    unique
+}
+
+fn owning_package(manager: PackageManager, file: &str) -> Option<String> {
+    file_owners(manager, file).into_iter().next()
+}
+
+fn installed_package_version(manager: PackageManager, package: &str) -> Option<String> {
+    match manager {
+        PackageManager::Apt => command_stdout("dpkg-query", &["-W", "-f=${Version}", package]),
+        PackageManager::Dnf | PackageManager::Yum | PackageManager::Zypper => {
+            command_stdout("rpm", &["-q", "--queryformat", "%{VERSION}-%{RELEASE}", package])
+        }
+        // "nginx 1.24.0-1"
+        PackageManager::Pacman => command_stdout("pacman", &["-Q", package])
+            .and_then(|installed| installed.split_whitespace().nth(1).map(|version| version.to_string())),
+    }
+}
+
+// Reads what the package cache already knows; refreshing it (apt update) needs root and is
+// left to the user
+fn candidate_package_version(manager: PackageManager, package: &str) -> Option<String> {
+    match manager {
+        PackageManager::Apt => {
+            // "nginx/jammy-updates 1.18.0-6ubuntu14.4 amd64 [upgradable from: 1.18.0-6ubuntu14.3]"
+            let output = Command::new("apt").args(&["list", "--upgradable"]).output().ok()?;
+            let prefix = format!("{}/", package);
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .find(|line| line.starts_with(&prefix))
+                .and_then(|line| line.split_whitespace().nth(1))
+                .map(|version| version.to_string())
+        }
+        PackageManager::Dnf | PackageManager::Yum => {
+            // Exits 100 when updates exist, with lines like "nginx.x86_64  1:1.24.0-1.fc39  updates"
+            let program = if manager == PackageManager::Dnf { "dnf" } else { "yum" };
+            let output = Command::new(program)
+                .args(&["check-update", "-q", "--cacheonly", package])
+                .output()
+                .ok()?;
+            let prefix = format!("{}.", package);
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .find(|line| line.starts_with(&prefix))
+                .and_then(|line| line.split_whitespace().nth(1))
+                .map(|version| version.to_string())
+        }
+        // "v | repo-oss | nginx | 1.21.5-1.1 | 1.24.0-1.1 | x86_64"
+        PackageManager::Zypper => command_stdout("zypper", &["--non-interactive", "--no-refresh", "list-updates"])
+            .and_then(|updates| {
+                updates.lines().find_map(|line| {
+                    let columns: Vec<&str> = line.split('|').map(|column| column.trim()).collect();
+                    if columns.get(2) == Some(&package) {
+                        columns.get(4).map(|version| version.to_string())
+                    } else {
+                        None
+                    }
+                })
+            }),
+        // Against the last synced database: "nginx 1.24.0-1 -> 1.26.0-1"
+        PackageManager::Pacman => command_stdout("pacman", &["-Qu", package])
+            .and_then(|upgrade| upgrade.split_whitespace().nth(3).map(|version| version.to_string())),
+    }
+}
+
+// None when the service can't be tied to a package, e.g. a binary installed by hand
+#[tauri::command]
+pub async fn check_service_update(service_name: String) -> Result<Option<ServiceUpdate>, CommandError> {
+    validate_service_name(&service_name)?;
+
+    if DockerBackend::container_name(&service_name).is_some() {
+        return Ok(None);
+    }
+    let Some(manager) = package_manager() else {
+        return Ok(None);
+    };
+
+    let systemd_service = find_service_name(&service_name)?;
+    let Some(binary) = exec_start_binary(&systemd_service) else {
+        return Ok(None);
+    };
+
+    log::info!("📦 Checking for updates to {} ({})", service_name, binary);
+    let update = tokio::task::spawn_blocking(move || {
+        let package = owning_package(manager, &binary)?;
+        let current_version = installed_package_version(manager, &package)?;
+        let candidate_version = candidate_package_version(manager, &package)
+            .filter(|candidate| *candidate != current_version);
+        Some((package, current_version, candidate_version))
+    })
+    .await
+    .map_err(|e| format!("Update check failed: {}", e))?;
+
+    let Some((package, current_version, candidate_version)) = update else {
+        log::debug!("ℹ️ Could not resolve the package providing {}", service_name);
+        return Ok(None);
+    };
+
+    Ok(Some(ServiceUpdate {
+        service_name,
+        package,
+        package_manager: manager,
+        current_version,
+        update_available: candidate_version.is_some(),
+        candidate_version,
+    }))
+}
+
+// Debian, RPM and Arch package names all fit in this set; anything else is refused before
+// it gets near sudo
+fn validate_package_name(package_name: &str) -> Result<(), CommandError> {
+    let valid = !package_name.is_empty()
+        && package_name.len() <= 128
+        && !package_name.starts_with('-')
+        && package_name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '+' | '-'));
+
+    if valid {
+        Ok(())
+    } else {
+        log::warn!("🚫 Rejected invalid package name: {:?}", package_name);
+        Err(CommandError::InvalidInput(format!("Invalid package name: {:?}", package_name)))
+    }
+}
+
+fn install_args(manager: PackageManager, package: &str) -> Vec<String> {
+    let args: &[&str] = match manager {
+        PackageManager::Apt => &["apt-get", "install", "-y"],
+        PackageManager::Dnf => &["dnf", "install", "-y"],
+        PackageManager::Yum => &["yum", "install", "-y"],
+        PackageManager::Zypper => &["zypper", "--non-interactive", "install"],
+        PackageManager::Pacman => &["pacman", "-S", "--noconfirm", "--needed"],
+    };
+    args.iter().map(|arg| arg.to_string()).chain([package.to_string()]).collect()
+}
+
+// Removal including the package's configuration where the manager supports it
+fn remove_args(manager: PackageManager, package: &str) -> Vec<String> {
+    let args: &[&str] = match manager {
+        PackageManager::Apt => &["apt-get", "remove", "--purge", "-y"],
+        PackageManager::Dnf => &["dnf", "remove", "-y"],
+        PackageManager::Yum => &["yum", "remove", "-y"],
+        PackageManager::Zypper => &["zypper", "--non-interactive", "remove"],
+        PackageManager::Pacman => &["pacman", "-Rns", "--noconfirm"],
+    };
+    args.iter().map(|arg| arg.to_string()).chain([package.to_string()]).collect()
+}
+
+// System units a package installed, e.g. ["nginx.service"]; templates are left out
+fn package_service_units(manager: PackageManager, package: &str) -> Vec<String> {
+    let files = match manager {
+        PackageManager::Apt => command_stdout("dpkg", &["-L", package]),
+        PackageManager::Dnf | PackageManager::Yum | PackageManager::Zypper => command_stdout("rpm", &["-ql", package]),
+        PackageManager::Pacman => command_stdout("pacman", &["-Qlq", package]),
+    };
+
+    let mut units: Vec<String> = Vec::new();
+    for path in files.unwrap_or_default().lines() {
+        if !path.contains("/systemd/system/") || !path.ends_with(".service") {
+            continue;
+        }
+        let Some(unit) = path.rsplit('/').next().filter(|unit| !unit.contains('@')) else {
+            continue;
+        };
+        if !units.iter().any(|known| known == unit) {
+            units.push(unit.to_string());
+        }
+    }
+    units
+}
+
+// Runs a command through sudo -S and emits every output line as it arrives. The password is
+// written and stdin closed before the output is read, so sudo never waits on us while we wait
+// on it. -k makes sudo read the password even with cached credentials, so the line never
+// reaches the command itself, and debconf is kept from prompting on the closed stdin.
+// Returns whether it succeeded and the last stderr lines for the error message, or
+// PermissionDenied when sudo itself refused the password.
+async fn run_sudo_streaming(
+    app_handle: &tauri::AppHandle,
+    args: &[String],
+    password: &str,
+    event_name: &'static str,
+    package: &str,
+) -> Result<(bool, String), CommandError> {
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+    log::debug!("🔧 Executing streamed command: {}", args.join(" "));
+    let mut child = tokio::process::Command::new("sudo")
+        .args(["-S", "-k", "-p", "", "env", "DEBIAN_FRONTEND=noninteractive"])
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| format!("Failed to start {}: {}", args.first().map(String::as_str).unwrap_or("command"), e))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(password.as_bytes()).await
+            .map_err(|e| format!("Failed to write password: {}", e))?;
+        stdin.write_all(b"\n").await
+            .map_err(|e| format!("Failed to write newline: {}", e))?;
+    }
+
+    let stdout = child.stdout.take().ok_or("Failed to capture stdout")?;
+    let stderr = child.stderr.take().ok_or("Failed to capture stderr")?;
+
+    let stdout_task = {
+        let app_handle = app_handle.clone();
+        let package = package.to_string();
+        tokio::spawn(async move {
+            let mut lines = BufReader::new(stdout).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                crate::events::emit_package_progress(&app_handle, event_name, &package, "stdout", &line);
+            }
+        })
+    };
+    let stderr_task = {
+        let app_handle = app_handle.clone();
+        let package = package.to_string();
+        tokio::spawn(async move {
+            let mut tail: std::collections::VecDeque<String> = std::collections::VecDeque::new();
+            let mut lines = BufReader::new(stderr).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                crate::events::emit_package_progress(&app_handle, event_name, &package, "stderr", &line);
+                // sudo's password prompt isn't worth reporting
+                if line.starts_with("[sudo]") {
+                    continue;
+                }
+                if tail.len() == 10 {
+                    tail.pop_front();
+                }
+                tail.push_back(line);
+            }
+            tail.into_iter().collect::<Vec<_>>().join("\n")
+        })
+    };
+
+    let status = child.wait().await
+        .map_err(|e| format!("Failed to wait for {}: {}", args.join(" "), e))?;
+    let _ = stdout_task.await;
+    let stderr_tail = stderr_task.await.unwrap_or_default();
+
+    if !status.success() {
+        if let Some(refusal) = sudo_refusal(&stderr_tail) {
+            return Err(CommandError::PermissionDenied(format!("Authentication failed: {}", refusal)));
+        }
+    }
+    Ok((status.success(), stderr_tail))
+}
+
+#[tauri::command]
+pub async fn install_service(
+    app_handle: tauri::AppHandle,
+    package_name: String,
+    password: String,
+) -> Result<PackageInstall, CommandError> {
+    validate_package_name(&package_name)?;
+
+    let manager = require_package_manager()?;
+
+    log::info!("📦 Installing package {} with {:?}", package_name, manager);
+    let args = install_args(manager, &package_name);
+    let (success, stderr_tail) = run_sudo_streaming(
+        &app_handle,
+        &args,
+        &password,
+        crate::events::INSTALL_PROGRESS_EVENT,
+        &package_name,
+    )
+    .await?;
+
+    if !success {
+        log::error!("❌ Failed to install {}: {}", package_name, stderr_tail);
+        return Ok(PackageInstall {
+            package: package_name.clone(),
+            success: false,
+            message: format!("Failed to install {}: {}", package_name, stderr_tail),
+            services: Vec::new(),
+        });
+    }
+
+    let services = package_service_units(manager, &package_name);
+    log::info!("✅ Installed {} ({} service units)", package_name, services.len());
+    Ok(PackageInstall {
+        message: format!("Successfully installed {}", package_name),
+        package: package_name,
+        success: true,
+        services,
+    })
+}
+
+// The package that shipped the unit file itself. The ExecStart binary can't be used: for
+// services run through python3, java, node or sh it names the interpreter's package, and purging
+// that takes everything depending on it along. Only a package owning this one unit qualifies.
+fn resolve_service_package(service_name: &str) -> Result<ServicePackage, CommandError> {
+    let manager = require_package_manager()?;
+    let systemd_service = find_service_name(service_name)?;
+
+    let unit_file = get_unit_property(&systemd_service, "FragmentPath")
+        .ok_or_else(|| CommandError::NotFound(format!("No unit file found for {}", service_name)))?;
+
+    let owners = file_owners(manager, &unit_file);
+    let package = match owners.as_slice() {
+        [] => {
+            return Err(CommandError::NotFound(format!(
+                "{} ({}) was not installed by a package", service_name, unit_file
+            )))
+        }
+        [package] => package.clone(),
+        _ => {
+            return Err(CommandError::InvalidInput(format!(
+                "{} is shared by several packages ({}); remove it with the package manager directly",
+                unit_file, owners.join(", ")
+            )))
+        }
+    };
+    validate_package_name(&package)?;
+
+    let other_units: Vec<String> = package_service_units(manager, &package)
+        .into_iter()
+        .filter(|unit| *unit != systemd_service)
+        .collect();
+    if !other_units.is_empty() {
+        return Err(CommandError::InvalidInput(format!(
+            "{} also provides {}; refusing to remove it for {}",
+            package, other_units.join(", "), service_name
+        )));
+    }
+
+    Ok(ServicePackage {
+        service_name: service_name.to_string(),
+        package,
+        package_manager: manager,
+        unit_file,
+    })
+}
+
+// Lets the UI show which package remove_service would purge and have the user confirm it
+#[tauri::command]
+pub async fn get_service_package(service_name: String) -> Result<ServicePackage, CommandError> {
+    validate_service_name(&service_name)?;
+    resolve_service_package(&service_name)
+}
+
+// `package` must be what get_service_package returned; removing anything else is refused
+#[tauri::command]
+pub async fn remove_service(
+    app_handle: tauri::AppHandle,
+    service_name: String,
+    package: String,
+    password: String,
+) -> Result<String, CommandError> {
+    // Validate service name for security
+    validate_service_name(&service_name)?;
+
+    let resolved = resolve_service_package(&service_name)?;
+    if resolved.package != package {
+        return Err(CommandError::InvalidInput(format!(
+            "{} is provided by {}, not {}; confirm the package before removing it",
+            service_name, resolved.package, package
+        )));
+    }
+    let manager = resolved.package_manager;
+    let systemd_service = find_service_name(&service_name)?;
+    log::info!("🗑️ Removing package {} for service {} with {:?}", package, service_name, manager);
+
+    // First stop the service; a refused password stops here, before anything is purged
+    let output = execute_sudo_command(&["systemctl", "stop", &systemd_service], Some(password.clone()), true)?;
+    if !output.status.success() {
+        return Err(format!("Failed to stop {}: {}", service_name,
+            String::from_utf8_lossy(&output.stderr).trim()).into());
+    }
+
+    // Disable the service; the package removal goes ahead even if this fails
+    let output = execute_sudo_command(&["systemctl", "disable", &systemd_service], Some(password.clone()), true)?;
+    if !output.status.success() {
+        log::warn!("⚠️ Failed to disable {}: {}", systemd_service, String::from_utf8_lossy(&output.stderr).trim());
+    }
+
+    // Remove the package, streaming its output so a long purge doesn't look frozen
+    let (success, stderr_tail) = run_sudo_streaming(
+        &app_handle,
+        &remove_args(manager, &package),
+        &password,
+        crate::events::REMOVE_PROGRESS_EVENT,
+        &package,
+    )
+    .await?;
+
+    if success {
+        log::info!("✅ Removed {} ({})", package, service_name);
+        Ok(format!("Successfully removed {} ({})", package, service_name))
+    } else {
+        Err(format!("Removal failed: {}", stderr_tail).into())
+    }
+}
+
+
+
+fn get_service_category(service_name: &str) -> &'static str {
+    let lower_name = service_name.to_lowercase();
+    
+    // Web Servers & Proxies
+    if lower_name.contains("nginx") || lower_name.contains("apache") || lower_name.contains("httpd") || 
+       lower_name.contains("lighttpd") || lower_name.contains("caddy") || lower_name.contains("traefik") ||
+       lower_name.contains("haproxy") || lower_name.contains("envoy") || lower_name.contains("kong") ||
+       lower_name.contains("openresty") || lower_name.contains("cherokee") {
+        return "Web Server";
+    }
+    
+    // Database Services
+    if lower_name.contains("mysql") || lower_name.contains("postgresql") || lower_name.contains("mariadb") || 
+       lower_name.contains("sqlite") || lower_name.contains("oracle") || lower_name.contains("sqlserver") ||
+       lower_name.contains("cockroachdb") || lower_name.contains("timescaledb") || lower_name.contains("clickhouse") {
+        return "Database";
+    }
+    
+    // NoSQL Databases
+    if lower_name.contains("mongodb") || lower_name.contains("cassandra") || lower_name.contains("couchdb") ||
+       lower_name.contains("neo4j") || lower_name.contains("redis") || lower_name.contains("memcached") ||
+       lower_name.contains("hazelcast") || lower_name.contains("ignite") {
+        return "NoSQL Database";
+    }
+    
+    // Cache Services
+    if lower_name.contains("cache") || lower_name.contains("redis") || lower_name.contains("memcache") ||
+       lower_name.contains("hazelcast") || lower_name.contains("ignite") {
+        return "Cache";
+    }
+    
+    // Container & Orchestration
+    if lower_name.contains("docker") || lower_name.contains("containerd") || lower_name.contains("kubernetes") ||
+       lower_name.contains("rancher") || lower_name.contains("nomad") || lower_name.contains("mesos") ||
+       lower_name.contains("swarm") || lower_name.contains("podman") || lower_name.contains("buildah") ||
+       lower_name.contains("skopeo") || lower_name.contains("cri-o") {
+        return "Container";
+    }
+    
+    // Message Brokers & Queues
+    if lower_name.contains("kafka") || lower_name.contains("rabbitmq") || lower_name.contains("activemq") ||
+       lower_name.contains("artemis") || lower_name.contains("pulsar") || lower_name.contains("nats") ||
+       lower_name.contains("mosquitto") || lower_name.contains("emqx") || lower_name.contains("vernemq") ||
+       lower_name.contains("mq") || lower_name.contains("queue") {
+        return "Message Broker";
+    }
+    
+    // Monitoring & Observability
+    if lower_name.contains("prometheus") || lower_name.contains("grafana") || lower_name.contains("jaeger") ||
+       lower_name.contains("zipkin") || lower_name.contains("datadog") || lower_name.contains("newrelic") ||
+       lower_name.contains("splunk") || lower_name.contains("logstash") || lower_name.contains("filebeat") ||
+       lower_name.contains("metricbeat") || lower_name.contains("packetbeat") || lower_name.contains("heartbeat") ||
+       lower_name.contains("monitor") || lower_name.contains("metric") {
+        return "Monitoring";
+    }
+    
+    // CI/CD & Development
+    if lower_name.contains("jenkins") || lower_name.contains("gitlab") || lower_name.contains("github-runner") ||
+       lower_name.contains("teamcity") || lower_name.contains("bamboo") || lower_name.contains("drone") ||
+       lower_name.contains("concourse") || lower_name.contains("gocd") || lower_name.contains("spinnaker") ||
+       lower_name.contains("argocd") || lower_name.contains("tekton") {
+        return "CI/CD";
+    }
+    
+    // Security & Identity
+    if lower_name.contains("keycloak") || lower_name.contains("ldap") || lower_name.contains("kerberos") ||
+       lower_name.contains("saml") || lower_name.contains("oauth") || lower_name.contains("cert-manager") ||
+       lower_name.contains("letsencrypt") || lower_name.contains("fail2ban") || lower_name.contains("clamav") ||
+       lower_name.contains("snort") || lower_name.contains("vault") {
+        return "Security";
+    }
+    
+    // Network & Communication
+    if lower_name.contains("openvpn") || lower_name.contains("wireguard") || lower_name.contains("strongswan") ||
+       lower_name.contains("freeradius") || lower_name.contains("dnsmasq") || lower_name.contains("bind9") ||
+       lower_name.contains("unbound") || lower_name.contains("dhcpd") || lower_name.contains("ntpd") ||
+       lower_name.contains("chronyd") || lower_name.contains("dns") || lower_name.contains("vpn") {
+        return "Network";
+    }
+    
+    // Storage & Backup
+    if lower_name.contains("minio") || lower_name.contains("ceph") || lower_name.contains("glusterfs") ||
+       lower_name.contains("nfs") || lower_name.contains("samba") || lower_name.contains("rsync") ||
+       lower_name.contains("duplicati") || lower_name.contains("restic") || lower_name.contains("borg") ||
+       lower_name.contains("rclone") || lower_name.contains("backup") || lower_name.contains("sync") {
+        return "Storage";
+    }
+    
+    // Search & Analytics
+    if lower_name.contains("elasticsearch") || lower_name.contains("solr") || lower_name.contains("opensearch") ||
+       lower_name.contains("meilisearch") || lower_name.contains("typesense") || lower_name.contains("algolia") ||
+       lower_name.contains("sphinx") || lower_name.contains("lucene") || lower_name.contains("kibana") ||
+       lower_name.contains("search") {
+        return "Search";
+    }
+    
+    // Runtime & Application Servers
+    if lower_name.contains("tomcat") || lower_name.contains("jetty") || lower_name.contains("wildfly") ||
+       lower_name.contains("glassfish") || lower_name.contains("weblogic") || lower_name.contains("websphere") ||
+       lower_name.contains("jboss") || lower_name.contains("spring") || lower_name.contains("django") ||
+       lower_name.contains("rails") || lower_name.contains("nodejs") || lower_name.contains("node") {
+        return "Runtime";
+    }
+    
+    // Queue & Stream Processing
+    if lower_name.contains("storm") || lower_name.contains("flink") || lower_name.contains("spark") ||
+       lower_name.contains("beam") || lower_name.contains("heron") || lower_name.contains("samza") ||
+       lower_name.contains("flume") || lower_name.contains("sqoop") || lower_name.contains("oozie") ||
+       lower_name.contains("airflow") || lower_name.contains("hive") {
+        return "Stream Processing";
+    }
+    
+    // Machine Learning & AI
+    if lower_name.contains("tensorflow") || lower_name.contains("pytorch") || lower_name.contains("jupyter") ||
+       lower_name.contains("mlflow") || lower_name.contains("kubeflow") || lower_name.contains("tensorboard") ||
+       lower_name.contains("wandb") || lower_name.contains("dvc") || lower_name.contains("polyaxon") ||
+       lower_name.contains("sagemaker") || lower_name.contains("ai") || lower_name.contains("ml") {
+        return "Machine Learning";
+    }
+    
+    // Media & Content
+    if lower_name.contains("ffmpeg") || lower_name.contains("gstreamer") || lower_name.contains("vlc") ||
+       lower_name.contains("plex") || lower_name.contains("emby") || lower_name.contains("jellyfin") ||
+       lower_name.contains("kodi") || lower_name.contains("sonarr") || lower_name.contains("radarr") ||
+       lower_name.contains("lidarr") || lower_name.contains("media") {
+        return "Media";
+    }
+    
+    // Development Tools
+    if lower_name.contains("vscode") || lower_name.contains("intellij") || lower_name.contains("eclipse") ||
+       lower_name.contains("atom") || lower_name.contains("sublime") || lower_name.contains("vim") ||
+       lower_name.contains("emacs") || lower_name.contains("neovim") || lower_name.contains("helix") ||
+       lower_name.contains("kakoune") || lower_name.contains("editor") || lower_name.contains("ide") {
+        return "Development Tools";
+    }
+    
+    // System Services
+    if lower_name.contains("cron") || lower_name.contains("systemd") || lower_name.contains("udev") ||
+       lower_name.contains("dbus") || lower_name.contains("avahi") || lower_name.contains("cups") ||
+       lower_name.contains("bluetooth") || lower_name.contains("wifi") || lower_name.contains("network") ||
+       lower_name.contains("firewall") || lower_name.contains("ssh") || lower_name.contains("telnet") ||
+       lower_name.contains("ftp") || lower_name.contains("sftp") || lower_name.contains("rsyslog") ||
+       lower_name.contains("syslog") || lower_name.contains("logrotate") || lower_name.contains("anacron") ||
+       lower_name.contains("atd") || lower_name.contains("systemd-timesyncd") || lower_name.contains("time") ||
+       lower_name.contains("ntp") || lower_name.contains("chrony") || lower_name.contains("log") ||
+       lower_name.contains("print") || lower_name.contains("audio") || lower_name.contains("pulse") ||
+       lower_name.contains("mail") || lower_name.contains("smtp") || lower_name.contains("imap") ||
+       lower_name.contains("pop") || lower_name.contains("update") || lower_name.contains("upgrade") ||
+       lower_name.contains("apt") || lower_name.contains("package") {
+        return "System";
+    }
+    
+    // Version Control
+    if lower_name.contains("git") {
+        return "Version Control";
+    }
+    
+    // Programming Languages
+    if lower_name.contains("python") || lower_name.contains("ruby") || lower_name.contains("php") ||
+       lower_name.contains("java") || lower_name.contains("go") || lower_name.contains("rust") ||
+       lower_name.contains("c++") || lower_name.contains("c#") || lower_name.contains("dotnet") {
+        return "Programming Language";
+    }
+    
+    // Default category
+    "Other"
+}
+
+// Database-related commands
+use crate::app_log::AppLogEntry;
+use crate::config_watch::{ConfigWatch, ConfigWatches};
+use crate::database::{Database, Host, MetricsSnapshot, SnoozedAlert, TrackedService};
+use crate::docker::DockerBackend;
+use crate::events::ServiceSubscriptions;
+use crate::ssh::SshBackend;
+use tauri::State;
+
+#[tauri::command]
+pub async fn get_tracked_services(
+    db: State<'_, Arc<Mutex<Database>>>,
+) -> Result<Vec<TrackedService>, CommandError> {
+    let db = db.lock().await;
+    Ok(db.get_tracked_services()
+        .await
+        .map_err(|e| format!("Failed to get tracked services: {}", e))?)
+}
+
+#[tauri::command]
+pub async fn add_service_to_tracking(
+    db: State<'_, Arc<Mutex<Database>>>,
+    name: String,
+    display_name: String,
+    description: Option<String>,
+    category: String,
+) -> Result<TrackedService, CommandError> {
+    // Tracked names are polled by the monitor, so they must be safe to pass to systemctl
+    validate_service_name(&name)?;
+
+    let db = db.lock().await;
+    let service = db.add_tracked_service(&name, &display_name, description.as_deref(), &category)
+        .await
+        .map_err(|e| format!("Failed to add service to tracking: {}", e))?;
+
+    crate::events::wake_monitor();
+    Ok(service)
+}
+
+#[tauri::command]
+pub async fn remove_service_from_tracking(
+    db: State<'_, Arc<Mutex<Database>>>,
+    name: String,
+) -> Result<(), CommandError> {
+    validate_service_name(&name)?;
+
+    let db = db.lock().await;
+    Ok(db.remove_tracked_service(&name)
+        .await
+        .map_err(|e| format!("Failed to remove service from tracking: {}", e))?)
+}
+
+#[tauri::command]
+pub async fn get_tracked_service(
+    db: State<'_, Arc<Mutex<Database>>>,
+    name: String,
+) -> Result<Option<TrackedService>, CommandError> {
+    let db = db.lock().await;
+    Ok(db.get_tracked_service(&name)
+        .await
+        .map_err(|e| format!("Failed to get tracked service: {}", e))?)
+}
+
+#[tauri::command]
+pub async fn is_service_tracked(
+    db: State<'_, Arc<Mutex<Database>>>,
+    name: String,
+) -> Result<bool, CommandError> {
+    validate_service_name(&name)?;
+
+    let db = db.lock().await;
+    Ok(db.is_service_tracked(&name)
+        .await
+        .map_err(|e| format!("Failed to check if service is tracked: {}", e))?)
+}
+
+#[tauri::command]
+pub async fn update_service_tracking_status(
+    db: State<'_, Arc<Mutex<Database>>>,
+    name: String,
+    enabled: bool,
+) -> Result<(), CommandError> {
+    validate_service_name(&name)?;
+
+    let db = db.lock().await;
+    db.update_service_enabled(&name, enabled)
+        .await
+        .map_err(|e| format!("Failed to update service tracking status: {}", e))?;
+
+    if enabled {
+        crate::events::wake_monitor();
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn update_tracked_service(
+    app_handle: tauri::AppHandle,
+    db: State<'_, Arc<Mutex<Database>>>,
+    name: String,
+    display_name: Option<String>,
+    description: Option<String>,
+    category: Option<String>,
+) -> Result<TrackedService, CommandError> {
+    if display_name.as_deref().is_some_and(|display_name| display_name.trim().is_empty()) {
+        return Err(CommandError::InvalidInput("Display name cannot be empty".to_string()));
+    }
+
+    let db = db.lock().await;
+    let service = db
+        .update_tracked_service(&name, display_name.as_deref(), description.as_deref(), category.as_deref())
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::RowNotFound => format!("Service '{}' is not tracked", name),
+            e => format!("Failed to update tracked service: {}", e),
+        })?;
+
+    crate::events::emit_database_updated(&app_handle, "service_updated", &name);
+    Ok(service)
+}
+
+#[tauri::command]
+pub async fn reorder_tracked_services(
+    db: State<'_, Arc<Mutex<Database>>>,
+    ordered_names: Vec<String>,
+) -> Result<(), CommandError> {
+    let db = db.lock().await;
+    Ok(db.reorder_tracked_services(&ordered_names)
+        .await
+        .map_err(|e| format!("Failed to reorder tracked services: {}", e))?)
+}
+
+#[tauri::command]
+pub async fn set_service_note(
+    app_handle: tauri::AppHandle,
+    db: State<'_, Arc<Mutex<Database>>>,
+    name: String,
+    note: String,
+) -> Result<(), CommandError> {
+    validate_service_name(&name)?;
+
+    // An empty note clears it
+    let note = Some(note).filter(|note| !note.trim().is_empty());
+
+    let db = db.lock().await;
+    db.set_service_note(&name, note.as_deref())
+        .await
+        .map_err(|e| format!("Failed to set service note: {}", e))?;
+
+    crate::events::emit_database_updated(&app_handle, "note_updated", &name);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn set_log_flood_threshold(
+    app_handle: tauri::AppHandle,
+    db: State<'_, Arc<Mutex<Database>>>,
+    service_name: String,
+    threshold: Option<f64>,
+) -> Result<(), CommandError> {
+    validate_service_name(&service_name)?;
+
+    // None falls back to the monitor's default threshold
+    if let Some(threshold) = threshold {
+        if !threshold.is_finite() || threshold <= 0.0 {
+            return Err(CommandError::InvalidInput(format!("Log flood threshold must be a positive rate, got {}", threshold)));
+        }
+    }
+
+    let db = db.lock().await;
+    db.set_log_flood_threshold(&service_name, threshold)
+        .await
+        .map_err(|e| format!("Failed to set log flood threshold: {}", e))?;
+
+    crate::events::emit_database_updated(&app_handle, "log_flood_threshold_updated", &service_name);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn set_service_critical(
+    app_handle: tauri::AppHandle,
+    db: State<'_, Arc<Mutex<Database>>>,
+    service_name: String,
+    critical: bool,
+) -> Result<(), CommandError> {
+    validate_service_name(&service_name)?;
+
+    let db = db.lock().await;
+    db.set_service_critical(&service_name, critical)
+        .await
+        .map_err(|e| format!("Failed to set critical flag: {}", e))?;
+
+    crate::events::emit_database_updated(&app_handle, "critical_updated", &service_name);
+    Ok(())
+}
+
+// How far from a requested time a recorded sample may be and still stand in for it
+const DEFAULT_METRICS_DIFF_TOLERANCE_SECS: i64 = 300;
+// Larger tolerances are clamped to a day
+const MAX_METRICS_DIFF_TOLERANCE_SECS: i64 = 86_400;
+
+async fn nearest_metrics_snapshot(
+    db: &Database,
+    service_name: &str,
+    at: DateTime<Utc>,
+    tolerance: chrono::Duration,
+) -> Result<MetricsSnapshot, CommandError> {
+    db.nearest_metrics_snapshot(service_name, at, tolerance)
+        .await
+        .map_err(|e| CommandError::Internal(format!("Failed to read metrics history: {}", e)))?
+        .ok_or_else(|| CommandError::NotFound(format!(
+            "No metrics snapshot for {} within {}s of {}", service_name, tolerance.num_seconds(), at.to_rfc3339())))
+}
+
+#[tauri::command]
+pub async fn diff_service_metrics(
+    db: State<'_, Arc<Mutex<Database>>>,
+    service_name: String,
+    from_timestamp: String,
+    to_timestamp: String,
+    tolerance_secs: Option<i64>,
+) -> Result<MetricsDiff, CommandError> {
+    validate_service_name(&service_name)?;
+
+    let parse = |timestamp: &str| {
+        DateTime::parse_from_rfc3339(timestamp)
+            .map(|parsed| parsed.with_timezone(&Utc))
+            .map_err(|e| CommandError::InvalidInput(format!("Invalid timestamp '{}': {}", timestamp, e)))
+    };
+    let from = parse(&from_timestamp)?;
+    let to = parse(&to_timestamp)?;
+    if from >= to {
+        return Err(CommandError::InvalidInput("from_timestamp must be before to_timestamp".to_string()));
+    }
+    let tolerance = chrono::Duration::seconds(
+        tolerance_secs.unwrap_or(DEFAULT_METRICS_DIFF_TOLERANCE_SECS).clamp(0, MAX_METRICS_DIFF_TOLERANCE_SECS));
+
+    let db = db.lock().await;
+    let from_snapshot = nearest_metrics_snapshot(&db, &service_name, from, tolerance).await?;
+    let to_snapshot = nearest_metrics_snapshot(&db, &service_name, to, tolerance).await?;
+    drop(db);
+
+    // With a wide tolerance both ends can land on the same sample, which would diff to nothing
+    if from_snapshot.recorded_at >= to_snapshot.recorded_at {
+        return Err(CommandError::NotFound(format!(
+            "No two distinct metrics snapshots for {} between {} and {}",
+            service_name, from.to_rfc3339(), to.to_rfc3339())));
+    }
+
+    let delta = |before: u64, after: u64| after as i64 - before as i64;
+    Ok(MetricsDiff {
+        service_name,
+        elapsed_secs: (to_snapshot.recorded_at - from_snapshot.recorded_at).num_seconds(),
+        cpu_change: to_snapshot.cpu_usage - from_snapshot.cpu_usage,
+        memory_change: delta(from_snapshot.memory_usage, to_snapshot.memory_usage),
+        disk_read_delta: delta(from_snapshot.disk_read, to_snapshot.disk_read),
+        disk_write_delta: delta(from_snapshot.disk_write, to_snapshot.disk_write),
+        network_in_delta: delta(from_snapshot.network_in, to_snapshot.network_in),
+        network_out_delta: delta(from_snapshot.network_out, to_snapshot.network_out),
+        from: from_snapshot,
+        to: to_snapshot,
+    })
+}
+
+#[tauri::command]
+pub async fn snooze_service_alerts(
+    db: State<'_, Arc<Mutex<Database>>>,
+    service_name: String,
+    until_timestamp: String,
+) -> Result<SnoozedAlert, CommandError> {
+    validate_service_name(&service_name)?;
+
+    let until = DateTime::parse_from_rfc3339(&until_timestamp)
+        .map_err(|e| format!("Invalid snooze timestamp '{}': {}", until_timestamp, e))?
+        .with_timezone(&Utc);
+    if until <= Utc::now() {
+        return Err(CommandError::InvalidInput("Snooze time must be in the future".to_string()));
+    }
+
+    let db = db.lock().await;
+    Ok(db.snooze_alerts(&service_name, until)
+        .await
+        .map_err(|e| format!("Failed to snooze alerts: {}", e))?)
+}
+
+#[tauri::command]
+pub async fn unsnooze_service_alerts(
+    db: State<'_, Arc<Mutex<Database>>>,
+    service_name: String,
+) -> Result<(), CommandError> {
+    validate_service_name(&service_name)?;
+
+    let db = db.lock().await;
+    let removed = db.unsnooze_alerts(&service_name)
+        .await
+        .map_err(|e| format!("Failed to unsnooze alerts: {}", e))?;
+
+    if removed {
+        Ok(())
+    } else {
+        Err(CommandError::NotFound(format!("Alerts for {} are not snoozed", service_name)))
+    }
+}
+
+#[tauri::command]
+pub async fn get_snoozed_services(
+    db: State<'_, Arc<Mutex<Database>>>,
+) -> Result<Vec<SnoozedAlert>, CommandError> {
+    let db = db.lock().await;
+    Ok(db.get_snoozed_alerts()
+        .await
+        .map_err(|e| format!("Failed to get snoozed services: {}", e))?)
+}
+
+#[tauri::command]
+pub async fn get_tracked_services_by_tag(
+    db: State<'_, Arc<Mutex<Database>>>,
+    tag: String,
+) -> Result<Vec<TrackedService>, CommandError> {
+    let db = db.lock().await;
+    Ok(db.get_tracked_services_by_tag(&tag)
+        .await
+        .map_err(|e| format!("Failed to get tracked services by tag: {}", e))?)
+}
+
+#[tauri::command]
+pub async fn add_service_tag(
+    db: State<'_, Arc<Mutex<Database>>>,
+    service_name: String,
+    tag: String,
+) -> Result<Vec<String>, CommandError> {
+    validate_service_name(&service_name)?;
+
+    if crate::database::normalize_tag(&tag).is_empty() {
+        return Err(CommandError::InvalidInput("Tag cannot be empty".to_string()));
+    }
+
+    let db = db.lock().await;
+    // Tags belong to tracked services and are removed with them, so an untracked name would leave an orphan
+    let tracked = db.is_service_tracked(&service_name)
+        .await
+        .map_err(|e| format!("Failed to check tracking status: {}", e))?;
+    if !tracked {
+        return Err(CommandError::NotFound(format!("{} is not a tracked service", service_name)));
+    }
+    db.add_tag(&service_name, &tag)
+        .await
+        .map_err(|e| format!("Failed to add tag: {}", e))?;
+    Ok(db.get_tags(&service_name)
+        .await
+        .map_err(|e| format!("Failed to get tags: {}", e))?)
+}
+
+#[tauri::command]
+pub async fn remove_service_tag(
+    db: State<'_, Arc<Mutex<Database>>>,
+    service_name: String,
+    tag: String,
+) -> Result<Vec<String>, CommandError> {
+    validate_service_name(&service_name)?;
+
+    let db = db.lock().await;
+    db.remove_tag(&service_name, &tag)
+        .await
+        .map_err(|e| format!("Failed to remove tag: {}", e))?;
+    Ok(db.get_tags(&service_name)
+        .await
+        .map_err(|e| format!("Failed to get tags: {}", e))?)
+}
+
+#[tauri::command]
+pub async fn get_service_tags(
+    db: State<'_, Arc<Mutex<Database>>>,
+    service_name: String,
+) -> Result<Vec<String>, CommandError> {
+    validate_service_name(&service_name)?;
+
+    let db = db.lock().await;
+    Ok(db.get_tags(&service_name)
+        .await
+        .map_err(|e| format!("Failed to get tags: {}", e))?)
+}
+
+// app_settings key holding the user's service prefix blocklist as a JSON array
+const SERVICE_BLOCKLIST_SETTING: &str = "service_blocklist";
+
+// Low-level plumbing that would only clutter the service list; the old per-unit
+// systemd-journal*/systemd-logind/... entries were all covered by "systemd-"
+const DEFAULT_SERVICE_BLOCKLIST: [&str; 4] = ["systemd-", "dbus-", "session-", "user-"];
+
+// Falls back to the default blocklist when none is stored or the stored value is unreadable
+async fn load_service_blocklist(db: &Arc<Mutex<Database>>) -> Vec<String> {
+    let stored = db.lock().await.get_app_setting(SERVICE_BLOCKLIST_SETTING).await;
+    match stored {
+        Ok(Some(value)) => serde_json::from_str(&value).unwrap_or_else(|e| {
+            log::warn!("⚠️ Ignoring invalid service blocklist setting: {}", e);
+            DEFAULT_SERVICE_BLOCKLIST.iter().map(|p| p.to_string()).collect()
+        }),
+        Ok(None) => DEFAULT_SERVICE_BLOCKLIST.iter().map(|p| p.to_string()).collect(),
+        Err(e) => {
+            log::warn!("⚠️ Failed to load service blocklist, using defaults: {}", e);
+            DEFAULT_SERVICE_BLOCKLIST.iter().map(|p| p.to_string()).collect()
+        }
+    }
+}
+
+// Template units are always hidden; everything else is matched against the prefix blocklist
+fn is_hidden_system_service(name: &str, blocklist: &[String]) -> bool {
+    name.contains('@') || blocklist.iter().any(|prefix| name.starts_with(prefix.as_str()))
+}
+
+// systemctl arguments shared by the local and SSH backends
+const LIST_UNIT_STATES_ARGS: [&str; 7] = ["systemctl", "list-units", "--all", "--type=service", "--no-legend", "--plain", "--no-pager"];
+const LIST_UNIT_FILES_ARGS: [&str; 5] = ["systemctl", "list-unit-files", "--type=service", "--no-pager", "--plain"];
+// Unit names are appended; systemctl prints one blank-line separated block per unit
+const SHOW_ACTIVE_ENTER_ARGS: [&str; 8] = [
+    "systemctl", "show", "-p", "Id", "-p", "ActiveEnterTimestamp", "-p", "ActiveEnterTimestampMonotonic",
+];
+
+// Which systemd manager a unit belongs to: the system one or the calling user's
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum ServiceScope {
+    System,
+    User,
+}
+
+// Runs one of the shared argument lists against the system or the user manager
+fn run_systemctl_in(scope: ServiceScope, args: &[&str]) -> std::io::Result<std::process::Output> {
+    let mut command = Command::new(args[0]);
+    if scope == ServiceScope::User {
+        command.arg("--user");
+    }
+    command.args(&args[1..]).output()
+}
+
+// Load/active/sub states of every service systemd currently has loaded, keyed by unit name
+pub fn list_service_unit_states() -> HashMap<String, (String, String, String)> {
+    list_service_unit_states_in(ServiceScope::System)
+}
+
+fn list_service_unit_states_in(scope: ServiceScope) -> HashMap<String, (String, String, String)> {
+    match run_systemctl_in(scope, &LIST_UNIT_STATES_ARGS) {
+        Ok(output) => parse_service_unit_states(&String::from_utf8_lossy(&output.stdout)),
+        Err(e) => {
+            log::warn!("⚠️ Failed to list unit states: {}", e);
+            HashMap::new()
+        }
+    }
+}
+
+fn parse_service_unit_states(stdout: &str) -> HashMap<String, (String, String, String)> {
+    stdout
+        .lines()
+        .filter_map(|line| {
+            // UNIT LOAD ACTIVE SUB DESCRIPTION
+            let mut fields = line.split_whitespace();
+            let unit = fields.next()?;
+            let load = fields.next()?;
+            let active = fields.next()?;
+            let sub = fields.next()?;
+            Some((unit.to_string(), (load.to_string(), active.to_string(), sub.to_string())))
+        })
+        .collect()
+}
+
+// Units whose start time is worth looking up
+fn active_units(unit_states: &HashMap<String, (String, String, String)>) -> Vec<&str> {
+    unit_states
+        .iter()
+        .filter(|(_, (_, active_state, _))| active_state == "active")
+        .map(|(unit, _)| unit.as_str())
+        .collect()
+}
+
+// Last start timestamp and uptime of every active unit in one systemctl call
+fn list_active_enter_times(
+    scope: ServiceScope,
+    unit_states: &HashMap<String, (String, String, String)>,
+) -> HashMap<String, (String, Option<u64>)> {
+    let units = active_units(unit_states);
+    if units.is_empty() {
+        return HashMap::new();
+    }
+
+    let system_uptime = std::fs::read_to_string("/proc/uptime")
+        .ok()
+        .and_then(|uptime| parse_proc_uptime(&uptime));
+
+    let mut args = SHOW_ACTIVE_ENTER_ARGS.to_vec();
+    args.extend(units);
+    match run_systemctl_in(scope, &args) {
+        Ok(output) => parse_active_enter_times(&String::from_utf8_lossy(&output.stdout), system_uptime),
+        Err(e) => {
+            log::warn!("⚠️ Failed to get unit start times: {}", e);
+            HashMap::new()
+        }
+    }
+}
+
+fn parse_proc_uptime(contents: &str) -> Option<f64> {
+    contents.split_whitespace().next()?.parse().ok()
+}
+
+// The monotonic timestamp counts microseconds since boot, so uptime is the system
+// uptime minus that; this avoids parsing the localized ActiveEnterTimestamp
+fn parse_active_enter_times(stdout: &str, system_uptime: Option<f64>) -> HashMap<String, (String, Option<u64>)> {
+    let mut times = HashMap::new();
+
+    for block in stdout.split("\n\n") {
+        let properties: HashMap<&str, &str> = block
+            .lines()
+            .filter_map(|line| line.split_once('='))
+            .collect();
+        let Some(unit) = properties.get("Id").filter(|id| !id.is_empty()) else {
+            continue;
+        };
+
+        let started = properties
+            .get("ActiveEnterTimestamp")
+            .map(|timestamp| timestamp.trim())
+            .filter(|timestamp| !timestamp.is_empty() && *timestamp != "n/a")
+            .map(|timestamp| timestamp.to_string());
+        let Some(started) = started else {
+            continue;
+        };
+
+        let uptime_secs = properties
+            .get("ActiveEnterTimestampMonotonic")
+            .and_then(|usec| usec.trim().parse::<u64>().ok())
+            .filter(|usec| *usec > 0)
+            .zip(system_uptime)
+            .map(|(usec, uptime)| (uptime - usec as f64 / 1_000_000.0).max(0.0) as u64);
+
+        times.insert(unit.to_string(), (started, uptime_secs));
+    }
+
+    times
+}
+
+// Lists installed service unit files as (unit, enablement state) pairs
+pub fn list_service_unit_files() -> Result<Vec<(String, String)>, String> {
+    require_systemctl()?;
+
+    let output = Command::new(LIST_UNIT_FILES_ARGS[0])
+        .args(&LIST_UNIT_FILES_ARGS[1..])
+        .output()
+        .map_err(|e| format!("Failed to list services: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("Failed to get services: {}", 
+            String::from_utf8_lossy(&output.stderr)));
+    }
+
+    Ok(parse_service_unit_files(&String::from_utf8_lossy(&output.stdout)))
+}
+
+fn parse_service_unit_files(stdout: &str) -> Vec<(String, String)> {
+    stdout
+        .lines()
+        .filter_map(|line| {
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            if parts.len() >= 2 && parts[0].ends_with(".service") {
+                Some((parts[0].to_string(), parts[1].to_string()))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+// Connects to the host behind host_id; None means the command targets the local machine
+async fn remote_backend(
+    db: &Arc<Mutex<Database>>,
+    host_id: Option<i64>,
+) -> Result<Option<SshBackend>, CommandError> {
+    let Some(host_id) = host_id else {
+        return Ok(None);
+    };
+
+    let host = db
+        .lock()
+        .await
+        .get_host(host_id)
+        .await
+        .map_err(|e| format!("Failed to load host: {}", e))?
+        .ok_or_else(|| CommandError::NotFound(format!("Host {} not found", host_id)))?;
+
+    run_blocking(move || Ok(SshBackend::connect(&host)?)).await.map(Some)
+}
+
+// libssh2 calls block until the remote answers, so connecting and every remote command run on
+// the blocking pool instead of stalling the async workers
+async fn run_blocking<T, F>(work: F) -> Result<T, CommandError>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T, CommandError> + Send + 'static,
+{
+    tokio::task::spawn_blocking(work)
+        .await
+        .map_err(|e| CommandError::Internal(format!("Remote task failed: {}", e)))?
+}
+
+// Remote hosts can't be searched with find_service_name, so bare names default to .service
+fn remote_unit_name(service_name: &str) -> String {
+    if has_unit_suffix(service_name) {
+        service_name.to_string()
+    } else {
+        format!("{}.service", service_name)
+    }
+}
+
+fn remote_system_services(backend: &SshBackend, blocklist: &[String]) -> Result<Vec<serde_json::Value>, CommandError> {
+    let unit_files = backend.run(&LIST_UNIT_FILES_ARGS)?;
+    if !unit_files.success() {
+        return Err(CommandError::Internal(format!("Failed to get services: {}", unit_files.stderr)));
+    }
+    let unit_states = parse_service_unit_states(&backend.run(&LIST_UNIT_STATES_ARGS)?.stdout);
+
+    // Uptime has to come from the remote clock, not ours
+    let units = active_units(&unit_states);
+    let start_times = if units.is_empty() {
+        HashMap::new()
+    } else {
+        let system_uptime = backend
+            .run(&["cat", "/proc/uptime"])
+            .ok()
+            .and_then(|output| parse_proc_uptime(&output.stdout));
+        let mut args = SHOW_ACTIVE_ENTER_ARGS.to_vec();
+        args.extend(units);
+        match backend.run(&args) {
+            Ok(output) => parse_active_enter_times(&output.stdout, system_uptime),
+            Err(e) => {
+                log::warn!("⚠️ Failed to get remote unit start times: {}", e);
+                HashMap::new()
+            }
+        }
+    };
+
+    Ok(build_system_services(
+        parse_service_unit_files(&unit_files.stdout),
+        &unit_states,
+        &start_times,
+        blocklist,
+        ServiceScope::System,
+        &CancelToken::default(),
+    ))
+}
+
+fn remote_service_status(backend: &SshBackend, service_name: &str) -> Result<Service, CommandError> {
+    let unit = remote_unit_name(service_name);
+    let output = backend.run(&[
+        "systemctl", "show", &unit,
+        "-p", "LoadState", "-p", "ActiveState", "-p", "SubState", "-p", "UnitFileState",
+        "-p", "Description", "-p", "ActiveEnterTimestamp", "-p", "NRestarts", "-p", "ExecMainStatus",
+        "-p", "Result",
+    ])?;
+    if !output.success() {
+        return Err(CommandError::Internal(format!("Failed to check status: {}", output.stderr)));
+    }
+
+    let properties: HashMap<&str, &str> = output
+        .stdout
+        .lines()
+        .filter_map(|line| line.split_once('='))
+        .collect();
+    let property = |name: &str| properties.get(name).copied().filter(|value| !value.is_empty());
+
+    if property("LoadState") == Some("not-found") {
+        return Err(CommandError::NotFound(format!("Service '{}' not found on remote host", service_name)));
+    }
+
+    Ok(service_from_properties(service_name, &unit, property))
+}
+
+// Version of a remote unit's binary, probed the same way as probe_binary_version but bounded
+// by timeout(1) on the remote side
+fn remote_service_version(backend: &SshBackend, service_name: &str) -> Option<String> {
+    let unit = remote_unit_name(service_name);
+    let exec_start = backend.run(&["systemctl", "show", "-p", "ExecStart", "--value", &unit]).ok()?;
+    let binary = exec_start
+        .stdout
+        .split(';')
+        .find_map(|field| field.trim().trim_start_matches('{').trim().strip_prefix("path="))?
+        .trim()
+        .to_string();
+    if !binary.starts_with('/') {
+        return None;
+    }
+
+    let output = backend.run(&["timeout", "3", &binary, "--version"]).ok()?;
+    if !output.success() {
+        return None;
+    }
+    [&output.stdout, &output.stderr]
+        .iter()
+        .find_map(|stream| stream.lines().map(|line| line.trim()).find(|line| !line.is_empty()))
+        .map(|line| line.to_string())
+}
+
+fn compare_services(a: &Service, b: &Service) -> Vec<ServiceFieldDiff> {
+    let fields = [
+        ("status", Some(format!("{:?}", a.status)), Some(format!("{:?}", b.status))),
+        ("enabled", Some(a.enabled.to_string()), Some(b.enabled.to_string())),
+        ("uptime", a.uptime.clone(), b.uptime.clone()),
+        ("description", Some(a.description.clone()), Some(b.description.clone())),
+        ("restart_count", Some(a.restart_count.to_string()), Some(b.restart_count.to_string())),
+        ("last_result", a.last_result.clone(), b.last_result.clone()),
+    ];
+
+    fields
+        .into_iter()
+        .filter(|(_, a_value, b_value)| a_value != b_value)
+        .map(|(field, a_value, b_value)| ServiceFieldDiff {
+            field: field.to_string(),
+            a_value,
+            b_value,
+        })
+        .collect()
+}
+
+// Compares two Service records the caller already has, e.g. from two inventory exports
+#[tauri::command]
+pub async fn diff_services(a: Service, b: Service) -> Result<Vec<ServiceFieldDiff>, CommandError> {
+    Ok(compare_services(&a, &b))
+}
+
+// The service's status and binary version on one host; None is this machine
+async fn service_with_version(
+    db: &Arc<Mutex<Database>>,
+    service_name: &str,
+    host_id: Option<i64>,
+) -> Result<(Service, Option<String>), CommandError> {
+    if let Some(backend) = remote_backend(db, host_id).await? {
+        let service_name = service_name.to_string();
+        return Ok(run_blocking(move || {
+            let service = remote_service_status(&backend, &service_name)?;
+            Ok((service, remote_service_version(&backend, &service_name)))
+        })
+        .await?);
+    }
+
+    let service = get_service_status_internal(service_name).await?;
+    let version = match find_service_name(service_name).ok().and_then(|unit| exec_start_binary(&unit)) {
+        Some(binary) => probe_binary_version(&binary).await,
+        None => None,
+    };
+    Ok((service, version))
+}
+
+// Fetches one service from two hosts and compares them, version included
+#[tauri::command]
+pub async fn diff_service_across_hosts(
+    db: State<'_, Arc<Mutex<Database>>>,
+    service_name: String,
+    host_a: Option<i64>,
+    host_b: Option<i64>,
+) -> Result<Vec<ServiceFieldDiff>, CommandError> {
+    validate_service_name(&service_name)?;
+
+    let (a, a_version) = service_with_version(&db, &service_name, host_a).await?;
+    let (b, b_version) = service_with_version(&db, &service_name, host_b).await?;
+    let mut diffs = compare_services(&a, &b);
+    if a_version != b_version {
+        diffs.push(ServiceFieldDiff {
+            field: "version".to_string(),
+            a_value: a_version,
+            b_value: b_version,
+        });
+    }
+
+    log::info!("🔍 {} differs in {} fields between hosts {:?} and {:?}", service_name, diffs.len(), host_a, host_b);
+    Ok(diffs)
+}
+
+// Runs systemctl start/stop/restart on a remote host, reported like local operations
+fn remote_operation(backend: &SshBackend, service_name: &str, action: &str, done: &str) -> Result<ServiceOperation, CommandError> {
+    let unit = remote_unit_name(service_name);
+    let output = backend.run_privileged(&["systemctl", action, &unit])?;
+
+    if output.success() {
+        log::info!("✅ Remote service {} {}", service_name, done);
+        Ok(ServiceOperation {
+            success: true,
+            message: format!("{} {} successfully", service_name, done),
+            service: remote_service_status(backend, service_name).ok(),
+        })
+    } else {
+        log::error!("❌ Failed to {} remote service {}: {}", action, service_name, output.stderr);
+        Ok(ServiceOperation {
+            success: false,
+            message: format!("Failed to {} {}: {}", action, service_name, output.stderr.trim()),
+            service: None,
+        })
+    }
+}
+
+#[tauri::command]
+pub async fn get_hosts(
+    db: State<'_, Arc<Mutex<Database>>>,
+) -> Result<Vec<Host>, CommandError> {
+    let db = db.lock().await;
+    Ok(db.get_hosts()
+        .await
+        .map_err(|e| format!("Failed to get hosts: {}", e))?)
+}
+
+#[tauri::command]
+pub async fn add_host(
+    db: State<'_, Arc<Mutex<Database>>>,
+    name: String,
+    address: String,
+    user: String,
+    port: Option<u16>,
+) -> Result<Host, CommandError> {
+    let (name, address, user) = (name.trim(), address.trim(), user.trim());
+    if name.is_empty() || address.is_empty() || user.is_empty() {
+        return Err(CommandError::InvalidInput("Host name, address and user are required".to_string()));
     }
-    
-    let output_length = combined_output.len();
-    log::debug!("📄 Command output length: {} characters", output_length);
-    
-    let result = TerminalCommand {
-        command,
-        output: combined_output,
-        exit_code: output.status.code().unwrap_or(-1),
-        timestamp,
-        duration_ms: duration.as_millis() as u64,
-    };
-    
-    log::info!("✅ Terminal command completed in {}ms", result.duration_ms);
-    Ok(result)
+
+    let db = db.lock().await;
+    Ok(db.add_host(name, address, user, port.unwrap_or(22))
+        .await
+        .map_err(|e| format!("Failed to add host: {}", e))?)
 }
 
 #[tauri::command]
-pub async fn get_current_directory() -> Result<String, String> {
-    Ok(std::env::current_dir()
-        .map_err(|e| format!("Failed to get current directory: {}", e))?
-        .to_string_lossy()
-        .to_string())
+pub async fn remove_host(
+    db: State<'_, Arc<Mutex<Database>>>,
+    host_id: i64,
+) -> Result<(), CommandError> {
+    let db = db.lock().await;
+    Ok(db.remove_host(host_id)
+        .await
+        .map_err(|e| format!("Failed to remove host: {}", e))?)
 }
 
 #[tauri::command]
-pub async fn get_service_info(service_name: String) -> Result<serde_json::Value, String> {
-    let systemd_service = find_service_name(&service_name)?;
-    
-    // Try to get version information from the service
-    let mut info = serde_json::json!({
-        "name": service_name,
-        "systemd_service": systemd_service
-    });
-    
-    // Try to get version from common commands
-    let version_commands = vec![
-        format!("{} --version", service_name),
-        format!("{} -v", service_name),
-        format!("{} version", service_name),
-    ];
-    
-    for cmd in version_commands {
-        if let Ok(output) = Command::new("sh")
-            .arg("-c")
-            .arg(&cmd)
-            .output()
-        {
-            if output.status.success() {
-                let version_output = String::from_utf8_lossy(&output.stdout);
-                if !version_output.trim().is_empty() {
-                    // Extract version number from output
-                    if let Some(version_line) = version_output.lines().next() {
-                        info["version"] = serde_json::Value::String(version_line.trim().to_string());
-                        break;
-                    }
-                }
-            }
+pub async fn get_service_blocklist(
+    db: State<'_, Arc<Mutex<Database>>>,
+) -> Result<Vec<String>, CommandError> {
+    Ok(load_service_blocklist(&db).await)
+}
+
+#[tauri::command]
+pub async fn set_service_blocklist(
+    db: State<'_, Arc<Mutex<Database>>>,
+    prefixes: Vec<String>,
+) -> Result<Vec<String>, CommandError> {
+    let mut blocklist: Vec<String> = Vec::new();
+    for prefix in prefixes {
+        let prefix = prefix.trim().to_string();
+        if !prefix.is_empty() && !blocklist.contains(&prefix) {
+            blocklist.push(prefix);
         }
     }
-    
-    Ok(info)
+
+    let value = serde_json::to_string(&blocklist)
+        .map_err(|e| format!("Failed to serialize service blocklist: {}", e))?;
+    let db = db.lock().await;
+    db.set_app_setting(SERVICE_BLOCKLIST_SETTING, &value)
+        .await
+        .map_err(|e| format!("Failed to save service blocklist: {}", e))?;
+
+    Ok(blocklist)
 }
 
 #[tauri::command]
-pub async fn get_service_ports(service_name: String) -> Result<Vec<String>, String> {
-    let mut ports = Vec::new();
-    
-    // Try to find ports using netstat and lsof
-    let commands = vec![
-        format!("netstat -tulpn | grep {}", service_name),
-        format!("lsof -i -P | grep {}", service_name),
-        format!("ss -tulpn | grep {}", service_name),
-    ];
-    
-    for cmd in commands {
-        if let Ok(output) = Command::new("sh")
-            .arg("-c")
-            .arg(&cmd)
-            .output()
-        {
-            if output.status.success() {
-                let output_str = String::from_utf8_lossy(&output.stdout);
-                for line in output_str.lines() {
-                    // Extract port numbers from the output using simple string parsing
-                    if let Some(colon_pos) = line.find(':') {
-                        let after_colon = &line[colon_pos + 1..];
-                        if let Some(space_pos) = after_colon.find(' ') {
-                            let port_str = &after_colon[..space_pos];
-                            if let Ok(_) = port_str.parse::<u16>() {
-                                let port = port_str.to_string();
-                                if !ports.contains(&port) {
-                                    ports.push(port);
-                                }
-                            }
-                        }
-                    }
-                }
+pub async fn get_all_system_services(
+    db: State<'_, Arc<Mutex<Database>>>,
+    requests: State<'_, PendingRequests>,
+    request_id: Option<String>,
+) -> Result<Vec<serde_json::Value>, CommandError> {
+    let request = RegisteredRequest::new(&requests, request_id);
+    let blocklist = load_service_blocklist(&db).await;
+    collect_system_services(&blocklist, &request.token)
+}
+
+// Metrics collection shells out per service, so only this many run at once
+const INVENTORY_METRICS_CONCURRENCY: usize = 8;
+
+#[tauri::command]
+pub async fn export_service_inventory(
+    db: State<'_, Arc<Mutex<Database>>>,
+    cpu_samples: State<'_, CpuSamples>,
+    path: String,
+    include_metrics: bool,
+    overwrite: Option<bool>,
+) -> Result<InventoryExport, CommandError> {
+    log::info!("💾 Exporting service inventory to {} (metrics: {})", path, include_metrics);
+    let export_path = sanitize_export_path(&path, overwrite.unwrap_or(false)).map_err(CommandError::InvalidInput)?;
+
+    let blocklist = load_service_blocklist(&db).await;
+    let mut services = collect_system_services(&blocklist, &CancelToken::default())?;
+
+    if include_metrics {
+        let permits = Arc::new(tokio::sync::Semaphore::new(INVENTORY_METRICS_CONCURRENCY));
+        let mut tasks = tokio::task::JoinSet::new();
+
+        for (index, service) in services.iter().enumerate() {
+            // Stopped services have no processes to measure; user units aren't visible to find_service_name
+            let running = service.get("status").and_then(|v| v.as_str()) == Some("running");
+            let system_scope = service.get("scope").and_then(|v| v.as_str()) == Some("System");
+            let Some(name) = service.get("name").and_then(|v| v.as_str()).filter(|_| running && system_scope) else {
+                continue;
+            };
+
+            let name = name.to_string();
+            let permits = permits.clone();
+            let cpu_samples = cpu_samples.inner().clone();
+            tasks.spawn(async move {
+                let _permit = permits.acquire_owned().await;
+                // Both run commands and read /proc synchronously, so keep them off the async workers
+                let runtime = tokio::runtime::Handle::current();
+                tokio::task::spawn_blocking(move || {
+                    runtime.block_on(async {
+                        let metrics = service_metrics(name.clone(), &cpu_samples, &CancelToken::default()).await.ok();
+                        let ports = get_service_ports(name).await.unwrap_or_default();
+                        (index, metrics, ports)
+                    })
+                })
+                .await
+            });
+        }
+
+        while let Some(result) = tasks.join_next().await {
+            let Ok(Ok((index, metrics, ports))) = result else {
+                continue;
+            };
+            if let Some(entry) = services[index].as_object_mut() {
+                entry.insert("metrics".to_string(), serde_json::to_value(metrics).unwrap_or_default());
+                entry.insert("ports".to_string(), serde_json::json!(ports));
             }
         }
     }
-    
-    // Limit to first 10 ports to avoid overwhelming the UI
-    ports.truncate(10);
-    Ok(ports)
+
+    let count = services.len();
+    let inventory = serde_json::json!({
+        "generated_at": Utc::now(),
+        "host": local_hostname(),
+        "include_metrics": include_metrics,
+        "services": services,
+    });
+    let contents = serde_json::to_vec_pretty(&inventory)
+        .map_err(|e| format!("Failed to serialize inventory: {}", e))?;
+    std::fs::write(&export_path, &contents)
+        .map_err(|e| format!("Failed to write {}: {}", export_path.display(), e))?;
+
+    log::info!("✅ Exported {} services ({} bytes) to {}", count, contents.len(), export_path.display());
+    Ok(InventoryExport {
+        path: export_path.to_string_lossy().to_string(),
+        services: count,
+        bytes: contents.len() as u64,
+        include_metrics,
+    })
+}
+
+fn local_hostname() -> String {
+    std::fs::read_to_string("/proc/sys/kernel/hostname")
+        .or_else(|_| std::fs::read_to_string("/etc/hostname"))
+        .map(|name| name.trim().to_string())
+        .ok()
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "unknown".to_string())
 }
 
+// System and user services, deduplicated and sorted; shared with the inventory export
+fn collect_system_services(blocklist: &[String], cancel: &CancelToken) -> Result<Vec<serde_json::Value>, CommandError> {
+    let mut services = list_system_services(blocklist, cancel)?;
+    cancel.check()?;
+    services.extend(list_user_services(blocklist, cancel));
+    cancel.check()?;
 
+    // The same unit name can exist in both managers, so entries are keyed by name and scope
+    let name_and_scope = |service: &serde_json::Value| {
+        (
+            service.get("name").and_then(|v| v.as_str()).unwrap_or_default().to_string(),
+            service.get("scope").and_then(|v| v.as_str()).unwrap_or_default().to_string(),
+        )
+    };
+    let mut seen = std::collections::HashSet::new();
+    services.retain(|service| seen.insert(name_and_scope(service)));
+    services.sort_by_key(name_and_scope);
 
+    Ok(services)
+}
 
+fn list_system_services(blocklist: &[String], cancel: &CancelToken) -> Result<Vec<serde_json::Value>, String> {
+    // One batched call instead of a status check per unit
+    let unit_states = list_service_unit_states();
+    let start_times = list_active_enter_times(ServiceScope::System, &unit_states);
+    Ok(build_system_services(list_service_unit_files()?, &unit_states, &start_times, blocklist, ServiceScope::System, cancel))
+}
 
-#[tauri::command]
-pub async fn remove_service(service_name: String, password: String) -> Result<String, String> {
-    // Validate service name for security
-    if !service_name.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_') {
-        return Err("Invalid service name".to_string());
-    }
+// Services of the calling user's systemd manager. There's none to talk to without a user
+// session (e.g. when running as root from a service), in which case this is empty.
+fn list_user_services(blocklist: &[String], cancel: &CancelToken) -> Vec<serde_json::Value> {
+    let unit_files = match run_systemctl_in(ServiceScope::User, &LIST_UNIT_FILES_ARGS) {
+        Ok(output) if output.status.success() => parse_service_unit_files(&String::from_utf8_lossy(&output.stdout)),
+        Ok(output) => {
+            log::debug!("ℹ️ No user session, skipping user services: {}", String::from_utf8_lossy(&output.stderr).trim());
+            return Vec::new();
+        }
+        Err(e) => {
+            log::warn!("⚠️ Failed to list user services: {}", e);
+            return Vec::new();
+        }
+    };
 
-    // First stop the service if it's running
-    let _ = tokio::process::Command::new("sudo")
-        .args(&["-S", "systemctl", "stop", &service_name])
-        .stdin(std::process::Stdio::piped())
-        .output()
-        .await;
+    let unit_states = list_service_unit_states_in(ServiceScope::User);
+    let start_times = list_active_enter_times(ServiceScope::User, &unit_states);
+    build_system_services(unit_files, &unit_states, &start_times, blocklist, ServiceScope::User, cancel)
+}
 
-    // Disable the service
-    let _ = tokio::process::Command::new("sudo")
-        .args(&["-S", "systemctl", "disable", &service_name])
-        .stdin(std::process::Stdio::piped())
-        .output()
-        .await;
+fn build_system_services(
+    unit_files: Vec<(String, String)>,
+    unit_states: &HashMap<String, (String, String, String)>,
+    start_times: &HashMap<String, (String, Option<u64>)>,
+    blocklist: &[String],
+    scope: ServiceScope,
+    cancel: &CancelToken,
+) -> Vec<serde_json::Value> {
+    let mut services = Vec::new();
 
-    // Remove the package
-    let mut cmd = tokio::process::Command::new("sudo");
-    cmd.args(&["-S", "apt", "remove", "--purge", "-y", &service_name]);
-    cmd.stdin(std::process::Stdio::piped());
-    cmd.stdout(std::process::Stdio::piped());
-    cmd.stderr(std::process::Stdio::piped());
+    for (unit, status) in unit_files {
+        // The caller turns a cancelled token into an error, so a partial list never escapes
+        if cancel.is_cancelled() {
+            break;
+        }
 
-    let mut child = cmd.spawn()
-        .map_err(|e| format!("Failed to start removal: {}", e))?;
+        let name = unit.replace(".service", "");
+        let status = status.as_str();
+        
+        // Skip template services and some system services
+        if is_hidden_system_service(&name, blocklist) {
+            continue;
+        }
+        
+        // Units systemd hasn't loaded yet are installed but have never run
+        let (load_state, active_state, sub_state) = match unit_states.get(&unit) {
+            Some(states) => states.clone(),
+            None => {
+                let load_state = if status == "masked" { "masked" } else { "loaded" };
+                (load_state.to_string(), "inactive".to_string(), "dead".to_string())
+            }
+        };
+        let real_status = status_from_states(&active_state, &sub_state);
+        
+        let status_str = match real_status {
+            ServiceStatus::Running => "running",
+            ServiceStatus::Stopped => "stopped",
+            ServiceStatus::Failed => "failed",
+            ServiceStatus::Unknown => "unknown",
+            ServiceStatus::SocketActivated => "socket_activated",
+            ServiceStatus::Activating => "activating",
+            ServiceStatus::Deactivating => "deactivating",
+            ServiceStatus::Reloading => "reloading",
+        };
+        
+        // Get description based on service name
+        let description = generate_service_description(&name);
 
-    // Send password to sudo
-    if let Some(stdin) = child.stdin.as_mut() {
-        use tokio::io::AsyncWriteExt;
-        stdin.write_all(password.as_bytes()).await
-            .map_err(|e| format!("Failed to write password: {}", e))?;
-        stdin.write_all(b"\n").await
-            .map_err(|e| format!("Failed to write newline: {}", e))?;
+        // Only running units have a meaningful start time
+        let (last_started, uptime_secs) = match start_times.get(&unit) {
+            Some((started, uptime)) if active_state == "active" => (Some(started.clone()), *uptime),
+            _ => (None, None),
+        };
+        
+        services.push(serde_json::json!({
+            "name": name,
+            "service_name": unit,
+            "status": status_str,
+            "enabled_status": status,
+            "category": get_service_category(&name),
+            "enabled": status == "enabled" || status == "static",
+            "description": description,
+            "load_state": load_state,
+            "active_state": active_state,
+            "sub_state": sub_state,
+            "last_started": last_started,
+            "uptime_secs": uptime_secs,
+            "scope": scope
+        }));
     }
 
-    let output = child.wait_with_output().await
-        .map_err(|e| format!("Failed to wait for removal: {}", e))?;
+    // Sort by name
+    services.sort_by(|a, b| {
+        a.get("name").unwrap().as_str().unwrap()
+            .cmp(b.get("name").unwrap().as_str().unwrap())
+    });
 
-    if output.status.success() {
-        Ok(format!("Successfully removed {}", service_name))
-    } else {
-        Err(format!("Removal failed: {}", 
-            String::from_utf8_lossy(&output.stderr)))
+    services
+}
+
+#[tauri::command]
+pub async fn subscribe_service(
+    subscriptions: State<'_, ServiceSubscriptions>,
+    service_name: String,
+) -> Result<(), CommandError> {
+    validate_service_name(&service_name)?;
+
+    // Make sure the name resolves before the monitor starts polling it
+    if DockerBackend::container_name(&service_name).is_none() {
+        find_service_name(&service_name)?;
     }
+
+    log::info!("👀 Subscribing to service: {}", service_name);
+    subscriptions.lock().await.insert(service_name);
+    crate::events::wake_monitor();
+    Ok(())
 }
 
+#[tauri::command]
+pub async fn unsubscribe_service(
+    subscriptions: State<'_, ServiceSubscriptions>,
+    service_name: String,
+) -> Result<(), CommandError> {
+    validate_service_name(&service_name)?;
 
+    log::info!("👀 Unsubscribing from service: {}", service_name);
+    subscriptions.lock().await.remove(&service_name);
+    Ok(())
+}
 
-fn get_service_category(service_name: &str) -> &'static str {
-    let lower_name = service_name.to_lowercase();
-    
-    // Web Servers & Proxies
-    if lower_name.contains("nginx") || lower_name.contains("apache") || lower_name.contains("httpd") || 
-       lower_name.contains("lighttpd") || lower_name.contains("caddy") || lower_name.contains("traefik") ||
-       lower_name.contains("haproxy") || lower_name.contains("envoy") || lower_name.contains("kong") ||
-       lower_name.contains("openresty") || lower_name.contains("cherokee") {
-        return "Web Server";
-    }
-    
-    // Database Services
-    if lower_name.contains("mysql") || lower_name.contains("postgresql") || lower_name.contains("mariadb") || 
-       lower_name.contains("sqlite") || lower_name.contains("oracle") || lower_name.contains("sqlserver") ||
-       lower_name.contains("cockroachdb") || lower_name.contains("timescaledb") || lower_name.contains("clickhouse") {
-        return "Database";
-    }
-    
-    // NoSQL Databases
-    if lower_name.contains("mongodb") || lower_name.contains("cassandra") || lower_name.contains("couchdb") ||
-       lower_name.contains("neo4j") || lower_name.contains("redis") || lower_name.contains("memcached") ||
-       lower_name.contains("hazelcast") || lower_name.contains("ignite") {
-        return "NoSQL Database";
-    }
-    
-    // Cache Services
-    if lower_name.contains("cache") || lower_name.contains("redis") || lower_name.contains("memcache") ||
-       lower_name.contains("hazelcast") || lower_name.contains("ignite") {
-        return "Cache";
-    }
-    
-    // Container & Orchestration
-    if lower_name.contains("docker") || lower_name.contains("containerd") || lower_name.contains("kubernetes") ||
-       lower_name.contains("rancher") || lower_name.contains("nomad") || lower_name.contains("mesos") ||
-       lower_name.contains("swarm") || lower_name.contains("podman") || lower_name.contains("buildah") ||
-       lower_name.contains("skopeo") || lower_name.contains("cri-o") {
-        return "Container";
-    }
-    
-    // Message Brokers & Queues
-    if lower_name.contains("kafka") || lower_name.contains("rabbitmq") || lower_name.contains("activemq") ||
-       lower_name.contains("artemis") || lower_name.contains("pulsar") || lower_name.contains("nats") ||
-       lower_name.contains("mosquitto") || lower_name.contains("emqx") || lower_name.contains("vernemq") ||
-       lower_name.contains("mq") || lower_name.contains("queue") {
-        return "Message Broker";
-    }
-    
-    // Monitoring & Observability
-    if lower_name.contains("prometheus") || lower_name.contains("grafana") || lower_name.contains("jaeger") ||
-       lower_name.contains("zipkin") || lower_name.contains("datadog") || lower_name.contains("newrelic") ||
-       lower_name.contains("splunk") || lower_name.contains("logstash") || lower_name.contains("filebeat") ||
-       lower_name.contains("metricbeat") || lower_name.contains("packetbeat") || lower_name.contains("heartbeat") ||
-       lower_name.contains("monitor") || lower_name.contains("metric") {
-        return "Monitoring";
-    }
-    
-    // CI/CD & Development
-    if lower_name.contains("jenkins") || lower_name.contains("gitlab") || lower_name.contains("github-runner") ||
-       lower_name.contains("teamcity") || lower_name.contains("bamboo") || lower_name.contains("drone") ||
-       lower_name.contains("concourse") || lower_name.contains("gocd") || lower_name.contains("spinnaker") ||
-       lower_name.contains("argocd") || lower_name.contains("tekton") {
-        return "CI/CD";
-    }
-    
-    // Security & Identity
-    if lower_name.contains("keycloak") || lower_name.contains("ldap") || lower_name.contains("kerberos") ||
-       lower_name.contains("saml") || lower_name.contains("oauth") || lower_name.contains("cert-manager") ||
-       lower_name.contains("letsencrypt") || lower_name.contains("fail2ban") || lower_name.contains("clamav") ||
-       lower_name.contains("snort") || lower_name.contains("vault") {
-        return "Security";
-    }
-    
-    // Network & Communication
-    if lower_name.contains("openvpn") || lower_name.contains("wireguard") || lower_name.contains("strongswan") ||
-       lower_name.contains("freeradius") || lower_name.contains("dnsmasq") || lower_name.contains("bind9") ||
-       lower_name.contains("unbound") || lower_name.contains("dhcpd") || lower_name.contains("ntpd") ||
-       lower_name.contains("chronyd") || lower_name.contains("dns") || lower_name.contains("vpn") {
-        return "Network";
-    }
-    
-    // Storage & Backup
-    if lower_name.contains("minio") || lower_name.contains("ceph") || lower_name.contains("glusterfs") ||
-       lower_name.contains("nfs") || lower_name.contains("samba") || lower_name.contains("rsync") ||
-       lower_name.contains("duplicati") || lower_name.contains("restic") || lower_name.contains("borg") ||
-       lower_name.contains("rclone") || lower_name.contains("backup") || lower_name.contains("sync") {
-        return "Storage";
+#[tauri::command]
+pub async fn watch_service_config(
+    app_handle: tauri::AppHandle,
+    watches: State<'_, ConfigWatches>,
+    service_name: String,
+    path: String,
+) -> Result<String, CommandError> {
+    validate_service_name(&service_name)?;
+
+    if DockerBackend::container_name(&service_name).is_none() {
+        find_service_name(&service_name)?;
     }
-    
-    // Search & Analytics
-    if lower_name.contains("elasticsearch") || lower_name.contains("solr") || lower_name.contains("opensearch") ||
-       lower_name.contains("meilisearch") || lower_name.contains("typesense") || lower_name.contains("algolia") ||
-       lower_name.contains("sphinx") || lower_name.contains("lucene") || lower_name.contains("kibana") ||
-       lower_name.contains("search") {
-        return "Search";
+
+    let watch = ConfigWatch::start(app_handle, service_name.clone(), std::path::Path::new(&path))?;
+    let watched = watch.path.display().to_string();
+
+    // Watching again replaces the previous path; dropping it stops the old watcher
+    if let Some(previous) = watches.lock().await.insert(service_name.clone(), watch) {
+        log::info!("👀 Replaced config watch on {} for {}", previous.path.display(), service_name);
     }
-    
-    // Runtime & Application Servers
-    if lower_name.contains("tomcat") || lower_name.contains("jetty") || lower_name.contains("wildfly") ||
-       lower_name.contains("glassfish") || lower_name.contains("weblogic") || lower_name.contains("websphere") ||
-       lower_name.contains("jboss") || lower_name.contains("spring") || lower_name.contains("django") ||
-       lower_name.contains("rails") || lower_name.contains("nodejs") || lower_name.contains("node") {
-        return "Runtime";
+
+    Ok(watched)
+}
+
+#[tauri::command]
+pub async fn unwatch_service_config(
+    watches: State<'_, ConfigWatches>,
+    service_name: String,
+) -> Result<(), CommandError> {
+    validate_service_name(&service_name)?;
+
+    match watches.lock().await.remove(&service_name) {
+        Some(watch) => {
+            log::info!("🛑 Stopped watching {} for {}", watch.path.display(), service_name);
+            Ok(())
+        }
+        None => Err(CommandError::NotFound(format!("No config watch for {}", service_name))),
     }
-    
-    // Queue & Stream Processing
-    if lower_name.contains("storm") || lower_name.contains("flink") || lower_name.contains("spark") ||
-       lower_name.contains("beam") || lower_name.contains("heron") || lower_name.contains("samza") ||
-       lower_name.contains("flume") || lower_name.contains("sqoop") || lower_name.contains("oozie") ||
-       lower_name.contains("airflow") || lower_name.contains("hive") {
-        return "Stream Processing";
+}
+
+// A single scheme://host[:port] origin; a wildcard would let any page read the events
+fn validate_origin(origin: &str) -> Result<(), CommandError> {
+    let valid = ["http://", "https://", "tauri://"].iter().any(|scheme| {
+        origin.strip_prefix(scheme).is_some_and(|authority| {
+            !authority.is_empty()
+                && authority
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | ':' | '[' | ']'))
+        })
+    });
+
+    if valid {
+        Ok(())
+    } else {
+        Err(CommandError::InvalidInput(format!("Invalid allowed origin: {:?}", origin)))
     }
-    
-    // Machine Learning & AI
-    if lower_name.contains("tensorflow") || lower_name.contains("pytorch") || lower_name.contains("jupyter") ||
-       lower_name.contains("mlflow") || lower_name.contains("kubeflow") || lower_name.contains("tensorboard") ||
-       lower_name.contains("wandb") || lower_name.contains("dvc") || lower_name.contains("polyaxon") ||
-       lower_name.contains("sagemaker") || lower_name.contains("ai") || lower_name.contains("ml") {
-        return "Machine Learning";
+}
+
+#[tauri::command]
+pub async fn start_event_stream(
+    db: State<'_, Arc<Mutex<Database>>>,
+    port: u16,
+    host: Option<String>,
+    allowed_origin: Option<String>,
+) -> Result<String, CommandError> {
+    // Loopback by default; exposing events to the network has to be asked for
+    let host = host.unwrap_or_else(|| "127.0.0.1".to_string());
+    if let Some(origin) = &allowed_origin {
+        validate_origin(origin)?;
     }
-    
-    // Media & Content
-    if lower_name.contains("ffmpeg") || lower_name.contains("gstreamer") || lower_name.contains("vlc") ||
-       lower_name.contains("plex") || lower_name.contains("emby") || lower_name.contains("jellyfin") ||
-       lower_name.contains("kodi") || lower_name.contains("sonarr") || lower_name.contains("radarr") ||
-       lower_name.contains("lidarr") || lower_name.contains("media") {
-        return "Media";
+
+    #[cfg(feature = "event-stream")]
+    {
+        Ok(crate::event_stream::start(&host, port, allowed_origin.as_deref(), db.inner().clone()).await?)
     }
-    
-    // Development Tools
-    if lower_name.contains("vscode") || lower_name.contains("intellij") || lower_name.contains("eclipse") ||
-       lower_name.contains("atom") || lower_name.contains("sublime") || lower_name.contains("vim") ||
-       lower_name.contains("emacs") || lower_name.contains("neovim") || lower_name.contains("helix") ||
-       lower_name.contains("kakoune") || lower_name.contains("editor") || lower_name.contains("ide") {
-        return "Development Tools";
+
+    #[cfg(not(feature = "event-stream"))]
+    {
+        let _ = (db, allowed_origin);
+        log::warn!("⚠️ Event stream requested on {}:{} but the event-stream feature is disabled", host, port);
+        Err("This build was compiled without the event-stream feature".to_string().into())
     }
-    
-    // System Services
-    if lower_name.contains("cron") || lower_name.contains("systemd") || lower_name.contains("udev") ||
-       lower_name.contains("dbus") || lower_name.contains("avahi") || lower_name.contains("cups") ||
-       lower_name.contains("bluetooth") || lower_name.contains("wifi") || lower_name.contains("network") ||
-       lower_name.contains("firewall") || lower_name.contains("ssh") || lower_name.contains("telnet") ||
-       lower_name.contains("ftp") || lower_name.contains("sftp") || lower_name.contains("rsyslog") ||
-       lower_name.contains("syslog") || lower_name.contains("logrotate") || lower_name.contains("anacron") ||
-       lower_name.contains("atd") || lower_name.contains("systemd-timesyncd") || lower_name.contains("time") ||
-       lower_name.contains("ntp") || lower_name.contains("chrony") || lower_name.contains("log") ||
-       lower_name.contains("print") || lower_name.contains("audio") || lower_name.contains("pulse") ||
-       lower_name.contains("mail") || lower_name.contains("smtp") || lower_name.contains("imap") ||
-       lower_name.contains("pop") || lower_name.contains("update") || lower_name.contains("upgrade") ||
-       lower_name.contains("apt") || lower_name.contains("package") {
-        return "System";
+}
+
+#[tauri::command]
+pub async fn get_monitor_all(
+    db: State<'_, Arc<Mutex<Database>>>,
+) -> Result<bool, CommandError> {
+    let db = db.lock().await;
+    Ok(db.get_app_setting(crate::events::MONITOR_ALL_SETTING)
+        .await
+        .map(|value| value.as_deref() == Some("true"))
+        .map_err(|e| format!("Failed to get monitor_all setting: {}", e))?)
+}
+
+#[tauri::command]
+pub async fn set_monitor_all(
+    db: State<'_, Arc<Mutex<Database>>>,
+    enabled: bool,
+) -> Result<(), CommandError> {
+    let db = db.lock().await;
+    db.set_app_setting(crate::events::MONITOR_ALL_SETTING, if enabled { "true" } else { "false" })
+        .await
+        .map_err(|e| format!("Failed to set monitor_all setting: {}", e))?;
+
+    if enabled {
+        crate::events::wake_monitor();
     }
-    
-    // Version Control
-    if lower_name.contains("git") {
-        return "Version Control";
+    Ok(())
+}
+
+// One row of the status report
+struct ReportRow {
+    name: String,
+    status: ServiceStatus,
+    enabled: bool,
+    uptime: String,
+    last_event: String,
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn render_markdown_report(summary: &SystemSummary, rows: &[ReportRow]) -> String {
+    let cell = |text: &str| text.replace('|', "\\|");
+    let mut report = String::new();
+
+    report.push_str("# Service Status Report\n\n");
+    report.push_str(&format!("_Generated {}_\n\n", summary.timestamp.format("%Y-%m-%d %H:%M:%S UTC")));
+    report.push_str("## System\n\n");
+    report.push_str(&format!("- **CPU:** {:.1}%\n", summary.cpu_usage));
+    report.push_str(&format!("- **Memory:** {} / {}\n", format_bytes(summary.memory_used), format_bytes(summary.memory_total)));
+    report.push_str(&format!("- **Load average:** {:.2} {:.2} {:.2}\n", summary.load_average_1, summary.load_average_5, summary.load_average_15));
+    report.push_str(&format!("- **Uptime:** {}\n", format_duration(summary.uptime_seconds)));
+    report.push_str(&format!("- **Services:** {} running, {} stopped, {} failed\n\n",
+        summary.services_running, summary.services_stopped, summary.services_failed));
+
+    report.push_str(&format!("## Tracked Services ({})\n\n", rows.len()));
+    report.push_str("| Service | Status | Enabled | Uptime | Last event |\n");
+    report.push_str("|---|---|---|---|---|\n");
+    for row in rows {
+        report.push_str(&format!("| {} | {:?} | {} | {} | {} |\n",
+            cell(&row.name),
+            row.status,
+            if row.enabled { "yes" } else { "no" },
+            cell(&row.uptime),
+            cell(&row.last_event)));
     }
-    
-    // Programming Languages
-    if lower_name.contains("python") || lower_name.contains("ruby") || lower_name.contains("php") ||
-       lower_name.contains("java") || lower_name.contains("go") || lower_name.contains("rust") ||
-       lower_name.contains("c++") || lower_name.contains("c#") || lower_name.contains("dotnet") {
-        return "Programming Language";
+
+    report
+}
+
+fn render_html_report(summary: &SystemSummary, rows: &[ReportRow]) -> String {
+    let mut report = String::new();
+
+    report.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Service Status Report</title>\n</head>\n<body>\n");
+    report.push_str("<h1>Service Status Report</h1>\n");
+    report.push_str(&format!("<p><em>Generated {}</em></p>\n", summary.timestamp.format("%Y-%m-%d %H:%M:%S UTC")));
+    report.push_str("<h2>System</h2>\n<ul>\n");
+    report.push_str(&format!("<li><strong>CPU:</strong> {:.1}%</li>\n", summary.cpu_usage));
+    report.push_str(&format!("<li><strong>Memory:</strong> {} / {}</li>\n", format_bytes(summary.memory_used), format_bytes(summary.memory_total)));
+    report.push_str(&format!("<li><strong>Load average:</strong> {:.2} {:.2} {:.2}</li>\n", summary.load_average_1, summary.load_average_5, summary.load_average_15));
+    report.push_str(&format!("<li><strong>Uptime:</strong> {}</li>\n", format_duration(summary.uptime_seconds)));
+    report.push_str(&format!("<li><strong>Services:</strong> {} running, {} stopped, {} failed</li>\n</ul>\n",
+        summary.services_running, summary.services_stopped, summary.services_failed));
+
+    report.push_str(&format!("<h2>Tracked Services ({})</h2>\n<table>\n", rows.len()));
+    report.push_str("<tr><th>Service</th><th>Status</th><th>Enabled</th><th>Uptime</th><th>Last event</th></tr>\n");
+    for row in rows {
+        report.push_str(&format!("<tr><td>{}</td><td>{:?}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+            escape_html(&row.name),
+            row.status,
+            if row.enabled { "yes" } else { "no" },
+            escape_html(&row.uptime),
+            escape_html(&row.last_event)));
     }
-    
-    // Default category
-    "Other"
+    report.push_str("</table>\n</body>\n</html>\n");
+
+    report
 }
 
-// Database-related commands
-use crate::database::{Database, TrackedService};
-use std::sync::Arc;
-use tokio::sync::Mutex;
-use tauri::State;
+#[tauri::command]
+pub async fn generate_status_report(
+    db: State<'_, Arc<Mutex<Database>>>,
+    format: ReportFormat,
+    output_path: Option<String>,
+    overwrite: Option<bool>,
+) -> Result<String, CommandError> {
+    log::info!("📝 Generating {:?} status report", format);
+
+    // Validate the destination before doing the slow collection work
+    let export_path = match &output_path {
+        Some(path) => Some(sanitize_export_path(path, overwrite.unwrap_or(false))?),
+        None => None,
+    };
+
+    let tracked = {
+        let db = db.lock().await;
+        db.get_tracked_services()
+            .await
+            .map_err(|e| format!("Failed to get tracked services: {}", e))?
+    };
+    let summary = get_system_summary().await?;
+
+    let mut rows = Vec::with_capacity(tracked.len());
+    for tracked_service in &tracked {
+        let row = match get_service_status_internal(&tracked_service.name).await {
+            Ok(service) => {
+                // The last state change is the most useful "what happened" for systemd units
+                let last_event = find_service_name(&tracked_service.name)
+                    .ok()
+                    .and_then(|unit| get_unit_property(&unit, "StateChangeTimestamp"))
+                    .filter(|timestamp| !timestamp.is_empty())
+                    .map(|timestamp| match &service.last_result {
+                        Some(result) if result != "success" => format!("{} ({})", timestamp, result),
+                        _ => timestamp,
+                    })
+                    .or_else(|| service.last_result.clone())
+                    .unwrap_or_else(|| "-".to_string());
+
+                ReportRow {
+                    name: tracked_service.display_name.clone(),
+                    status: service.status,
+                    enabled: service.enabled,
+                    uptime: service.uptime.unwrap_or_else(|| "-".to_string()),
+                    last_event,
+                }
+            }
+            Err(e) => ReportRow {
+                name: tracked_service.display_name.clone(),
+                status: ServiceStatus::Unknown,
+                enabled: false,
+                uptime: "-".to_string(),
+                last_event: e.to_string(),
+            },
+        };
+        rows.push(row);
+    }
+
+    // Failed services go first; the sort is stable so the tracked order is otherwise kept
+    rows.sort_by_key(|row| row.status != ServiceStatus::Failed);
+
+    let report = match format {
+        ReportFormat::Markdown => render_markdown_report(&summary, &rows),
+        ReportFormat::Html => render_html_report(&summary, &rows),
+    };
+
+    if let Some(export_path) = export_path {
+        std::fs::write(&export_path, &report)
+            .map_err(|e| format!("Failed to write {}: {}", export_path.display(), e))?;
+        log::info!("✅ Status report written to {}", export_path.display());
+    }
+
+    Ok(report)
+}
 
 #[tauri::command]
-pub async fn get_tracked_services(
+pub async fn backup_database(
     db: State<'_, Arc<Mutex<Database>>>,
-) -> Result<Vec<TrackedService>, String> {
+    path: String,
+    overwrite: Option<bool>,
+) -> Result<crate::database::DatabaseBackup, CommandError> {
+    let backup_path = sanitize_export_path(&path, overwrite.unwrap_or(false))?;
+    // VACUUM INTO refuses to write over an existing file
+    if backup_path.exists() {
+        std::fs::remove_file(&backup_path)
+            .map_err(|e| format!("Failed to replace {}: {}", backup_path.display(), e))?;
+    }
+
     let db = db.lock().await;
-    db.get_tracked_services()
+    Ok(db.backup(&backup_path)
         .await
-        .map_err(|e| format!("Failed to get tracked services: {}", e))
+        .map_err(|e| format!("Failed to back up database: {}", e))?)
 }
 
 #[tauri::command]
-pub async fn add_service_to_tracking(
+pub async fn restore_database(
+    app_handle: tauri::AppHandle,
     db: State<'_, Arc<Mutex<Database>>>,
-    name: String,
-    display_name: String,
-    description: Option<String>,
-    category: String,
-) -> Result<TrackedService, String> {
+    path: String,
+) -> Result<crate::database::DatabaseBackup, CommandError> {
+    let source = std::path::PathBuf::from(path.trim());
+    if !source.is_file() {
+        return Err(CommandError::NotFound(format!("Backup file not found: {}", source.display())));
+    }
+
+    let restored = {
+        let mut db = db.lock().await;
+        db.restore(&source).await?
+    };
+
+    crate::events::emit_database_updated(&app_handle, "restored", "");
+    // The restored database may track services the idle monitor doesn't know about
+    crate::events::wake_monitor();
+    Ok(restored)
+}
+
+#[tauri::command]
+pub async fn check_database_integrity(
+    db: State<'_, Arc<Mutex<Database>>>,
+) -> Result<crate::database::DatabaseIntegrity, CommandError> {
     let db = db.lock().await;
-    db.add_tracked_service(&name, &display_name, description.as_deref(), &category)
+    Ok(db.check_integrity()
         .await
-        .map_err(|e| format!("Failed to add service to tracking: {}", e))
+        .map_err(|e| format!("Failed to check database integrity: {}", e))?)
 }
 
 #[tauri::command]
-pub async fn remove_service_from_tracking(
+pub async fn vacuum_database(
     db: State<'_, Arc<Mutex<Database>>>,
-    name: String,
-) -> Result<(), String> {
+) -> Result<crate::database::DatabaseVacuum, CommandError> {
     let db = db.lock().await;
-    db.remove_tracked_service(&name)
+    Ok(db.vacuum()
         .await
-        .map_err(|e| format!("Failed to remove service from tracking: {}", e))
+        .map_err(|e| format!("Failed to vacuum database: {}", e))?)
 }
 
 #[tauri::command]
-pub async fn is_service_tracked(
+pub async fn set_service_config(
+    app_handle: tauri::AppHandle,
     db: State<'_, Arc<Mutex<Database>>>,
-    name: String,
-) -> Result<bool, String> {
+    service_name: String,
+    config_key: String,
+    config_value: String,
+    config_type: String,
+) -> Result<(), CommandError> {
+    validate_service_name(&service_name)?;
+
     let db = db.lock().await;
-    db.is_service_tracked(&name)
+    db.set_service_config(&service_name, &config_key, &config_value, &config_type)
         .await
-        .map_err(|e| format!("Failed to check if service is tracked: {}", e))
+        .map_err(|e| format!("Failed to set service config: {}", e))?;
+
+    crate::events::emit_config_changed(&app_handle, &service_name, &config_key);
+    Ok(())
 }
 
 #[tauri::command]
-pub async fn update_service_tracking_status(
+pub async fn set_service_configs(
+    app_handle: tauri::AppHandle,
     db: State<'_, Arc<Mutex<Database>>>,
-    name: String,
-    enabled: bool,
-) -> Result<(), String> {
+    service_name: String,
+    configs: Vec<crate::database::ServiceConfig>,
+) -> Result<(), CommandError> {
+    validate_service_name(&service_name)?;
+    if configs.is_empty() {
+        return Ok(());
+    }
+
+    // Everything is checked before the transaction starts so a bad value saves nothing
+    for config in &configs {
+        validate_config_value(&config.config_key, &config.config_value, &config.config_type)?;
+    }
+
     let db = db.lock().await;
-    db.update_service_enabled(&name, enabled)
+    db.set_service_configs(&service_name, &configs)
         .await
-        .map_err(|e| format!("Failed to update service tracking status: {}", e))
+        .map_err(|e| format!("Failed to set service configs: {}", e))?;
+
+    let keys: Vec<String> = configs.into_iter().map(|config| config.config_key).collect();
+    crate::events::emit_configs_changed(&app_handle, &service_name, &keys);
+    Ok(())
 }
 
-#[tauri::command]
-pub async fn get_all_system_services() -> Result<Vec<serde_json::Value>, String> {
-    let output = Command::new("systemctl")
-        .args(&["list-unit-files", "--type=service", "--no-pager", "--plain"])
-        .output()
-        .map_err(|e| format!("Failed to list services: {}", e))?;
+// Checks a value against the config_type the UI declared for it
+fn validate_config_value(key: &str, value: &str, config_type: &str) -> Result<(), CommandError> {
+    let valid = match config_type {
+        "string" => true,
+        "number" => value.trim().parse::<f64>().is_ok_and(|number| number.is_finite()),
+        "boolean" => matches!(value, "true" | "false"),
+        "json" => serde_json::from_str::<serde_json::Value>(value).is_ok(),
+        other => return Err(CommandError::InvalidInput(format!("Unknown config type '{}' for {}", other, key))),
+    };
 
-    if !output.status.success() {
-        return Err(format!("Failed to get services: {}", 
-            String::from_utf8_lossy(&output.stderr)));
+    if valid {
+        Ok(())
+    } else {
+        Err(CommandError::InvalidInput(format!("Invalid {} value for {}: '{}'", config_type, key, value)))
     }
+}
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    let mut services = Vec::new();
+// Always yields a value: the stored one, or the default (written back only when persist_default is set)
+#[tauri::command]
+pub async fn get_service_config_or_default(
+    app_handle: tauri::AppHandle,
+    db: State<'_, Arc<Mutex<Database>>>,
+    service_name: String,
+    config_key: String,
+    default_value: String,
+    config_type: String,
+    persist_default: Option<bool>,
+) -> Result<crate::database::ServiceConfig, CommandError> {
+    validate_service_name(&service_name)?;
 
-    for line in stdout.lines() {
-        let parts: Vec<&str> = line.split_whitespace().collect();
-        if parts.len() >= 2 && parts[0].ends_with(".service") {
-            let name = parts[0].replace(".service", "");
-            let status = parts[1];
-            
-            // Skip template services and some system services
-            if name.contains("@") || 
-               name.starts_with("systemd-") ||
-               name.starts_with("dbus-") ||
-               name.starts_with("user@") ||
-               name.starts_with("session-") ||
-               name.starts_with("user-runtime-dir") ||
-               name.starts_with("user-slice") ||
-               name.starts_with("user-") ||
-               name.starts_with("systemd-user-sessions") ||
-               name.starts_with("systemd-logind") ||
-               name.starts_with("systemd-udevd") ||
-               name.starts_with("systemd-resolved") ||
-               name.starts_with("systemd-timesyncd") ||
-               name.starts_with("systemd-random-seed") ||
-               name.starts_with("systemd-machine-id-commit") ||
-               name.starts_with("systemd-journald") ||
-               name.starts_with("systemd-journal-flush") ||
-               name.starts_with("systemd-journal-catalog-update") ||
-               name.starts_with("systemd-journal-remote") ||
-               name.starts_with("systemd-journal-upload") ||
-               name.starts_with("systemd-journal-gatewayd") ||
-               name.starts_with("systemd-journal-export") ||
-               name.starts_with("systemd-journal-import") ||
-               name.starts_with("systemd-journal-verify") ||
-               name.starts_with("systemd-journal-rotate") ||
-               name.starts_with("systemd-journal-remote") ||
-               name.starts_with("systemd-journal-upload") ||
-               name.starts_with("systemd-journal-gatewayd") ||
-               name.starts_with("systemd-journal-export") ||
-               name.starts_with("systemd-journal-import") ||
-               name.starts_with("systemd-journal-verify") ||
-               name.starts_with("systemd-journal-rotate") {
-                continue;
-            }
-            
-            // Get real-time status
-            let real_status = check_service_status(&format!("{}.service", name))
-                .unwrap_or(ServiceStatus::Unknown);
-            
-            let status_str = match real_status {
-                ServiceStatus::Running => "running",
-                ServiceStatus::Stopped => "stopped",
-                ServiceStatus::Failed => "failed",
-                ServiceStatus::Unknown => "unknown",
-            };
-            
-            // Get description based on service name
-            let description = generate_service_description(&name);
-            
-            services.push(serde_json::json!({
-                "name": name,
-                "service_name": parts[0],
-                "status": status_str,
-                "enabled_status": status,
-                "category": get_service_category(&name),
-                "enabled": status == "enabled" || status == "static",
-                "description": description
-            }));
-        }
+    let db = db.lock().await;
+    if let Some(config) = db.get_service_config(&service_name, &config_key)
+        .await
+        .map_err(|e| format!("Failed to get service config: {}", e))?
+    {
+        return Ok(config);
     }
 
-    // Sort by name
-    services.sort_by(|a, b| {
-        a.get("name").unwrap().as_str().unwrap()
-            .cmp(b.get("name").unwrap().as_str().unwrap())
-    });
+    if persist_default.unwrap_or(false) {
+        db.set_service_config(&service_name, &config_key, &default_value, &config_type)
+            .await
+            .map_err(|e| format!("Failed to store default service config: {}", e))?;
+        crate::events::emit_config_changed(&app_handle, &service_name, &config_key);
+    }
 
-    Ok(services)
+    Ok(crate::database::ServiceConfig {
+        service_name,
+        config_key,
+        config_value: default_value,
+        config_type,
+    })
 }
 
 #[tauri::command]
-pub async fn set_service_config(
+pub async fn delete_service_config(
+    app_handle: tauri::AppHandle,
     db: State<'_, Arc<Mutex<Database>>>,
     service_name: String,
     config_key: String,
-    config_value: String,
-    config_type: String,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
+    validate_service_name(&service_name)?;
+
     let db = db.lock().await;
-    db.set_service_config(&service_name, &config_key, &config_value, &config_type)
+    let deleted = db.delete_service_config(&service_name, &config_key)
         .await
-        .map_err(|e| format!("Failed to set service config: {}", e))
+        .map_err(|e| format!("Failed to delete service config: {}", e))?;
+    if !deleted {
+        return Err(CommandError::NotFound(format!("No config '{}' for {}", config_key, service_name)));
+    }
+
+    crate::events::emit_config_changed(&app_handle, &service_name, &config_key);
+    Ok(())
 }
 
 #[tauri::command]
 pub async fn get_service_configs(
     db: State<'_, Arc<Mutex<Database>>>,
     service_name: String,
-) -> Result<Vec<crate::database::ServiceConfig>, String> {
+) -> Result<Vec<crate::database::ServiceConfig>, CommandError> {
+    validate_service_name(&service_name)?;
+
     let db = db.lock().await;
-    db.get_service_configs(&service_name)
+    Ok(db.get_service_configs(&service_name)
         .await
-        .map_err(|e| format!("Failed to get service configs: {}", e))
+        .map_err(|e| format!("Failed to get service configs: {}", e))?)
+}
+
+const DEFAULT_APP_LOG_LIMIT: usize = 500;
+
+// The manager's own log records, for debugging the app rather than a service
+#[tauri::command]
+pub async fn get_app_logs(level: Option<String>, limit: Option<usize>) -> Result<Vec<AppLogEntry>, CommandError> {
+    let min_level = match level.as_deref() {
+        Some(level) => level
+            .parse::<log::Level>()
+            .map_err(|_| CommandError::InvalidInput(format!("Unknown log level: {}", level)))?,
+        None => log::Level::Trace,
+    };
+
+    Ok(crate::app_log::recent(min_level, limit.unwrap_or(DEFAULT_APP_LOG_LIMIT)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strip_ansi_codes_removes_csi_sequences() {
+        assert_eq!(strip_ansi_codes("\u{1b}[1;31mfailed\u{1b}[0m"), "failed");
+        assert_eq!(strip_ansi_codes("a\u{1b}[2Kb\u{1b}[10;20Hc\u{1b}[?25l"), "abc");
+        assert_eq!(strip_ansi_codes("\u{9b}32mok"), "ok");
+    }
+
+    #[test]
+    fn strip_ansi_codes_removes_osc_strings() {
+        assert_eq!(strip_ansi_codes("\u{1b}]0;title\u{07}prompt"), "prompt");
+        assert_eq!(strip_ansi_codes("\u{1b}]8;;https://example.com\u{1b}\\link\u{1b}]8;;\u{1b}\\"), "link");
+    }
+
+    #[test]
+    fn strip_ansi_codes_removes_charset_designators() {
+        assert_eq!(strip_ansi_codes("\u{1b}(Bplain\u{1b})0"), "plain");
+    }
+
+    #[test]
+    fn strip_ansi_codes_drops_truncated_escape() {
+        assert_eq!(strip_ansi_codes("done\u{1b}"), "done");
+        assert_eq!(strip_ansi_codes("done\u{1b}[1;3"), "done");
+    }
+
+    #[test]
+    fn sudo_refusal_only_matches_sudo_lines() {
+        assert_eq!(
+            sudo_refusal("Sorry, try again.\nsudo: 1 incorrect password attempt\n"),
+            Some("sudo: 1 incorrect password attempt")
+        );
+        assert_eq!(sudo_refusal("sudo: a password is required"), Some("sudo: a password is required"));
+        // The command's own failure is not a refused password
+        assert_eq!(sudo_refusal("rm: cannot remove '/x': Permission denied"), None);
+        assert_eq!(sudo_refusal("Failed to stop nginx.service: Unit nginx.service not loaded."), None);
+    }
+
+    const MALICIOUS_NAMES: [&str; 6] = ["-foo", "../x", "a;b", "$(x)", "nginx\nrm", "nginx\0"];
+
+    #[test]
+    fn validate_service_name_rejects_malicious_names() {
+        for name in MALICIOUS_NAMES {
+            assert!(
+                matches!(validate_service_name(name), Err(CommandError::InvalidInput(_))),
+                "{:?} should be rejected",
+                name
+            );
+        }
+    }
+
+    // docker passes the part after the prefix on its own command line
+    #[test]
+    fn validate_service_name_rejects_dash_prefixed_containers() {
+        for name in ["docker:-x", "docker:--follow"] {
+            assert!(
+                matches!(validate_service_name(name), Err(CommandError::InvalidInput(_))),
+                "{:?} should be rejected",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn validate_service_name_accepts_unit_names() {
+        for name in ["nginx", "nginx.service", "getty@tty1.service", "docker:redis", "systemd-journald"] {
+            assert!(validate_service_name(name).is_ok(), "{:?} should be accepted", name);
+        }
+    }
+
+    // Validation runs before the unit is looked up, so nothing is spawned for these
+    #[tokio::test]
+    async fn operations_reject_malicious_names_before_running_anything() {
+        for name in MALICIOUS_NAMES {
+            let result = start_with_password(name.to_string(), None, false).await;
+            assert!(matches!(result, Err(CommandError::InvalidInput(_))), "{:?} should be rejected", name);
+        }
+    }
 }
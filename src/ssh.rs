@@ -0,0 +1,212 @@
+use std::io::{ErrorKind, Read};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use ssh2::{CheckResult, KnownHostFileKind, Session};
+use crate::database::Host;
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+// Applies to every blocking libssh2 call once connected, in milliseconds
+const SESSION_TIMEOUT_MS: u32 = 30_000;
+
+// Result of a command run on a remote host, shaped like std::process::Output
+pub struct RemoteOutput {
+    pub exit_status: i32,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+impl RemoteOutput {
+    pub fn success(&self) -> bool {
+        self.exit_status == 0
+    }
+}
+
+// Runs the same commands as the local backend, but over an SSH session
+pub struct SshBackend {
+    session: Session,
+    label: String,
+    is_root: bool,
+}
+
+impl SshBackend {
+    pub fn connect(host: &Host) -> Result<Self, String> {
+        let label = format!("{}@{}:{}", host.user, host.address, host.port);
+        log::info!("🔐 Connecting to remote host {} ({})", host.name, label);
+
+        let unreachable = |reason: String| format!("Host unreachable: {} ({}): {}", host.name, label, reason);
+
+        let address = (host.address.as_str(), host.port)
+            .to_socket_addrs()
+            .map_err(|e| unreachable(e.to_string()))?
+            .next()
+            .ok_or_else(|| unreachable("address did not resolve".to_string()))?;
+        let tcp = TcpStream::connect_timeout(&address, CONNECT_TIMEOUT)
+            .map_err(|e| unreachable(e.to_string()))?;
+
+        let mut session = Session::new().map_err(|e| unreachable(e.to_string()))?;
+        session.set_tcp_stream(tcp);
+        session.set_timeout(SESSION_TIMEOUT_MS);
+        session.handshake().map_err(|e| unreachable(e.to_string()))?;
+
+        // Commands (sudo ones included) only go to a host whose key the user has already accepted
+        Self::verify_host_key(&session, &host.address, host.port)
+            .map_err(|e| format!("Host key verification failed for {}: {}", label, e))?;
+
+        Self::authenticate(&session, &host.user)
+            .map_err(|e| format!("Authentication failed for {}: {}", label, e))?;
+
+        log::info!("✅ Connected to {}", label);
+        Ok(Self {
+            session,
+            label,
+            is_root: host.user == "root",
+        })
+    }
+
+    // Checks the server's key against ~/.ssh/known_hosts. Unknown hosts are refused rather than
+    // trusted on first use; connecting once with ssh adds them.
+    fn verify_host_key(session: &Session, address: &str, port: u16) -> Result<(), String> {
+        let (key, _) = session.host_key().ok_or("the server sent no host key")?;
+
+        let known_hosts_file = ssh_dir().join("known_hosts");
+        let mut known_hosts = session.known_hosts().map_err(|e| e.to_string())?;
+        if known_hosts_file.exists() {
+            known_hosts
+                .read_file(&known_hosts_file, KnownHostFileKind::OpenSSH)
+                .map_err(|e| format!("failed to read {}: {}", known_hosts_file.display(), e))?;
+        }
+
+        match known_hosts.check_port(address, port, key) {
+            CheckResult::Match => Ok(()),
+            CheckResult::NotFound => Err(format!(
+                "{} is not in {}; connect once with ssh to verify and add its key",
+                address, known_hosts_file.display()
+            )),
+            CheckResult::Mismatch => {
+                log::error!("❌ Host key for {}:{} does not match known_hosts", address, port);
+                Err(format!("the key of {} does not match {}; the host may be impersonated", address, known_hosts_file.display()))
+            }
+            CheckResult::Failure => Err("could not check the host key".to_string()),
+        }
+    }
+
+    // Tries the SSH agent first, then the usual default key files
+    fn authenticate(session: &Session, user: &str) -> Result<(), String> {
+        if session.userauth_agent(user).is_ok() && session.authenticated() {
+            return Ok(());
+        }
+
+        let ssh_dir = ssh_dir();
+        for key in ["id_ed25519", "id_ecdsa", "id_rsa"] {
+            let private_key = ssh_dir.join(key);
+            if !private_key.exists() {
+                continue;
+            }
+            if session.userauth_pubkey_file(user, None, &private_key, None).is_ok() && session.authenticated() {
+                log::debug!("🔑 Authenticated with {}", private_key.display());
+                return Ok(());
+            }
+        }
+
+        Err("no usable key in the SSH agent or ~/.ssh".to_string())
+    }
+
+    // Runs a command built for the local backend; arguments are quoted for the remote shell
+    pub fn run(&self, args: &[&str]) -> Result<RemoteOutput, String> {
+        let command = args.iter().map(|arg| shell_quote(arg)).collect::<Vec<_>>().join(" ");
+        log::debug!("🔐 [{}] {}", self.label, command);
+
+        let mut channel = self
+            .session
+            .channel_session()
+            .map_err(|e| format!("Host unreachable: {}: {}", self.label, e))?;
+        channel
+            .exec(&command)
+            .map_err(|e| format!("Failed to run command on {}: {}", self.label, e))?;
+        channel.send_eof().ok();
+
+        // Both streams share one window, so reading stdout to EOF first deadlocks a command that
+        // fills stderr; read whichever has data until the remote closes both
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let mut buffer = [0u8; 8192];
+        let deadline = Instant::now() + Duration::from_millis(SESSION_TIMEOUT_MS as u64);
+        self.session.set_blocking(false);
+        let mut eof_seen = false;
+        let drained = loop {
+            let read = read_available(&mut channel, &mut buffer, &mut stdout).and_then(|read_stdout| {
+                let read_stderr = read_available(&mut channel.stderr(), &mut buffer, &mut stderr)?;
+                Ok(read_stdout || read_stderr)
+            });
+            match read {
+                Err(e) => break Err(format!("Failed to read output from {}: {}", self.label, e)),
+                Ok(true) => {}
+                // One more pass after EOF picks up anything the last read let in
+                Ok(false) if channel.eof() => {
+                    if eof_seen {
+                        break Ok(());
+                    }
+                    eof_seen = true;
+                }
+                Ok(false) if Instant::now() > deadline => {
+                    break Err(format!("Timed out reading output from {}", self.label));
+                }
+                Ok(false) => std::thread::sleep(Duration::from_millis(10)),
+            }
+        };
+        self.session.set_blocking(true);
+        drained?;
+        channel.wait_close().ok();
+        let stdout = String::from_utf8_lossy(&stdout).into_owned();
+        let stderr = String::from_utf8_lossy(&stderr).into_owned();
+
+        let exit_status = channel
+            .exit_status()
+            .map_err(|e| format!("Failed to get exit status from {}: {}", self.label, e))?;
+
+        Ok(RemoteOutput { exit_status, stdout, stderr })
+    }
+
+    // Runs a privileged command; non-root users need passwordless sudo for systemctl
+    pub fn run_privileged(&self, args: &[&str]) -> Result<RemoteOutput, String> {
+        if self.is_root {
+            return self.run(args);
+        }
+
+        let mut sudo_args = vec!["sudo", "-n"];
+        sudo_args.extend_from_slice(args);
+        self.run(&sudo_args)
+    }
+}
+
+// Appends whatever a non-blocking stream has ready; true when anything was read
+fn read_available(stream: &mut impl Read, buffer: &mut [u8], target: &mut Vec<u8>) -> std::io::Result<bool> {
+    match stream.read(buffer) {
+        Ok(read) => {
+            target.extend_from_slice(&buffer[..read]);
+            Ok(read > 0)
+        }
+        Err(e) if e.kind() == ErrorKind::WouldBlock => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+fn ssh_dir() -> PathBuf {
+    dirs::home_dir()
+        .map(|home| home.join(".ssh"))
+        .unwrap_or_else(|| PathBuf::from(".ssh"))
+}
+
+// Single-quotes an argument for a POSIX shell unless it only contains safe characters
+fn shell_quote(arg: &str) -> String {
+    let safe = !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./=:@%+,".contains(c));
+    if safe {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', "'\\''"))
+    }
+}
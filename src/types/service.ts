@@ -6,9 +6,13 @@ export interface Service {
   uptime?: string
   last_started?: string
   description: string
+  restart_count?: number
+  last_result?: string
+  tracked?: boolean
+  display_name?: string
 }
 
-export type ServiceStatus = 'Running' | 'Stopped' | 'Failed' | 'Unknown'
+export type ServiceStatus = 'Running' | 'Stopped' | 'Failed' | 'Unknown' | 'SocketActivated' | 'Activating' | 'Deactivating' | 'Reloading'
 
 export interface ServiceOperation {
   success: boolean
@@ -16,12 +20,84 @@ export interface ServiceOperation {
   service?: Service
 }
 
+// One restart per failed unit; recovered counts the ones no longer failed afterwards
+export interface RestartFailedSummary {
+  operations: ServiceOperation[]
+  recovered: number
+  still_failed: number
+}
+
+export type PackageManager = 'Apt' | 'Dnf' | 'Yum' | 'Zypper' | 'Pacman'
+
+// What remove_service would uninstall, from get_service_package
+export interface ServicePackage {
+  service_name: string
+  package: string
+  package_manager: PackageManager
+  unit_file: string
+}
+
+// services lists the units the package installed, for offering to track them
+export interface PackageInstall {
+  package: string
+  success: boolean
+  message: string
+  services: string[]
+}
+
+// Payload of the install-progress and remove-progress events, one per output line
+export interface PackageProgress {
+  package: string
+  stream: 'stdout' | 'stderr'
+  line: string
+  timestamp: string
+}
+
+// From check_service_update; candidate_version is only set when an upgrade is available
+export interface ServiceUpdate {
+  service_name: string
+  package: string
+  package_manager: PackageManager
+  current_version: string
+  candidate_version?: string
+  update_available: boolean
+}
+
+// A field that differs between two views of a service, from diff_services or diff_service_across_hosts
+export interface ServiceFieldDiff {
+  field: 'status' | 'enabled' | 'uptime' | 'description' | 'restart_count' | 'last_result' | 'version'
+  a_value?: string
+  b_value?: string
+}
+
+export type UndoableAction = 'Start' | 'Stop' | 'Enable' | 'Disable'
+
+// An operation undo_last_operation can reverse, with the state it replaced
+export interface UndoEntry {
+  service_name: string
+  action: UndoableAction
+  previous_state: string
+  user_unit: boolean
+  timestamp: string
+}
+
+export interface UndoResult {
+  undone: UndoEntry
+  operation: ServiceOperation
+}
+
 export interface ServiceLogs {
   service_name: string
   logs: string[]
   timestamp: string
 }
 
+// Used by get_service_logs when lines or since is omitted
+export interface LogDefaults {
+  lines: number
+  since: string
+}
+
 export interface SystemLogs {
   service_name: string
   system_logs: string[]
@@ -30,6 +106,15 @@ export interface SystemLogs {
   timestamp: string
 }
 
+export type SyslogLevel = 'emerg' | 'alert' | 'crit' | 'err' | 'warning' | 'notice' | 'info' | 'debug'
+
+export interface KernelLogEntry {
+  timestamp: string
+  level: SyslogLevel
+  priority: number
+  message: string
+}
+
 export interface SystemMetrics {
   service_name: string
   cpu_usage: number
@@ -41,10 +126,114 @@ export interface SystemMetrics {
   disk_write: number
   process_count: number
   open_files: number
+  open_files_sampled?: boolean
+  timestamp: string
+  display: SystemMetricsDisplay
+}
+
+export interface SystemMetricsDisplay {
+  memory_usage_human: string
+  memory_total_human: string
+  memory_percent: number
+  network_in_human: string
+  network_out_human: string
+  disk_read_human: string
+  disk_write_human: string
+}
+
+// Configured resource caps; null means unlimited
+export interface ServiceLimits {
+  service_name: string
+  memory_max: number | null
+  memory_high: number | null
+  cpu_quota_percent: number | null
+  // Quota as a percent of all cores, the same scale as cpu_usage
+  cpu_quota_share: number | null
+  tasks_max: number | null
+  open_files_max: number | null
+}
+
+export type SettableLimit = 'MemoryMax' | 'MemoryHigh' | 'CPUQuota' | 'TasksMax' | 'LimitNOFILE'
+
+export interface ServiceLimitUpdate {
+  success: boolean
+  message: string
+  restart_required: boolean
+  limits?: ServiceLimits
+}
+
+// A record from the manager's own log, returned by get_app_logs
+export interface AppLogEntry {
   timestamp: string
+  level: 'ERROR' | 'WARN' | 'INFO' | 'DEBUG' | 'TRACE'
+  module?: string
+  message: string
+}
+
+export interface MetricsSnapshot {
+  service_name: string
+  cpu_usage: number
+  memory_usage: number
+  network_in: number
+  network_out: number
+  disk_read: number
+  disk_write: number
+  recorded_at: string
+}
+
+// Deltas between the two snapshots nearest the requested times
+export interface MetricsDiff {
+  service_name: string
+  from: MetricsSnapshot
+  to: MetricsSnapshot
+  elapsed_secs: number
+  cpu_change: number
+  memory_change: number
+  disk_read_delta: number
+  disk_write_delta: number
+  network_in_delta: number
+  network_out_delta: number
+}
+
+export type ConfigFileSource = 'ExecStartFlag' | 'ExecStartArgument' | 'EnvironmentFile' | 'Convention'
+
+// A file get_service_config_files thinks the service reads; exists is false for paths it names but that are missing
+export interface ConfigFileCandidate {
+  path: string
+  source: ConfigFileSource
+  exists: boolean
+}
+
+// Returned by read_text_file; truncated means contents stops at max_bytes
+export interface TextFileContents {
+  path: string
+  contents: string
+  size: number
+  modified?: string
+  truncated: boolean
+}
+
+export interface ProcessInfo {
+  pid: number
+  name: string
+  command_line: string
+  state: string
+  kernel_thread: boolean
+  // Pass back to stop_process_by_pid so a reused PID isn't signalled
+  start_time: number
+}
+
+// terminated is whether the process exited within a few seconds of the signal
+export interface ProcessStopResult {
+  pid: number
+  name: string
+  command_line: string
+  signal: string
+  terminated: boolean
 }
 
 export interface TerminalCommand {
+  job_id: string
   command: string
   output: string
   exit_code: number
@@ -52,7 +241,36 @@ export interface TerminalCommand {
   duration_ms: number
 }
 
+export type TerminalJobState = 'Running' | 'Exited' | 'Killed'
+
+export interface TerminalJobStatus {
+  job_id: string
+  command: string
+  state: TerminalJobState
+  exit_code?: number
+  output: string
+  started_at: string
+  duration_ms: number
+}
+
 export interface TerminalSession {
   commands: TerminalCommand[]
   current_directory: string
-}
\ No newline at end of file
+}
+export type CommandErrorCode = 'NotFound' | 'PermissionDenied' | 'BackendUnavailable' | 'InvalidInput' | 'Cancelled' | 'Internal'
+
+// Shape of every error a backend command rejects with
+export interface CommandError {
+  code: CommandErrorCode
+  message: string
+}
+
+export const isCommandError = (error: unknown): error is CommandError =>
+  typeof error === 'object' && error !== null && 'code' in error && 'message' in error
+
+export const errorMessage = (error: unknown): string => {
+  if (isCommandError(error) || error instanceof Error) {
+    return error.message
+  }
+  return String(error)
+}